@@ -0,0 +1,148 @@
+use std::{collections::BTreeMap, fmt::Display, io, path::Path};
+
+use crate::solution::Params;
+use crate::toml;
+
+pub static ANSWERS_FILE: &str = "answers.toml";
+
+// the known answers for a set of inputs, stored as a small toml file:
+//
+// [d01]
+// part_1 = "67450"
+// part_2 = "199357"
+//
+// answers are always kept as strings, so every type an Answer can have round trips. the parameters
+// a day's puzzle gives for these inputs are kept next to them, e.g. for the example of day 15:
+//
+// [d15]
+// param.area = "20"
+// param.row = "10"
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Answers {
+    answers: BTreeMap<(u8, u8), String>,
+    params: BTreeMap<u8, Params>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verdict {
+    Pass,
+    Fail(String),
+    New,
+}
+
+impl Verdict {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Verdict::Pass => "PASS",
+            Verdict::Fail(_) => "FAIL",
+            Verdict::New => "NEW",
+        }
+    }
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Verdict::Pass => write!(f, "PASS"),
+            Verdict::Fail(expected) => write!(f, "FAIL (expected {})", expected),
+            Verdict::New => write!(f, "NEW"),
+        }
+    }
+}
+
+impl Answers {
+    // a missing file is the same as an empty one, so the first --record can create it
+    pub fn load(path: &Path) -> io::Result<Self> {
+        match std::fs::read_to_string(path) {
+            Ok(content) => Self::parse(&content).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e)),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e),
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        std::fs::write(path, self.to_toml())
+    }
+
+    pub fn get(&self, day: u8, part: u8) -> Option<&str> {
+        self.answers.get(&(day, part)).map(String::as_str)
+    }
+
+    pub fn insert(&mut self, day: u8, part: u8, answer: String) {
+        self.answers.insert((day, part), answer);
+    }
+
+    // none for inputs that need none, like the real ones
+    pub fn params(&self, day: u8) -> Params {
+        self.params.get(&day).cloned().unwrap_or_default()
+    }
+
+    pub fn set_param(&mut self, day: u8, name: &str, value: String) {
+        self.params.entry(day).or_default().insert(name.to_string(), value);
+    }
+
+    pub fn check(&self, day: u8, part: u8, answer: &str) -> Verdict {
+        match self.get(day, part) {
+            Some(expected) if expected == answer => Verdict::Pass,
+            Some(expected) => Verdict::Fail(expected.to_string()),
+            None => Verdict::New,
+        }
+    }
+
+    pub fn parse(content: &str) -> Result<Self, String> {
+        let mut answers = Self::default();
+
+        for entry in toml::parse(content)? {
+            let value = toml::unquote(entry.value).ok_or(format!("line {}: invalid string {}", entry.line, entry.value))?;
+            if let Some(name) = entry.key.strip_prefix("param.") {
+                answers.set_param(entry.day, name, value);
+                continue;
+            }
+            let part = entry.key.strip_prefix("part_")
+                .and_then(|p| p.parse::<u8>().ok())
+                .ok_or(format!("line {}: unknown key '{}', expected part_1, part_2 or param.<name>", entry.line, entry.key))?;
+            answers.insert(entry.day, part, value);
+        }
+
+        Ok(answers)
+    }
+
+    pub fn to_toml(&self) -> String {
+        let params = self.params.iter().flat_map(|(&day, params)| params.iter().map(move |(name, value)| (day, format!("param.{}", name), value)));
+        let answers = self.answers.iter().map(|(&(day, part), answer)| (day, format!("part_{}", part), answer));
+        let mut entries = params.chain(answers).collect::<Vec<_>>();
+        entries.sort();
+        toml::write(entries.into_iter().map(|(day, key, value)| (day, key, toml::quote(value))))
+    }
+}
+
+#[test]
+fn test_answers() {
+    let mut answers = Answers::default();
+    answers.insert(10, 2, "PZULBAUA".to_string());
+    answers.insert(1, 1, "67450".to_string());
+    answers.insert(1, 2, "199357".to_string());
+    answers.insert(3, 1, "a \"quoted\"\nscreen\\".to_string());
+    answers.insert(15, 1, "26".to_string());
+    answers.set_param(15, "row", "10".to_string());
+
+    let toml = answers.to_toml();
+    assert!(toml.starts_with("[d01]\npart_1 = \"67450\"\npart_2 = \"199357\"\n\n[d03]\n"));
+    assert!(toml.ends_with("[d15]\nparam.row = \"10\"\npart_1 = \"26\"\n"));
+    assert_eq!(Answers::parse(&toml), Ok(answers));
+}
+
+#[test]
+fn test_parse_answers() {
+    let answers = Answers::parse("# comment\n[d25]\npart_1 = \"2-==10--=-0101==1201\"\n").unwrap();
+    assert_eq!(answers.check(25, 1, "2-==10--=-0101==1201"), Verdict::Pass);
+    assert_eq!(answers.check(25, 1, "2-0"), Verdict::Fail("2-==10--=-0101==1201".to_string()));
+    assert_eq!(answers.check(1, 1, "67450"), Verdict::New);
+
+    assert!(Answers::parse("[d01]\npart_one = \"1\"").is_err());
+    assert!(Answers::parse("[d01]\npart_1 = 1").is_err());
+
+    let answers = Answers::parse("[d15]\nparam.row = \"10\"\nparam.area = \"20\"\n").unwrap();
+    assert_eq!(answers.params(15).get("row").map(String::as_str), Some("10"));
+    assert!(answers.params(16).is_empty());
+}
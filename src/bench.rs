@@ -0,0 +1,162 @@
+use std::{collections::BTreeMap, fmt::Display, hint::black_box, io, path::Path, time::{Duration, Instant}};
+
+use crate::toml;
+
+pub static DEFAULT_THRESHOLD: f64 = 10.0;
+
+// summary of a number of timed runs
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    pub p95: Duration,
+}
+
+impl Stats {
+    pub fn from_samples(samples: &mut [Duration]) -> Self {
+        assert!(!samples.is_empty(), "need at least one sample");
+        samples.sort();
+        let n = samples.len();
+        let median = if n.is_multiple_of(2) { (samples[n / 2 - 1] + samples[n / 2]) / 2 } else { samples[n / 2] };
+        // nearest rank
+        let p95 = samples[(n * 95).div_ceil(100) - 1];
+
+        Self { min: samples[0], median, mean: samples.iter().sum::<Duration>() / n as u32, p95 }
+    }
+}
+
+// runs f a few times without timing it, then returns the runtime of n more runs
+pub fn measure<T, F: FnMut() -> T>(n: usize, mut f: F) -> Vec<Duration> {
+    for _ in 0..(n / 10).max(1) {
+        black_box(f());
+    }
+
+    // the result is dropped after stopping the clock, freeing it is not part of the runtime
+    (0..n).map(|_| {
+        let start = Instant::now();
+        let result = black_box(f());
+        let elapsed = start.elapsed();
+        drop(result);
+        elapsed
+    }).collect()
+}
+
+// which part of a day a time belongs to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Phase {
+    Parse,
+    Part(u8),
+}
+
+impl Display for Phase {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Phase::Parse => write!(f, "parse"),
+            Phase::Part(part) => write!(f, "part_{}", part),
+        }
+    }
+}
+
+// median times of an earlier benchmark run, stored in the same kind of toml file as the answers:
+//
+// [d01]
+// parse = 105432
+// part_1 = 2410
+//
+// times are in nanoseconds
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Baseline(BTreeMap<(u8, Phase), u128>);
+
+impl Baseline {
+    pub fn load(path: &Path) -> io::Result<Self> {
+        let content = std::fs::read_to_string(path)?;
+        Self::parse(&content).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        std::fs::write(path, self.to_toml())
+    }
+
+    pub fn insert(&mut self, day: u8, phase: Phase, time: Duration) {
+        self.0.insert((day, phase), time.as_nanos());
+    }
+
+    // the relative change in percent, if the baseline has a time for day and phase
+    pub fn change(&self, day: u8, phase: Phase, time: Duration) -> Option<f64> {
+        let &base = self.0.get(&(day, phase))?;
+        Some((time.as_nanos() as f64 - base as f64) / base.max(1) as f64 * 100.)
+    }
+
+    pub fn parse(content: &str) -> Result<Self, String> {
+        let mut baseline = Self::default();
+
+        for entry in toml::parse(content)? {
+            let phase = match entry.key {
+                "parse" => Phase::Parse,
+                key => Phase::Part(key.strip_prefix("part_")
+                    .and_then(|p| p.parse::<u8>().ok())
+                    .ok_or(format!("line {}: unknown key '{}', expected parse, part_1 or part_2", entry.line, key))?),
+            };
+            let time = entry.value.parse::<u128>().map_err(|_| format!("line {}: invalid time {}", entry.line, entry.value))?;
+            baseline.0.insert((entry.day, phase), time);
+        }
+
+        Ok(baseline)
+    }
+
+    pub fn to_toml(&self) -> String {
+        toml::write(self.0.iter().map(|(&(day, phase), time)| (day, phase, time)))
+    }
+}
+
+// short human readable duration, picks the unit so there are at most three digits before the point
+pub fn format_duration(d: Duration) -> String {
+    match d.as_nanos() {
+        n if n < 1_000 => format!("{}ns", n),
+        n if n < 1_000_000 => format!("{:.1}us", n as f64 / 1e3),
+        n if n < 1_000_000_000 => format!("{:.1}ms", n as f64 / 1e6),
+        n => format!("{:.2}s", n as f64 / 1e9),
+    }
+}
+
+#[test]
+fn test_stats() {
+    let mut samples = [5, 1, 4, 2, 3].map(Duration::from_micros);
+    let stats = Stats::from_samples(&mut samples);
+    assert_eq!(stats.min, Duration::from_micros(1));
+    assert_eq!(stats.median, Duration::from_micros(3));
+    assert_eq!(stats.mean, Duration::from_micros(3));
+    assert_eq!(stats.p95, Duration::from_micros(5));
+
+    let mut samples = (1..=100).map(Duration::from_micros).collect::<Vec<_>>();
+    let stats = Stats::from_samples(&mut samples);
+    assert_eq!(stats.median, Duration::from_nanos(50_500));
+    assert_eq!(stats.p95, Duration::from_micros(95));
+}
+
+#[test]
+fn test_baseline() {
+    let mut baseline = Baseline::default();
+    baseline.insert(1, Phase::Parse, Duration::from_micros(100));
+    baseline.insert(1, Phase::Part(1), Duration::from_micros(10));
+    baseline.insert(25, Phase::Part(1), Duration::from_micros(3));
+
+    let toml = baseline.to_toml();
+    assert_eq!(toml, "[d01]\nparse = 100000\npart_1 = 10000\n\n[d25]\npart_1 = 3000\n");
+    assert_eq!(Baseline::parse(&toml), Ok(baseline));
+
+    let baseline = Baseline::parse(&toml).unwrap();
+    assert_eq!(baseline.change(1, Phase::Part(1), Duration::from_micros(12)), Some(20.));
+    assert_eq!(baseline.change(1, Phase::Parse, Duration::from_micros(50)), Some(-50.));
+    assert_eq!(baseline.change(1, Phase::Part(2), Duration::from_micros(12)), None);
+    assert!(Baseline::parse("[d01]\nsolve = 3").is_err());
+}
+
+#[test]
+fn test_format_duration() {
+    assert_eq!(format_duration(Duration::from_nanos(999)), "999ns");
+    assert_eq!(format_duration(Duration::from_nanos(2_450)), "2.5us");
+    assert_eq!(format_duration(Duration::from_micros(1_500)), "1.5ms");
+    assert_eq!(format_duration(Duration::from_millis(6_300)), "6.30s");
+}
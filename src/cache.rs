@@ -0,0 +1,137 @@
+use std::{collections::BTreeMap, io, path::Path, time::Duration};
+
+use crate::{solution::Answer, toml};
+
+pub static CACHE_FILE: &str = ".cache.toml";
+
+// the answers of earlier runs, so that a part whose input and solver didn't change needn't be solved again.
+// an answer is found by day, part, implementation and the hash of the input, and only counts while the
+// solver's version is the same as when it was stored:
+//
+// [d16]
+// part_1.default.5e0a1c2b3d4f6789 = "9a8b7c6d5e4f3021 u32 109064000 1376"
+//
+// the value holds the version, the type of the answer, the runtime in nanoseconds and the answer
+#[derive(Debug, Default, PartialEq)]
+pub struct Cache(BTreeMap<Key, Cached>);
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Key {
+    pub day: u8,
+    pub part: u8,
+    pub implementation: String,
+    pub input: u64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Cached {
+    pub version: u64,
+    pub answer: Answer,
+    // of the run that computed the answer
+    pub runtime: Duration,
+}
+
+// FNV-1a, a const fn so that the registry can hash the solvers' sources at compile time
+pub const fn hash(bytes: &[u8]) -> u64 {
+    hash_from(0xcbf2_9ce4_8422_2325, bytes)
+}
+
+// goes on hashing after what gave hash, so sources can be hashed one after the other
+pub const fn hash_from(mut hash: u64, bytes: &[u8]) -> u64 {
+    let mut i = 0;
+    while i < bytes.len() {
+        hash ^= bytes[i] as u64;
+        hash = hash.wrapping_mul(0x0100_0000_01b3);
+        i += 1;
+    }
+    hash
+}
+
+impl Cache {
+    // a missing file is an empty cache
+    pub fn load(path: &Path) -> io::Result<Self> {
+        match std::fs::read_to_string(path) {
+            Ok(content) => Self::parse(&content).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e)),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e),
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        std::fs::write(path, self.to_toml())
+    }
+
+    // None if the answer was never stored or a different version of the solver stored it
+    pub fn get(&self, key: &Key, version: u64) -> Option<&Cached> {
+        self.0.get(key).filter(|cached| cached.version == version)
+    }
+
+    pub fn insert(&mut self, key: Key, cached: Cached) {
+        self.0.insert(key, cached);
+    }
+
+    pub fn parse(content: &str) -> Result<Self, String> {
+        let mut cache = Self::default();
+
+        for entry in toml::parse(content)? {
+            let invalid = || format!("line {}: expected part_N.<implementation>.<input hash> = \"<version> <type> <ns> <answer>\"", entry.line);
+            let [part, implementation, input] = entry.key.split('.').collect::<Vec<_>>()[..] else { return Err(invalid()) };
+            let value = toml::unquote(entry.value).ok_or_else(invalid)?;
+            let [version, kind, runtime, answer] = value.splitn(4, ' ').collect::<Vec<_>>()[..] else { return Err(invalid()) };
+
+            let key = Key {
+                day: entry.day,
+                part: part.strip_prefix("part_").and_then(|p| p.parse().ok()).ok_or_else(invalid)?,
+                implementation: implementation.to_string(),
+                input: u64::from_str_radix(input, 16).map_err(|_| invalid())?,
+            };
+            let cached = Cached {
+                version: u64::from_str_radix(version, 16).map_err(|_| invalid())?,
+                answer: Answer::parse(kind, answer).ok_or_else(invalid)?,
+                runtime: Duration::from_nanos(runtime.parse().map_err(|_| invalid())?),
+            };
+            cache.insert(key, cached);
+        }
+
+        Ok(cache)
+    }
+
+    pub fn to_toml(&self) -> String {
+        toml::write(self.0.iter().map(|(key, cached)| {
+            let value = format!("{:016x} {} {} {}", cached.version, cached.answer.kind(), cached.runtime.as_nanos(), cached.answer);
+            (key.day, format!("part_{}.{}.{:016x}", key.part, key.implementation, key.input), toml::quote(&value))
+        }))
+    }
+}
+
+#[test]
+fn test_hash() {
+    assert_eq!(hash(b""), 0xcbf2_9ce4_8422_2325);
+    assert_eq!(hash(b"a"), 0xaf63_dc4c_8601_ec8c);
+    assert_ne!(hash(b"d16"), hash(b"d17"));
+    assert_eq!(hash_from(hash(b"d1"), b"6"), hash(b"d16"));
+}
+
+#[test]
+fn test_cache() {
+    let key = Key { day: 10, part: 2, implementation: "default".to_string(), input: hash(b"noop\n") };
+    let cached = Cached { version: 7, answer: Answer::from("#..#\n.##.".to_string()), runtime: Duration::from_micros(23) };
+    let mut cache = Cache::default();
+    cache.insert(key.clone(), cached.clone());
+    cache.insert(Key { day: 1, part: 1, ..key.clone() }, Cached { answer: Answer::from(67450_usize), ..cached.clone() });
+
+    let toml = cache.to_toml();
+    assert_eq!(toml, "\
+[d01]
+part_1.default.7e31d471dc8ca505 = \"0000000000000007 usize 23000 67450\"
+
+[d10]
+part_2.default.7e31d471dc8ca505 = \"0000000000000007 string 23000 #..#\\n.##.\"
+");
+    let parsed = Cache::parse(&toml).unwrap();
+    assert_eq!(parsed, cache);
+    assert_eq!(parsed.get(&key, 7), Some(&cached));
+    assert_eq!(parsed.get(&key, 8), None);
+    assert!(Cache::parse("[d01]\npart_1 = \"7 usize 1 2\"").is_err());
+    assert!(Cache::parse("[d01]\npart_1.default.1 = \"7 usize 1 x\"").is_err());
+}
@@ -0,0 +1,791 @@
+use std::{path::PathBuf, time::Duration};
+
+use aoc22::{
+    answers::ANSWERS_FILE,
+    bench::DEFAULT_THRESHOLD,
+    frames::{self, Film},
+    generate,
+    input::{Source, DEFAULT_DATA_DIR},
+    registry::{self, Entry},
+    repl::{self, Explorer},
+    report::Format,
+};
+
+pub(crate) static USAGE: &str = "\
+usage: aoc22 [options]
+       aoc22 import [--data-dir <DIR>] [--answers <PATH>] <PAGE>...
+       aoc22 new-day <NN>
+       aoc22 generate <NN> [--seed <N>] [--size <N>] [--output <PATH>]
+       aoc22 compare [--day <DAYS>] [--data-dir <DIR>] [--seeds <N>]
+       aoc22 repl <NN> [--input <PATH>] [--data-dir <DIR>]
+       aoc22 animate <NN> [--input <PATH>] [--data-dir <DIR>] [--every <N>] [--frames <N>]
+                     [--fps <N>] [--scale <N>] [--output <PATH>]
+
+options:
+    -d, --day <DAYS>        run only the given days, e.g. `16`, `10-15` or `1,3,20-25`
+        --days <DAYS>       same as --day
+    -p, --part <PART>       run only part 1 or part 2 of the selected days
+    -i, --input <PATH>      read the puzzle input from PATH, `-` reads from stdin (needs a single day)
+        --data-dir <DIR>    read the inputs from DIR/dNN.txt [default: data]
+        --verify            compare the results against the recorded answers, prints PASS/FAIL/NEW
+        --record            write the results into the answers file
+        --answers <PATH>    the answers file used by --verify and --record, its param.<name> keys are
+                            passed to the days that take parameters [default: DIR/answers.toml]
+        --bench <N>         time parsing and solving N times each after a few warmup runs
+        --baseline <PATH>   compare the --bench times against PATH, or save them there if it does not exist
+        --threshold <PCT>   report times more than PCT percent slower than the baseline [default: 10]
+    -j, --jobs <N>          run the selected parts on N worker threads, each part parses its own input
+        --timeout <SECS>    report parts taking longer than SECS as TIMEOUT, uses all cores without --jobs.
+                            a part that timed out keeps running until the runner exits, next to the N
+                            parts of --jobs, so the parts after it can take longer than on their own
+        --format <FMT>      print the results as json, csv or markdown once all days are done
+        --matrix            run every input in DIR/dNN/*.txt, checking each against the .toml file next to it
+        --impl <NAME>       run the implementation NAME of the days that have one instead of the default
+        --memory            count the allocations, allocated bytes and peak live bytes of every part
+        --trace <PATH>      write the time spent in the phases of the solvers to PATH, as a Chrome
+                            trace if PATH ends in .json and as folded stacks for flamegraphs otherwise
+        --no-cache          solve every part even if its answer is cached in DIR/.cache.toml, which
+                            holds the answers of earlier runs by input and solver version. --verify
+                            and --record always solve every part
+    -h, --help              print this message
+
+import copies the examples of puzzle pages saved from the website to DIR/dNN_test.txt,
+DIR/dNN_test_2.txt and so on, and records the answers shown on the pages in the answers file.
+
+new-day adds src/dNN.rs with empty data files to the crate in the current directory, registers it
+and adds placeholder example answers, it never overwrites an existing module.
+
+generate writes a random input for day NN to stdout or PATH, the same seed always gives the same
+input [default seed: 0]. --size scales it and defaults to about the size of a real input:
+    day  7: directories      day 18: cubes
+    day  8: rows and columns day 20: numbers
+    day  9: moves            day 21: monkeys
+    day 13: pairs of packets day 23: rows and columns
+
+compare runs every implementation of the selected days on DIR/dNN.txt, the examples in DIR, the
+inputs in DIR/dNN/ and N generated inputs [default: 20] and lists the answers they disagree on.
+
+repl loads the input of day NN and reads commands from stdin: `step [N]` advances the day's
+simulation by N ticks, `show` prints its state and `help` lists the questions the day answers,
+like `size /a/e` on day 7. days 7, 9, 11, 14, 17, 23 and 24 can be explored.
+
+animate records the simulation of day NN on its input and plays it in the terminal at --fps
+frames per second [default: 10], or writes it to PATH as an animated GIF if PATH ends in .gif and
+as PATH_0000.ppm, PATH_0001.ppm and so on if it ends in .ppm, with --scale pixels per cell
+[default: 4]. only every Nth step becomes a frame [default: 1], up to --frames of them
+[default: 300]. days 12, 14, 17, 22, 23 and 24 can be animated.";
+
+const N_DAYS: u8 = 25;
+
+pub(crate) enum Command {
+    Run(Options),
+    Import(Import),
+    NewDay(u8),
+    Generate(Generate),
+    Compare(Compare),
+    Repl(Repl),
+    Animate(Animate),
+    Help,
+}
+
+// what to do with the results besides printing them
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Mode {
+    Run,
+    Verify,
+    Record,
+}
+
+pub(crate) struct Options {
+    pub(crate) selection: Selection,
+    pub(crate) input: Option<Source>,
+    pub(crate) data_dir: PathBuf,
+    pub(crate) mode: Mode,
+    pub(crate) answers: Option<PathBuf>,
+    pub(crate) bench: Option<Bench>,
+    // None prints the human readable table while running
+    pub(crate) format: Option<Format>,
+    // None runs the days one after the other on the main thread
+    pub(crate) pool: Option<Pool>,
+    // run the inputs in the day's directory instead of the single input of the day
+    pub(crate) matrix: bool,
+    // None runs the default implementation of every day
+    pub(crate) implementation: Option<String>,
+    pub(crate) memory: bool,
+    pub(crate) trace: Option<PathBuf>,
+    // take the answers of parts from the cache when neither their input nor their solver changed
+    pub(crate) cache: bool,
+}
+
+pub(crate) struct Pool {
+    pub(crate) jobs: usize,
+    pub(crate) timeout: Option<Duration>,
+}
+
+pub(crate) struct Bench {
+    pub(crate) runs: usize,
+    pub(crate) baseline: Option<PathBuf>,
+    pub(crate) threshold: f64,
+}
+
+impl Options {
+    // the input of a day, either given explicitly or taken from the data directory
+    pub(crate) fn source(&self, day: u8) -> Source {
+        self.input.clone().unwrap_or_else(|| Source::default_for(&self.data_dir, day))
+    }
+
+    pub(crate) fn answers_path(&self) -> PathBuf {
+        self.answers.clone().unwrap_or_else(|| self.data_dir.join(ANSWERS_FILE))
+    }
+
+    // the selected implementation of a day, or its default one if the day has no such implementation
+    pub(crate) fn entry(&self, day: u8) -> Option<&'static Entry> {
+        self.implementation.as_deref().and_then(|name| registry::find(day, name)).or_else(|| registry::default_for(day))
+    }
+}
+
+pub(crate) struct Import {
+    pub(crate) pages: Vec<PathBuf>,
+    pub(crate) data_dir: PathBuf,
+    pub(crate) answers: Option<PathBuf>,
+}
+
+impl Import {
+    pub(crate) fn answers_path(&self) -> PathBuf {
+        self.answers.clone().unwrap_or_else(|| self.data_dir.join(ANSWERS_FILE))
+    }
+}
+
+pub(crate) struct Generate {
+    pub(crate) generator: &'static generate::Generator,
+    pub(crate) seed: u64,
+    pub(crate) size: usize,
+    // None writes to stdout
+    pub(crate) output: Option<PathBuf>,
+}
+
+pub(crate) struct Compare {
+    pub(crate) days: Vec<u8>,
+    pub(crate) data_dir: PathBuf,
+    pub(crate) seeds: u64,
+}
+
+pub(crate) struct Repl {
+    pub(crate) explorer: &'static Explorer,
+    // the commands come from stdin, so the input can't
+    pub(crate) source: Source,
+}
+
+pub(crate) struct Animate {
+    pub(crate) film: &'static Film,
+    pub(crate) source: Source,
+    pub(crate) every: usize,
+    pub(crate) frames: usize,
+    pub(crate) fps: u32,
+    pub(crate) scale: usize,
+    // None plays the frames in the terminal
+    pub(crate) output: Option<PathBuf>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) struct Selection {
+    days: Vec<u8>,
+    part: Option<u8>,
+}
+
+impl Selection {
+    pub(crate) fn days(&self) -> &[u8] {
+        &self.days
+    }
+
+    pub(crate) fn contains(&self, day: u8, part: u8) -> bool {
+        self.days.contains(&day) && self.part.is_none_or(|p| p == part)
+    }
+}
+
+impl Default for Selection {
+    fn default() -> Self {
+        Self { days: (1..=N_DAYS).collect(), part: None }
+    }
+}
+
+pub(crate) fn parse_args<I: Iterator<Item = String>>(args: I) -> Result<Command, String> {
+    let mut args = args.peekable();
+    if args.next_if(|arg| arg == "import").is_some() {
+        return parse_import_args(args);
+    }
+    if args.next_if(|arg| arg == "new-day").is_some() {
+        let day = args.next().ok_or("new-day needs the number of the day")?;
+        return match args.next() {
+            Some(arg) => Err(format!("unknown argument '{}'", arg)),
+            None => Ok(Command::NewDay(parse_day(&day)?)),
+        };
+    }
+    if args.next_if(|arg| arg == "generate").is_some() {
+        return parse_generate_args(args);
+    }
+    if args.next_if(|arg| arg == "compare").is_some() {
+        return parse_compare_args(args);
+    }
+    if args.next_if(|arg| arg == "repl").is_some() {
+        return parse_repl_args(args);
+    }
+    if args.next_if(|arg| arg == "animate").is_some() {
+        return parse_animate_args(args);
+    }
+
+    let mut days = Vec::new();
+    let mut part = None;
+    let mut input = None;
+    let mut data_dir = PathBuf::from(DEFAULT_DATA_DIR);
+    let mut mode = Mode::Run;
+    let mut answers = None;
+    let mut runs = None;
+    let mut baseline = None;
+    let mut threshold = None;
+    let mut format = None;
+    let mut jobs = None;
+    let mut timeout = None;
+    let mut matrix = false;
+    let mut implementation = None;
+    let mut memory = false;
+    let mut trace = None;
+    let mut cache = true;
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-h" | "--help" => return Ok(Command::Help),
+            "-d" | "--day" | "--days" => {
+                let spec = next_value(&mut args, &arg)?;
+                days.append(&mut parse_days(&spec)?);
+            },
+            "-p" | "--part" => {
+                let value = next_value(&mut args, &arg)?;
+                part = match value.as_str() {
+                    "1" => Some(1),
+                    "2" => Some(2),
+                    _ => return Err(format!("unknown part '{}', expected 1 or 2", value)),
+                };
+            },
+            "-i" | "--input" => input = Some(Source::from(next_value(&mut args, &arg)?.as_str())),
+            "--data-dir" => data_dir = PathBuf::from(next_value(&mut args, &arg)?),
+            "--verify" | "--record" => {
+                let new_mode = if arg == "--verify" { Mode::Verify } else { Mode::Record };
+                if mode != Mode::Run && mode != new_mode {
+                    return Err("--verify and --record can not be used together".to_string());
+                }
+                mode = new_mode;
+            },
+            "--answers" => answers = Some(PathBuf::from(next_value(&mut args, &arg)?)),
+            "--bench" => {
+                let value = next_value(&mut args, &arg)?;
+                runs = match value.parse::<usize>() {
+                    Ok(n) if n > 0 => Some(n),
+                    _ => return Err(format!("invalid number of runs '{}'", value)),
+                };
+            },
+            "-j" | "--jobs" => {
+                let value = next_value(&mut args, &arg)?;
+                jobs = match value.parse::<usize>() {
+                    Ok(n) if n > 0 => Some(n),
+                    _ => return Err(format!("invalid number of jobs '{}'", value)),
+                };
+            },
+            "--timeout" => {
+                let value = next_value(&mut args, &arg)?;
+                timeout = match value.parse::<f64>() {
+                    Ok(secs) if secs > 0. && secs.is_finite() => Some(Duration::from_secs_f64(secs)),
+                    _ => return Err(format!("invalid timeout '{}', expected a number of seconds", value)),
+                };
+            },
+            "--format" => format = Some(next_value(&mut args, &arg)?.parse::<Format>()?),
+            "--matrix" => matrix = true,
+            "--impl" => implementation = Some(next_value(&mut args, &arg)?),
+            "--memory" => memory = true,
+            "--trace" => trace = Some(PathBuf::from(next_value(&mut args, &arg)?)),
+            "--no-cache" => cache = false,
+            "--baseline" => baseline = Some(PathBuf::from(next_value(&mut args, &arg)?)),
+            "--threshold" => {
+                let value = next_value(&mut args, &arg)?;
+                threshold = match value.parse::<f64>() {
+                    Ok(pct) if pct >= 0. => Some(pct),
+                    _ => return Err(format!("invalid threshold '{}', expected a percentage", value)),
+                };
+            },
+            _ => return Err(format!("unknown argument '{}'", arg)),
+        }
+    }
+
+    days.sort();
+    days.dedup();
+    if input.is_some() && days.len() != 1 {
+        return Err("--input needs exactly one day selected with --day".to_string());
+    }
+
+    let bench = match runs {
+        Some(runs) => Some(Bench { runs, baseline, threshold: threshold.unwrap_or(DEFAULT_THRESHOLD) }),
+        None if baseline.is_some() || threshold.is_some() => return Err("--baseline and --threshold need --bench".to_string()),
+        None => None,
+    };
+
+    let pool = match (jobs, timeout) {
+        (None, None) => None,
+        _ if bench.is_some() => return Err("--bench can not be used with --jobs or --timeout".to_string()),
+        (jobs, timeout) => Some(Pool {
+            jobs: jobs.unwrap_or_else(|| std::thread::available_parallelism().map_or(1, |n| n.get())),
+            timeout,
+        }),
+    };
+
+    // every input of the matrix has its own answers
+    if matrix && (input.is_some() || answers.is_some() || bench.is_some() || format.is_some()) {
+        return Err("--matrix can not be used with --input, --answers, --bench or --format".to_string());
+    }
+
+    // the counts are shared by all threads, only a single part may run at a time
+    if memory && (pool.is_some() || bench.is_some() || matrix) {
+        return Err("--memory can not be used with --jobs, --timeout, --bench or --matrix".to_string());
+    }
+
+    let selection = if days.is_empty() {
+        Selection { part, ..Default::default() }
+    } else {
+        Selection { days, part }
+    };
+    if let Some(name) = &implementation {
+        let entries = || registry::REGISTRY.iter().filter(|e| e.name != "default");
+        if !entries().any(|e| e.name == *name && selection.days.contains(&e.day)) {
+            let known = entries().map(|e| format!("{} (day {})", e.name, e.day)).collect::<Vec<_>>();
+            return Err(format!("none of the selected days has an implementation '{}', there are {}", name, known.join(", ")));
+        }
+    }
+    // checking or recording the answers has to be about what the code produces now
+    let cache = cache && matches!(mode, Mode::Run);
+    Ok(Command::Run(Options { selection, input, data_dir, mode, answers, bench, format, pool, matrix, implementation, memory, trace, cache }))
+}
+
+fn parse_import_args<I: Iterator<Item = String>>(mut args: I) -> Result<Command, String> {
+    let mut pages = Vec::new();
+    let mut data_dir = PathBuf::from(DEFAULT_DATA_DIR);
+    let mut answers = None;
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-h" | "--help" => return Ok(Command::Help),
+            "--data-dir" => data_dir = PathBuf::from(next_value(&mut args, &arg)?),
+            "--answers" => answers = Some(PathBuf::from(next_value(&mut args, &arg)?)),
+            _ if arg.starts_with('-') => return Err(format!("unknown argument '{}'", arg)),
+            _ => pages.push(PathBuf::from(arg)),
+        }
+    }
+
+    if pages.is_empty() {
+        return Err("import needs at least one saved puzzle page".to_string());
+    }
+    Ok(Command::Import(Import { pages, data_dir, answers }))
+}
+
+fn parse_generate_args<I: Iterator<Item = String>>(mut args: I) -> Result<Command, String> {
+    let mut day = None;
+    let mut seed = 0;
+    let mut size = None;
+    let mut output = None;
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-h" | "--help" => return Ok(Command::Help),
+            "--seed" => {
+                let value = next_value(&mut args, &arg)?;
+                seed = value.parse::<u64>().map_err(|_| format!("invalid seed '{}'", value))?;
+            },
+            "--size" => {
+                let value = next_value(&mut args, &arg)?;
+                size = match value.parse::<usize>() {
+                    Ok(n) if n > 0 => Some(n),
+                    _ => return Err(format!("invalid size '{}'", value)),
+                };
+            },
+            "-o" | "--output" => output = Some(PathBuf::from(next_value(&mut args, &arg)?)),
+            _ if arg.starts_with('-') || day.is_some() => return Err(format!("unknown argument '{}'", arg)),
+            _ => day = Some(parse_day(&arg)?),
+        }
+    }
+
+    let day = day.ok_or("generate needs the number of the day")?;
+    let generator = generate::for_day(day).ok_or_else(|| {
+        let days = generate::GENERATORS.iter().map(|g| g.day.to_string()).collect::<Vec<_>>();
+        format!("no generator for day {}, there are generators for days {}", day, days.join(", "))
+    })?;
+    Ok(Command::Generate(Generate { generator, seed, size: size.unwrap_or(generator.default_size), output }))
+}
+
+fn parse_compare_args<I: Iterator<Item = String>>(mut args: I) -> Result<Command, String> {
+    let mut days = Vec::new();
+    let mut data_dir = PathBuf::from(DEFAULT_DATA_DIR);
+    let mut seeds = 20;
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-h" | "--help" => return Ok(Command::Help),
+            "-d" | "--day" | "--days" => days.append(&mut parse_days(&next_value(&mut args, &arg)?)?),
+            "--data-dir" => data_dir = PathBuf::from(next_value(&mut args, &arg)?),
+            "--seeds" => {
+                let value = next_value(&mut args, &arg)?;
+                seeds = value.parse::<u64>().map_err(|_| format!("invalid number of seeds '{}'", value))?;
+            },
+            _ => return Err(format!("unknown argument '{}'", arg)),
+        }
+    }
+
+    // only days with several implementations have anything to compare
+    let several = |day: u8| registry::implementations(day).nth(1).is_some();
+    if days.is_empty() {
+        days = (1..=N_DAYS).filter(|day| several(*day)).collect();
+    }
+    days.sort();
+    days.dedup();
+    if let Some(day) = days.iter().find(|day| !several(**day)) {
+        return Err(format!("day {} has a single implementation, there is nothing to compare", day));
+    }
+    Ok(Command::Compare(Compare { days, data_dir, seeds }))
+}
+
+fn parse_repl_args<I: Iterator<Item = String>>(mut args: I) -> Result<Command, String> {
+    let mut day = None;
+    let mut input = None;
+    let mut data_dir = PathBuf::from(DEFAULT_DATA_DIR);
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-h" | "--help" => return Ok(Command::Help),
+            "-i" | "--input" => match Source::from(next_value(&mut args, &arg)?.as_str()) {
+                Source::Stdin => return Err("repl reads its commands from stdin, the input has to be a file".to_string()),
+                source => input = Some(source),
+            },
+            "--data-dir" => data_dir = PathBuf::from(next_value(&mut args, &arg)?),
+            _ if arg.starts_with('-') || day.is_some() => return Err(format!("unknown argument '{}'", arg)),
+            _ => day = Some(parse_day(&arg)?),
+        }
+    }
+
+    let day = day.ok_or("repl needs the number of the day")?;
+    let explorer = repl::for_day(day).ok_or_else(|| {
+        let days = repl::EXPLORERS.iter().map(|e| e.day.to_string()).collect::<Vec<_>>();
+        format!("day {} can't be explored, days {} can", day, days.join(", "))
+    })?;
+    Ok(Command::Repl(Repl { explorer, source: input.unwrap_or_else(|| Source::default_for(&data_dir, day)) }))
+}
+
+fn parse_animate_args<I: Iterator<Item = String>>(mut args: I) -> Result<Command, String> {
+    let mut day = None;
+    let mut input = None;
+    let mut data_dir = PathBuf::from(DEFAULT_DATA_DIR);
+    let mut every = 1;
+    let mut frames = 300;
+    let mut fps = 10;
+    let mut scale = 4;
+    let mut output = None;
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-h" | "--help" => return Ok(Command::Help),
+            "-i" | "--input" => input = Some(Source::from(next_value(&mut args, &arg)?.as_str())),
+            "--data-dir" => data_dir = PathBuf::from(next_value(&mut args, &arg)?),
+            "--every" => every = parse_positive(&next_value(&mut args, &arg)?, "number of steps per frame")?,
+            "--frames" => frames = parse_positive(&next_value(&mut args, &arg)?, "number of frames")?,
+            "--fps" => fps = parse_positive(&next_value(&mut args, &arg)?, "number of frames per second")? as u32,
+            "--scale" => scale = parse_positive(&next_value(&mut args, &arg)?, "scale")?,
+            "-o" | "--output" => {
+                let path = PathBuf::from(next_value(&mut args, &arg)?);
+                if !matches!(path.extension().and_then(|e| e.to_str()), Some("gif" | "ppm")) {
+                    return Err(format!("can't write frames to '{}', the output has to end in .gif or .ppm", path.display()));
+                }
+                output = Some(path);
+            },
+            _ if arg.starts_with('-') && arg != "-" || day.is_some() => return Err(format!("unknown argument '{}'", arg)),
+            _ => day = Some(parse_day(&arg)?),
+        }
+    }
+
+    let day = day.ok_or("animate needs the number of the day")?;
+    let film = frames::for_day(day).ok_or_else(|| {
+        let days = frames::FILMS.iter().map(|f| f.day.to_string()).collect::<Vec<_>>();
+        format!("day {} can't be animated, days {} can", day, days.join(", "))
+    })?;
+    let source = input.unwrap_or_else(|| Source::default_for(&data_dir, day));
+    Ok(Command::Animate(Animate { film, source, every, frames, fps, scale, output }))
+}
+
+fn parse_positive(value: &str, what: &str) -> Result<usize, String> {
+    match value.parse::<usize>() {
+        Ok(n) if n > 0 && n <= u32::MAX as usize => Ok(n),
+        _ => Err(format!("invalid {} '{}'", what, value)),
+    }
+}
+
+fn next_value<I: Iterator<Item = String>>(args: &mut I, flag: &str) -> Result<String, String> {
+    args.next().ok_or(format!("missing value for {}", flag))
+}
+
+// accepts single days, ranges and comma separated lists of both: "3", "10-15", "1,3,20-25"
+fn parse_days(spec: &str) -> Result<Vec<u8>, String> {
+    let mut days = Vec::new();
+
+    for item in spec.split(',') {
+        match item.split_once('-') {
+            Some((from, to)) => {
+                let (from, to) = (parse_day(from)?, parse_day(to)?);
+                if from > to {
+                    return Err(format!("invalid day range '{}'", item));
+                }
+                days.extend(from..=to);
+            },
+            None => days.push(parse_day(item)?),
+        }
+    }
+
+    Ok(days)
+}
+
+fn parse_day(day: &str) -> Result<u8, String> {
+    match day.trim().parse::<u8>() {
+        Ok(n) if (1..=N_DAYS).contains(&n) => Ok(n),
+        _ => Err(format!("unknown day '{}', expected a number from 1 to {}", day, N_DAYS)),
+    }
+}
+
+#[cfg(test)]
+fn parse_test_args(args: &[&str]) -> Result<Command, String> {
+    parse_args(args.iter().map(|arg| arg.to_string()))
+}
+
+#[test]
+fn test_parse_days() {
+    assert_eq!(parse_days("16"), Ok(vec![16]));
+    assert_eq!(parse_days("10-13"), Ok(vec![10, 11, 12, 13]));
+    assert_eq!(parse_days("1,3,24-25"), Ok(vec![1, 3, 24, 25]));
+    assert!(parse_days("0").is_err());
+    assert!(parse_days("26").is_err());
+    assert!(parse_days("15-10").is_err());
+    assert!(parse_days("a").is_err());
+}
+
+#[test]
+fn test_parse_args() {
+    let Ok(Command::Run(options)) = parse_test_args(&["--day", "16", "--part", "2"]) else { panic!() };
+    assert!(options.selection.contains(16, 2));
+    assert!(!options.selection.contains(16, 1));
+    assert!(!options.selection.contains(15, 2));
+
+    let Ok(Command::Run(options)) = parse_test_args(&["--days", "3,1-2", "-d", "2"]) else { panic!() };
+    assert_eq!(options.selection, Selection { days: vec![1, 2, 3], part: None });
+
+    let Ok(Command::Run(options)) = parse_test_args(&[]) else { panic!() };
+    assert_eq!(options.selection, Selection::default());
+    assert_eq!(options.source(3), Source::File(PathBuf::from("data/d03.txt")));
+
+    assert!(matches!(parse_test_args(&["-h"]), Ok(Command::Help)));
+    assert!(parse_test_args(&["--day"]).is_err());
+    assert!(parse_test_args(&["--part", "3"]).is_err());
+    assert!(parse_test_args(&["--frobnicate"]).is_err());
+}
+
+#[test]
+fn test_parse_input_args() {
+    let Ok(Command::Run(options)) = parse_test_args(&["-d", "5", "--input", "-"]) else { panic!() };
+    assert_eq!(options.source(5), Source::Stdin);
+
+    let Ok(Command::Run(options)) = parse_test_args(&["--data-dir", "inputs/alice", "-d", "1-2"]) else { panic!() };
+    assert_eq!(options.source(2), Source::File(PathBuf::from("inputs/alice/d02.txt")));
+
+    assert!(parse_test_args(&["--input", "d05.txt"]).is_err());
+    assert!(parse_test_args(&["-d", "4-5", "--input", "d05.txt"]).is_err());
+}
+
+#[test]
+fn test_parse_answer_args() {
+    let Ok(Command::Run(options)) = parse_test_args(&["--verify", "--data-dir", "inputs/alice"]) else { panic!() };
+    assert_eq!(options.mode, Mode::Verify);
+    assert_eq!(options.answers_path(), PathBuf::from("inputs/alice/answers.toml"));
+
+    let Ok(Command::Run(options)) = parse_test_args(&["--record", "--answers", "mine.toml"]) else { panic!() };
+    assert_eq!(options.mode, Mode::Record);
+    assert_eq!(options.answers_path(), PathBuf::from("mine.toml"));
+
+    let Ok(Command::Run(options)) = parse_test_args(&[]) else { panic!() };
+    assert_eq!(options.mode, Mode::Run);
+
+    assert!(parse_test_args(&["--verify", "--record"]).is_err());
+}
+
+#[test]
+fn test_parse_pool_args() {
+    let Ok(Command::Run(options)) = parse_test_args(&["-j", "4", "--timeout", "1.5"]) else { panic!() };
+    let pool = options.pool.unwrap();
+    assert_eq!(pool.jobs, 4);
+    assert_eq!(pool.timeout, Some(Duration::from_millis(1500)));
+
+    let Ok(Command::Run(options)) = parse_test_args(&["--timeout", "10"]) else { panic!() };
+    assert!(options.pool.unwrap().jobs >= 1);
+
+    let Ok(Command::Run(options)) = parse_test_args(&[]) else { panic!() };
+    assert!(options.pool.is_none());
+
+    assert!(parse_test_args(&["--jobs", "0"]).is_err());
+    assert!(parse_test_args(&["--timeout", "-1"]).is_err());
+    assert!(parse_test_args(&["--jobs", "2", "--bench", "10"]).is_err());
+}
+
+#[test]
+fn test_parse_format_args() {
+    let Ok(Command::Run(options)) = parse_test_args(&["--format", "csv"]) else { panic!() };
+    assert_eq!(options.format, Some(Format::Csv));
+
+    let Ok(Command::Run(options)) = parse_test_args(&[]) else { panic!() };
+    assert_eq!(options.format, None);
+
+    assert!(parse_test_args(&["--format", "yaml"]).is_err());
+}
+
+#[test]
+fn test_parse_matrix_args() {
+    let Ok(Command::Run(options)) = parse_test_args(&["--matrix", "-d", "12,22", "--timeout", "5"]) else { panic!() };
+    assert!(options.matrix);
+    assert!(options.pool.is_some());
+
+    let Ok(Command::Run(options)) = parse_test_args(&[]) else { panic!() };
+    assert!(!options.matrix);
+
+    assert!(parse_test_args(&["--matrix", "--format", "json"]).is_err());
+    assert!(parse_test_args(&["--matrix", "--answers", "a.toml"]).is_err());
+}
+
+#[test]
+fn test_parse_animate_args() {
+    let Ok(Command::Animate(animate)) = parse_test_args(&["animate", "14"]) else { panic!() };
+    assert_eq!(animate.film.day, 14);
+    assert_eq!(animate.source, Source::File(PathBuf::from("data/d14.txt")));
+    assert_eq!((animate.every, animate.frames, animate.fps, animate.scale, animate.output), (1, 300, 10, 4, None));
+
+    let Ok(Command::Animate(animate)) = parse_test_args(&["animate", "23", "--every", "5", "--frames", "50", "-o", "grove.gif"]) else { panic!() };
+    assert_eq!((animate.every, animate.frames), (5, 50));
+    assert_eq!(animate.output, Some(PathBuf::from("grove.gif")));
+
+    assert!(parse_test_args(&["animate"]).is_err());
+    assert!(parse_test_args(&["animate", "1"]).is_err());
+    assert!(parse_test_args(&["animate", "14", "--fps", "0"]).is_err());
+    assert!(parse_test_args(&["animate", "14", "-o", "sand.png"]).is_err());
+}
+
+#[test]
+fn test_parse_repl_args() {
+    let Ok(Command::Repl(repl)) = parse_test_args(&["repl", "07"]) else { panic!() };
+    assert_eq!(repl.explorer.day, 7);
+    assert_eq!(repl.source, Source::File(PathBuf::from("data/d07.txt")));
+
+    let Ok(Command::Repl(repl)) = parse_test_args(&["repl", "--input", "big.txt", "23"]) else { panic!() };
+    assert_eq!(repl.source, Source::File(PathBuf::from("big.txt")));
+
+    assert!(parse_test_args(&["repl"]).is_err());
+    assert!(parse_test_args(&["repl", "1"]).is_err());
+    assert!(parse_test_args(&["repl", "7", "-i", "-"]).is_err());
+}
+
+#[test]
+fn test_parse_generate_args() {
+    let Ok(Command::Generate(generate)) = parse_test_args(&["generate", "21", "--seed", "7", "-o", "d21.txt"]) else { panic!() };
+    assert_eq!(generate.generator.day, 21);
+    assert_eq!((generate.seed, generate.size), (7, generate.generator.default_size));
+    assert_eq!(generate.output, Some(PathBuf::from("d21.txt")));
+
+    let Ok(Command::Generate(generate)) = parse_test_args(&["generate", "--size", "10", "9"]) else { panic!() };
+    assert_eq!((generate.seed, generate.size, generate.output), (0, 10, None));
+
+    assert!(parse_test_args(&["generate"]).is_err());
+    assert!(parse_test_args(&["generate", "1"]).is_err());
+    assert!(parse_test_args(&["generate", "9", "--size", "0"]).is_err());
+    assert!(parse_test_args(&["generate", "9", "13"]).is_err());
+}
+
+#[test]
+fn test_parse_impl_args() {
+    let Ok(Command::Run(options)) = parse_test_args(&["-d", "6-8", "--impl", "hash-map"]) else { panic!() };
+    assert_eq!(options.entry(7).map(|e| e.name), Some("hash-map"));
+    assert_eq!(options.entry(8).map(|e| e.name), Some("default"));
+
+    assert!(parse_test_args(&["-d", "8", "--impl", "hash-map"]).is_err());
+    assert!(parse_test_args(&["--impl", "default"]).is_err());
+}
+
+#[test]
+fn test_parse_memory_args() {
+    let Ok(Command::Run(options)) = parse_test_args(&["--memory", "--format", "csv"]) else { panic!() };
+    assert!(options.memory);
+    assert!(parse_test_args(&["--memory", "-j", "2"]).is_err());
+    assert!(parse_test_args(&["--memory", "--bench", "5"]).is_err());
+}
+
+#[test]
+fn test_parse_trace_args() {
+    let Ok(Command::Run(options)) = parse_test_args(&["-d", "24", "--trace", "d24.json"]) else { panic!() };
+    assert_eq!(options.trace, Some(PathBuf::from("d24.json")));
+    assert!(parse_test_args(&["--trace"]).is_err());
+}
+
+#[test]
+fn test_parse_cache_args() {
+    let Ok(Command::Run(options)) = parse_test_args(&["-d", "16"]) else { panic!() };
+    assert!(options.cache);
+    let Ok(Command::Run(options)) = parse_test_args(&["-d", "16", "--no-cache"]) else { panic!() };
+    assert!(!options.cache);
+    let Ok(Command::Run(options)) = parse_test_args(&["-d", "16", "--verify"]) else { panic!() };
+    assert!(!options.cache);
+    let Ok(Command::Run(options)) = parse_test_args(&["-d", "16", "--record"]) else { panic!() };
+    assert!(!options.cache);
+}
+
+#[test]
+fn test_parse_compare_args() {
+    let Ok(Command::Compare(compare)) = parse_test_args(&["compare"]) else { panic!() };
+    assert_eq!((compare.days, compare.seeds), (vec![7, 8, 23], 20));
+
+    let Ok(Command::Compare(compare)) = parse_test_args(&["compare", "-d", "8", "--seeds", "3"]) else { panic!() };
+    assert_eq!((compare.days, compare.seeds), (vec![8], 3));
+
+    assert!(parse_test_args(&["compare", "-d", "1"]).is_err());
+}
+
+#[test]
+fn test_parse_import_args() {
+    let Ok(Command::Import(import)) = parse_test_args(&["import", "instr", "--data-dir", "inputs", "d24"]) else { panic!() };
+    assert_eq!(import.pages, vec![PathBuf::from("instr"), PathBuf::from("d24")]);
+    assert_eq!(import.answers_path(), PathBuf::from("inputs/answers.toml"));
+
+    assert!(parse_test_args(&["import"]).is_err());
+    assert!(parse_test_args(&["import", "instr", "--day", "24"]).is_err());
+    assert!(parse_test_args(&["instr", "import"]).is_err());
+}
+
+#[test]
+fn test_parse_new_day_args() {
+    assert!(matches!(parse_test_args(&["new-day", "07"]), Ok(Command::NewDay(7))));
+    assert!(parse_test_args(&["new-day"]).is_err());
+    assert!(parse_test_args(&["new-day", "26"]).is_err());
+    assert!(parse_test_args(&["new-day", "7", "8"]).is_err());
+}
+
+#[test]
+fn test_parse_bench_args() {
+    let Ok(Command::Run(options)) = parse_test_args(&["--bench", "100", "--baseline", "base.toml"]) else { panic!() };
+    let bench = options.bench.unwrap();
+    assert_eq!(bench.runs, 100);
+    assert_eq!(bench.baseline, Some(PathBuf::from("base.toml")));
+    assert_eq!(bench.threshold, DEFAULT_THRESHOLD);
+
+    let Ok(Command::Run(options)) = parse_test_args(&["--bench", "5", "--threshold", "2.5"]) else { panic!() };
+    assert_eq!(options.bench.unwrap().threshold, 2.5);
+
+    assert!(parse_test_args(&["--bench", "0"]).is_err());
+    assert!(parse_test_args(&["--bench", "5", "--threshold", "-1"]).is_err());
+    assert!(parse_test_args(&["--baseline", "base.toml"]).is_err());
+}
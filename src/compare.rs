@@ -0,0 +1,88 @@
+// runs every implementation of a day on the same input, they all have to give the same answers
+use std::{fmt::Display, io, panic::{self, AssertUnwindSafe}, path::{Path, PathBuf}};
+
+use crate::matrix;
+use crate::registry::{self, Entry};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Disagreement {
+    pub day: u8,
+    pub part: u8,
+    // the answer of every implementation by name, PARSE ERROR or PANIC if there was none
+    pub answers: Vec<(&'static str, String)>,
+}
+
+impl Display for Disagreement {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let answers = self.answers.iter().map(|(name, answer)| format!("{} {}", name, answer)).collect::<Vec<_>>();
+        write!(f, "day {} part {}: {}", self.day, self.part, answers.join(", "))
+    }
+}
+
+// the files in the data directory the implementations of a day are compared on: DIR/dNN.txt, the
+// examples DIR/dNN_test*.txt and the inputs of --matrix in DIR/dNN/*.txt
+pub fn inputs(data_dir: &Path, day: u8) -> io::Result<Vec<PathBuf>> {
+    let prefix = format!("d{:02}", day);
+    let mut files = Vec::new();
+    match std::fs::read_dir(data_dir) {
+        Ok(entries) => {
+            for entry in entries {
+                let path = entry?.path();
+                let file_name = path.file_name().unwrap_or_default().to_string_lossy();
+                if file_name == format!("{}.txt", prefix) || (file_name.starts_with(&format!("{}_test", prefix)) && file_name.ends_with(".txt")) {
+                    files.push(path);
+                }
+            }
+        },
+        Err(e) if e.kind() == io::ErrorKind::NotFound => (),
+        Err(e) => return Err(e),
+    }
+    files.sort();
+    files.extend(matrix::inputs(data_dir, day)?.into_iter().map(|input| input.path));
+    Ok(files)
+}
+
+// the parts the implementations disagree on, an input that none of them parses is no disagreement
+pub fn compare(day: u8, input: &str) -> Vec<Disagreement> {
+    let answers = registry::implementations(day).map(|entry| (entry.name, answers(entry, input))).collect::<Vec<_>>();
+    let parts = answers.iter().map(|(_, answers)| answers.len()).max().unwrap_or(0);
+
+    (1..=parts as u8)
+        .map(|part| Disagreement {
+            day,
+            part,
+            answers: answers.iter()
+                .map(|(name, answers)| (*name, answers.get(part as usize - 1).cloned().unwrap_or_else(|| "-".to_string())))
+                .collect(),
+        })
+        .filter(|disagreement| disagreement.answers.windows(2).any(|pair| pair[0].1 != pair[1].1))
+        .collect()
+}
+
+fn answers(entry: &Entry, input: &str) -> Vec<String> {
+    let parts = entry.runner.parts() as usize;
+    match panic::catch_unwind(AssertUnwindSafe(|| entry.runner.parse(input))) {
+        Ok(Ok(parsed)) => (1..=entry.runner.parts())
+            .map(|part| panic::catch_unwind(AssertUnwindSafe(|| parsed.solve(part).to_string())).unwrap_or_else(|_| "PANIC".to_string()))
+            .collect(),
+        Ok(Err(_)) => vec!["PARSE ERROR".to_string(); parts],
+        Err(_) => vec!["PANIC".to_string(); parts],
+    }
+}
+
+#[test]
+fn test_compare() {
+    assert_eq!(compare(7, include_str!("../data/d07_test.txt")), Vec::new());
+    assert_eq!(compare(7, "not a puzzle input\n"), Vec::new());
+
+    let disagreement = Disagreement { day: 8, part: 2, answers: vec![("default", "8".to_string()), ("4-bit", "PANIC".to_string())] };
+    assert_eq!(disagreement.to_string(), "day 8 part 2: default 8, 4-bit PANIC");
+}
+
+#[test]
+fn test_inputs() {
+    let data_dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("data");
+    let names = |day| inputs(&data_dir, day).unwrap().iter().map(|path| path.file_name().unwrap().to_string_lossy().to_string()).collect::<Vec<_>>();
+    assert_eq!(names(9), vec!["d09.txt", "d09_test.txt", "d09_test_2.txt"]);
+    assert!(inputs(&data_dir.join("missing"), 9).unwrap().is_empty());
+}
@@ -1,24 +1,44 @@
-fn parse(input: &str) -> Vec<usize> {
+use crate::error::{self, ParseError};
+use crate::solution::{Answer, Solution};
+
+pub fn parse(input: &str) -> Result<Vec<usize>, ParseError> {
     let mut elves = Vec::new();
     let mut elf = Vec::new();
     
-    for line in input.lines() {
-        if line.is_empty() {
+    for line in error::lines(input) {
+        if line.text.is_empty() {
             elves.push(elf.iter().sum::<usize>());
             elf = Vec::new();
         } else {
-            elf.push(line.parse::<usize>().unwrap());
+            elf.push(line.number::<usize>(line.text)?);
         }
     }
+    // the last elf isn't necessarily followed by an empty line
+    if !elf.is_empty() {
+        elves.push(elf.iter().sum::<usize>());
+    }
 
+    if elves.len() < 3 {
+        return Err(ParseError::end_of_input(input, "at least three elves"));
+    }
     elves.sort_by(|a, b| a.cmp(b).reverse());
-    elves
+    Ok(elves)
 } 
 
-pub fn get_solution_1() -> usize {
-    parse(include_str!("../data/d01.txt"))[0]
-}
+pub struct Day01;
 
-pub fn get_solution_2() -> usize {
-    parse(include_str!("../data/d01.txt"))[..3].iter().sum()
+impl Solution for Day01 {
+    type Parsed<'a> = Vec<usize>;
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, ParseError> {
+        parse(input)
+    }
+
+    fn part_1(elves: &Vec<usize>) -> Answer {
+        elves[0].into()
+    }
+
+    fn part_2(elves: &Vec<usize>) -> Answer {
+        elves[..3].iter().sum::<usize>().into()
+    }
 }
\ No newline at end of file
@@ -1,9 +1,20 @@
-fn parse(input: &str) -> Vec<(Shape, Shape)> {
-    input.lines().map(|l| l.chars().collect::<Vec<char>>()).map(|v| (v[0].into(), v[2].into())).collect()
+use crate::error::{self, ParseError};
+use crate::solution::{Answer, Solution};
+
+pub fn parse(input: &str) -> Result<Vec<(Shape, Shape)>, ParseError> {
+    error::lines(input)
+        .map(|line| match line.text.chars().collect::<Vec<char>>()[..] {
+            [elf, ' ', player] => Ok((
+                Shape::try_from(elf).map_err(|_| line.error_at(1, "A, B or C"))?,
+                Shape::try_from(player).map_err(|_| line.error_at(3, "X, Y or Z"))?,
+            )),
+            _ => Err(line.error(line.text, "two shapes like 'A Y'")),
+        })
+        .collect()
 }
 
 #[derive(Clone, Copy)]
-enum Shape {
+pub enum Shape {
     Rock, // win
     Paper, // loose
     Scissors, // draw
@@ -51,27 +62,39 @@ impl Shape {
     }
 }
 
-impl From<char> for Shape {
-    fn from(input: char) -> Self {
+impl TryFrom<char> for Shape {
+    type Error = char;
+
+    fn try_from(input: char) -> Result<Self, char> {
         match input {
-            'A' | 'X' => Self::Rock,
-            'B' | 'Y' => Self::Paper,
-            'C' | 'Z' => Self::Scissors,
-            _ => unreachable!(),
+            'A' | 'X' => Ok(Self::Rock),
+            'B' | 'Y' => Ok(Self::Paper),
+            'C' | 'Z' => Ok(Self::Scissors),
+            c => Err(c),
         }
     }
 }
 
-pub fn get_solution_1() -> usize {
-    parse(include_str!("../data/d02.txt"))
-        .into_iter()
-        .map(|(elf, player)| Shape::score(player, elf))
-        .sum()
-}
+pub struct Day02;
 
-pub fn get_solution_2() -> usize {
-    parse(include_str!("../data/d02.txt"))
-        .into_iter()
-        .map(|(elf, player)| Shape::score_2(player, elf))
-        .sum()
+impl Solution for Day02 {
+    type Parsed<'a> = Vec<(Shape, Shape)>;
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, ParseError> {
+        parse(input)
+    }
+
+    fn part_1(rounds: &Vec<(Shape, Shape)>) -> Answer {
+        rounds.iter()
+              .map(|(elf, player)| Shape::score(*player, *elf))
+              .sum::<usize>()
+              .into()
+    }
+
+    fn part_2(rounds: &Vec<(Shape, Shape)>) -> Answer {
+        rounds.iter()
+              .map(|(elf, player)| Shape::score_2(*player, *elf))
+              .sum::<usize>()
+              .into()
+    }
 }
\ No newline at end of file
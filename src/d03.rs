@@ -1,12 +1,26 @@
-fn parse(input: &'static str) -> Vec<Rucksack> {
-    input.lines()
-         .map(|line| Rucksack { 
-            items: line, 
-         })
-         .collect()
+use crate::error::{self, ParseError};
+use crate::solution::{Answer, Solution};
+
+pub fn parse(input: &str) -> Result<Vec<Rucksack<'_>>, ParseError> {
+    let rucksacks = error::lines(input)
+        .map(|line| {
+            if let Some(pos) = line.text.find(|c: char| !c.is_ascii_alphabetic()) {
+                return Err(line.error(&line.text[pos..pos + 1], "an item from a-z or A-Z"));
+            }
+            if !line.text.len().is_multiple_of(2) {
+                return Err(line.end_error("an even number of items"));
+            }
+            Ok(Rucksack { items: line.text })
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    if !rucksacks.len().is_multiple_of(3) {
+        return Err(ParseError::end_of_input(input, "a number of rucksacks divisible by 3"));
+    }
+    Ok(rucksacks)
 }
 
-fn divide_into_group(rucksacks: Vec<Rucksack>) -> Vec<[Rucksack; 3]> {
+fn divide_into_group<'a>(rucksacks: &[Rucksack<'a>]) -> Vec<[Rucksack<'a>; 3]> {
     let mut all_groups = Vec::new();
 
     for chunk in rucksacks.chunks(3) {
@@ -17,11 +31,11 @@ fn divide_into_group(rucksacks: Vec<Rucksack>) -> Vec<[Rucksack; 3]> {
 }
 
 #[derive(Clone, Copy, Debug)]
-struct Rucksack {
-    items: &'static str,
+pub struct Rucksack<'a> {
+    items: &'a str,
 }
 
-impl Rucksack {
+impl Rucksack<'_> {
     fn get_double_item(&self) -> char {
         for l_item in self.items[..self.items.len() / 2].chars() {
             for r_item in self.items[self.items.len() / 2..].chars() {
@@ -55,16 +69,27 @@ fn get_score(ch: char) -> u32 {
     }
 }
 
-pub fn get_solution_1() -> u32 {
-    parse(include_str!("../data/d03.txt"))
-        .into_iter()
-        .map(|rs| get_score(rs.get_double_item()))
-        .sum()
-}
+pub struct Day03;
 
-pub fn get_solution_2() -> u32 {
-    divide_into_group(parse(include_str!("../data/d03.txt")))
-        .into_iter()
-        .map(|g| get_score(Rucksack::get_triple(g)))
-        .sum()
+impl Solution for Day03 {
+    type Parsed<'a> = Vec<Rucksack<'a>>;
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, ParseError> {
+        parse(input)
+    }
+
+    fn part_1(rucksacks: &Vec<Rucksack>) -> Answer {
+        rucksacks.iter()
+                 .map(|rs| get_score(rs.get_double_item()))
+                 .sum::<u32>()
+                 .into()
+    }
+
+    fn part_2(rucksacks: &Vec<Rucksack>) -> Answer {
+        divide_into_group(rucksacks)
+            .into_iter()
+            .map(|g| get_score(Rucksack::get_triple(g)))
+            .sum::<u32>()
+            .into()
+    }
 }
\ No newline at end of file
@@ -1,11 +1,17 @@
-fn parse(input: &str) -> Vec<((usize, usize), (usize, usize))> {
+use crate::error::{self, ParseError};
+use crate::solution::{Answer, Solution};
+
+type Pairs = Vec<((usize, usize), (usize, usize))>;
+
+pub fn parse(input: &str) -> Result<Pairs, ParseError> {
     let mut elf_ids = Vec::new();
-    for line in input.lines() {
-        let parts = line.split(',').flat_map(|range| range.split('-')).map(|p| p.parse::<usize>().unwrap()).collect::<Vec<usize>>();
-        elf_ids.push(((parts[0], parts[1]), (parts[2], parts[3])));
+    for line in error::lines(input) {
+        let (lhs, rhs) = line.split_once(line.text, ",")?;
+        let ((l0, l1), (r0, r1)) = (line.split_once(lhs, "-")?, line.split_once(rhs, "-")?);
+        elf_ids.push(((line.number(l0)?, line.number(l1)?), (line.number(r0)?, line.number(r1)?)));
     }
 
-    elf_ids
+    Ok(elf_ids)
 }
 
 fn is_contained((lhs, rhs): ((usize, usize), (usize, usize))) -> bool {
@@ -18,17 +24,27 @@ fn does_overlap((lhs, rhs): ((usize, usize), (usize, usize))) -> bool {
     rhs.1 >= lhs.0 && rhs.0 <= lhs.0
 }
 
-pub fn get_solution_1() -> usize {
-    parse(include_str!("../data/d04.txt")).into_iter().filter(|pair| is_contained(*pair)).count()
-}
+pub struct Day04;
 
-pub fn get_solution_2() -> usize {
-    parse(include_str!("../data/d04.txt")).into_iter().filter(|pair| does_overlap(*pair)).count()
+impl Solution for Day04 {
+    type Parsed<'a> = Pairs;
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, ParseError> {
+        parse(input)
+    }
+
+    fn part_1(pairs: &Pairs) -> Answer {
+        pairs.iter().filter(|pair| is_contained(**pair)).count().into()
+    }
+
+    fn part_2(pairs: &Pairs) -> Answer {
+        pairs.iter().filter(|pair| does_overlap(**pair)).count().into()
+    }
 }
 
 #[test]
 fn test() {
-    println!("{:?}", parse(include_str!("../data/d04.txt")));
+    println!("{:?}", parse(include_str!("../data/d04.txt")).unwrap());
 }
 
 #[test]
@@ -38,14 +54,14 @@ fn test_is_contained() {
 5-7,7-9
 2-8,3-7
 6-6,4-6
-2-6,4-8");
+2-6,4-8").unwrap();
 
-    assert_eq!(is_contained(elves[0]), false);
-    assert_eq!(is_contained(elves[1]), false);
-    assert_eq!(is_contained(elves[2]), false);
-    assert_eq!(is_contained(elves[3]), true);
-    assert_eq!(is_contained(elves[4]), true);
-    assert_eq!(is_contained(elves[5]), false);
+    assert!(!is_contained(elves[0]));
+    assert!(!is_contained(elves[1]));
+    assert!(!is_contained(elves[2]));
+    assert!(is_contained(elves[3]));
+    assert!(is_contained(elves[4]));
+    assert!(!is_contained(elves[5]));
 }
 
 #[test]
@@ -55,12 +71,12 @@ fn test_does_overlap() {
 5-7,7-9
 2-8,3-7
 6-6,4-6
-2-6,4-8"); 
+2-6,4-8").unwrap();
 
-    assert_eq!(does_overlap(elves[0]), false);
-    assert_eq!(does_overlap(elves[1]), false);
-    assert_eq!(does_overlap(elves[2]), true);
-    assert_eq!(does_overlap(elves[3]), true);
-    assert_eq!(does_overlap(elves[4]), true);
-    assert_eq!(does_overlap(elves[5]), true); 
+    assert!(!does_overlap(elves[0]));
+    assert!(!does_overlap(elves[1]));
+    assert!(does_overlap(elves[2]));
+    assert!(does_overlap(elves[3]));
+    assert!(does_overlap(elves[4]));
+    assert!(does_overlap(elves[5])); 
 }
\ No newline at end of file
@@ -1,29 +1,40 @@
-// instructions are (amount, from, to)
-fn parse(input: &str) -> Vec<(usize, usize, usize)> {
+use crate::error::{self, ParseError};
+use crate::solution::{Answer, Solution};
+
+// instructions are (amount, from, to), n_stacks is used to check from and to
+pub fn parse(input: &str, n_stacks: usize) -> Result<Vec<(usize, usize, usize)>, ParseError> {
     let mut instructions = Vec::new();
 
-    for line in input.lines().filter(|l| l.starts_with("move")) {
-        let parts = line.split_whitespace().filter_map(|l| l.parse::<usize>().ok()).collect::<Vec<usize>>();
-        instructions.push((parts[0], parts[1] - 1, parts[2] - 1)) // decrement by one for indexing
+    for line in error::lines(input).filter(|l| l.text.starts_with("move")) {
+        let [_, amount, _, from, _, to] = line.words::<6>()?;
+        let stack = |n: &str| match line.number::<usize>(n)? {
+            n if (1..=n_stacks).contains(&n) => Ok(n - 1), // decrement by one for indexing
+            _ => Err(line.error(n, format!("a stack from 1 to {}", n_stacks))),
+        };
+        instructions.push((line.number(amount)?, stack(from)?, stack(to)?))
     }
 
-    instructions
+    Ok(instructions)
 }
 
-fn parse_stacks(input: &str) -> Vec<Vec<char>> {
+pub fn parse_stacks(input: &str) -> Result<Vec<Vec<char>>, ParseError> {
     let mut stacks = Vec::new();
-    for line in input.lines().filter(|l| !l.starts_with("move") && !l.is_empty()).rev() {
-        for (i, ch) in line.chars().skip(1).step_by(4).enumerate() {
+    for line in error::lines(input).filter(|l| !l.text.starts_with("move") && !l.text.is_empty()).collect::<Vec<_>>().into_iter().rev() {
+        let chars = line.text.chars().collect::<Vec<_>>();
+        for (i, slot) in chars.chunks(4).enumerate() {
             if stacks.len() == i {
                 stacks.push(Vec::new());
             }
-            if ch.is_alphabetic() {
-                stacks[i].push(ch)
+            // a crate, an empty slot or one of the numbers below the stacks
+            match slot {
+                ['[', ch, ']', ..] if ch.is_alphabetic() => stacks[i].push(*ch),
+                [' ', ch, ..] if *ch == ' ' || ch.is_ascii_digit() => (),
+                _ => return Err(line.error_at(i * 4 + 1, "a crate like '[A]'")),
             }
         }
     }
 
-    stacks
+    Ok(stacks)
 }
 
 fn execute_instruction_9000((amount, from, to): (usize, usize, usize), crates: &mut [Vec<char>]) {
@@ -56,24 +67,31 @@ fn get_top_crates(crates: Vec<Vec<char>>) -> String {
     result
 }
 
-pub fn get_solution_1() -> String {
-    let input = include_str!("../data/d05.txt");
-    let instructions = parse(input);
-    let mut crates = parse_stacks(input);
-    for instruction in instructions {
-        execute_instruction_9000(instruction, &mut crates);
+pub struct Day05;
+
+impl Solution for Day05 {
+    type Parsed<'a> = (Vec<(usize, usize, usize)>, Vec<Vec<char>>);
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, ParseError> {
+        let stacks = parse_stacks(input)?;
+        Ok((parse(input, stacks.len())?, stacks))
     }
 
-    get_top_crates(crates)
-}
+    fn part_1((instructions, crates): &Self::Parsed<'_>) -> Answer {
+        let mut crates = crates.clone();
+        for instruction in instructions {
+            execute_instruction_9000(*instruction, &mut crates);
+        }
 
-pub fn get_solution_2() -> String {
-    let input = include_str!("../data/d05.txt");
-    let instructions = parse(input);
-    let mut crates = parse_stacks(input);
-    for instruction in instructions {
-        execute_instruction_9001(instruction, &mut crates);
+        get_top_crates(crates).into()
     }
 
-    get_top_crates(crates)
+    fn part_2((instructions, crates): &Self::Parsed<'_>) -> Answer {
+        let mut crates = crates.clone();
+        for instruction in instructions {
+            execute_instruction_9001(*instruction, &mut crates);
+        }
+
+        get_top_crates(crates).into()
+    }
 }
\ No newline at end of file
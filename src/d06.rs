@@ -1,34 +1,55 @@
-fn find_first_packet_marker(input: &str) -> usize {
+use crate::error::{self, ParseError};
+use crate::solution::{Answer, Solution};
+
+fn find_first_packet_marker(input: &str) -> Option<usize> {
     for (i, (((ch1, ch2), ch3), ch4)) in input.chars().zip(input[1..].chars()).zip(input[2..].chars()).zip(input[3..].chars()).enumerate() {
         if ch1 != ch2 && ch1 != ch3 && ch1 != ch4 &&
            ch2 != ch3 && ch2 != ch4 &&
            ch3 != ch4 {
-            return i + 4
+            return Some(i + 4)
            }
     }
 
-    unreachable!()
+    None
 }
 
-fn find_first_msg_marker(input: &str) -> usize {
+fn find_first_msg_marker(input: &str) -> Option<usize> {
     let mut uniques = Vec::new();
     for (i, ch) in input.chars().enumerate() {
-        if uniques.contains(&ch) {
-            uniques = Vec::new();
+        // the window restarts right after the earlier occurrence of ch
+        if let Some(pos) = uniques.iter().position(|c| *c == ch) {
+            uniques.drain(..=pos);
         }
         uniques.push(ch);
         if uniques.len() == 14 {
-            return i + 1;
+            return Some(i + 1);
         }
     }
     
-    unreachable!()
+    None
 }
 
-pub fn get_solution_1() -> usize {
-    find_first_packet_marker(include_str!("../data/d06.txt"))
-}
+pub struct Day06;
+
+impl Solution for Day06 {
+    type Parsed<'a> = &'a str;
 
-pub fn get_solution_2() -> usize {
-    find_first_msg_marker(include_str!("../data/d06.txt"))
+    // the markers are searched for on the first line only. a signal without a message marker has
+    // no packet marker either, as any 4 of 14 different letters are different too
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, ParseError> {
+        let line = error::lines(input).next().ok_or(ParseError::end_of_input(input, "a signal"))?;
+        match line.text.find(|c: char| !c.is_ascii_lowercase()) {
+            Some(pos) => Err(line.error_at(line.column(&line.text[pos..]), "a letter from a-z")),
+            None if find_first_msg_marker(line.text).is_none() => Err(line.end_error("a marker of 14 different letters")),
+            None => Ok(line.text),
+        }
+    }
+
+    fn part_1(signal: &&str) -> Answer {
+        find_first_packet_marker(signal).expect("parse checks for a marker").into()
+    }
+
+    fn part_2(signal: &&str) -> Answer {
+        find_first_msg_marker(signal).expect("parse checks for a marker").into()
+    }
 }
\ No newline at end of file
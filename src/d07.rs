@@ -1,76 +1,97 @@
-use std::{fmt::{ Display, Write }, collections::HashMap, path::PathBuf};
+use std::{fmt::{ Display, Write }, collections::HashMap, path::{Path, PathBuf}};
 
-fn parse(input: &'static str) -> Dir {
-    add_dir(&input.lines().map(|line| line.split_whitespace().collect::<Vec<&str>>()).collect::<Vec<Vec<&str>>>(), &mut 0)
+use crate::error::{self, Line, ParseError};
+use crate::repl::Explore;
+use crate::solution::{Answer, Solution};
+
+type Inputs<'a> = Vec<(Line<'a>, Vec<&'a str>)>;
+
+// expects every directory to be listed right after changing into it
+pub fn parse(input: &str) -> Result<Dir<'_>, ParseError> {
+    let inputs = error::lines(input).map(|line| (line, line.text.split_whitespace().collect::<Vec<&str>>())).collect::<Inputs>();
+    if inputs.is_empty() {
+        return Err(ParseError::end_of_input(input, "'$ cd /'"));
+    }
+    add_dir(input, &inputs, &mut 0)
 }
 
-fn add_dir(inputs: &Vec<Vec<&'static str>>, cursor: &mut usize) -> Dir {
-    let cd_cmd = &inputs[*cursor];
-    let mut dir = Dir::new(cd_cmd[2]);
+fn add_dir<'a>(input: &str, inputs: &Inputs<'a>, cursor: &mut usize) -> Result<Dir<'a>, ParseError> {
+    let (line, cd_cmd) = &inputs[*cursor];
+    let mut dir = match cd_cmd[..] {
+        ["$", "cd", name] => Dir::new(name),
+        _ => return Err(line.error(line.text, "'$ cd <dir>'")),
+    };
     *cursor += 1;
-    *cursor += 1; // skip past ls
+    match inputs.get(*cursor) {
+        Some((_, cmd)) if cmd[..] == ["$", "ls"] => *cursor += 1, // skip past ls
+        Some((line, _)) => return Err(line.error(line.text, "'$ ls'")),
+        None => return Err(ParseError::end_of_input(input, "'$ ls'")),
+    }
 
-    add_files(inputs, &mut dir, cursor);
+    add_files(inputs, &mut dir, cursor)?;
     if *cursor >= inputs.len() {
-        return dir;
+        return Ok(dir);
     }
-    while let Some(cmd) = inputs.get(*cursor) {
-        if cmd[2] == ".." {
-            break;
+    while let Some((line, cmd)) = inputs.get(*cursor) {
+        match cmd[..] {
+            ["$", "cd", ".."] => break,
+            ["$", "cd", _] => dir.add_dir(add_dir(input, inputs, cursor)?),
+            _ => return Err(line.error(line.text, "'$ cd <dir>'")),
         }
-        dir.add_dir(add_dir(inputs, cursor));
         *cursor += 1;
     } 
 
-    dir
+    Ok(dir)
 }
 
-fn add_files(inputs: &[Vec<&'static str>], dir: &mut Dir, cursor: &mut usize) {
-    while let Some(cmd) = inputs.get(*cursor) {
-        match cmd[0] {
-            "$" => break,
-            "dir" => (),
-            n => dir.add_file(File::new(cmd[1], n.parse().unwrap())),
+fn add_files<'a>(inputs: &Inputs<'a>, dir: &mut Dir<'a>, cursor: &mut usize) -> Result<(), ParseError> {
+    while let Some((line, cmd)) = inputs.get(*cursor) {
+        match cmd[..] {
+            ["$", ..] => break,
+            ["dir", _] => (),
+            [n, name] => dir.add_file(File::new(name, line.number(n)?)),
+            _ => return Err(line.error(line.text, "'dir <name>' or '<size> <name>'")),
         }
         *cursor += 1;
     }
+    Ok(())
 }
 
 #[derive(Debug)]
-struct File {
-    name: &'static str,
+struct File<'a> {
+    name: &'a str,
     size: usize,
 }
 
-impl File {
-    fn new(name: &'static str, size: usize) -> Self {
+impl<'a> File<'a> {
+    fn new(name: &'a str, size: usize) -> Self {
         Self { name, size }
     }
 }
 
-impl Display for File {
+impl Display for File<'_> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "{} - {}", self.name, self.size)
     }
 }
 
 #[derive(Debug)]
-struct Dir {
-    _name: &'static str,
-    dirs: Vec<Dir>,
-    files: Vec<File>,
+pub struct Dir<'a> {
+    name: &'a str,
+    dirs: Vec<Dir<'a>>,
+    files: Vec<File<'a>>,
 }
 
-impl Dir {
-    fn new(name: &'static str) -> Self {
-        Self { _name: name, dirs: Vec::new(), files: Vec::new() }
+impl<'a> Dir<'a> {
+    fn new(name: &'a str) -> Self {
+        Self { name, dirs: Vec::new(), files: Vec::new() }
     }
 
-    fn add_dir(&mut self, dir: Dir) {
+    fn add_dir(&mut self, dir: Dir<'a>) {
         self.dirs.push(dir);
     }
 
-    fn add_file(&mut self, file: File) {
+    fn add_file(&mut self, file: File<'a>) {
         self.files.push(file);
     }
 
@@ -85,10 +106,19 @@ impl Dir {
         size
     }
 
+    fn size(&self) -> usize {
+        self.files.iter().map(|f| f.size).sum::<usize>() + self.dirs.iter().map(Dir::size).sum::<usize>()
+    }
+
+    // the directory at a path like /a/e, relative to this one
+    fn find(&self, path: &str) -> Option<&Dir<'a>> {
+        path.split('/').filter(|name| !name.is_empty()).try_fold(self, |dir, name| dir.dirs.iter().find(|d| d.name == name))
+    }
+
     fn get_string(&self, indent_lvl: usize) -> String {
         let width = indent_lvl * 2;
         let mut string = String::new();
-        let _ = writeln!(string, "{:width$}{}:", "", self._name);
+        let _ = writeln!(string, "{:width$}{}:", "", self.name);
         for f in &self.files {
             let _ = writeln!(string, "{:width$}  {}", "", f);
         }
@@ -101,100 +131,146 @@ impl Dir {
     }
 }
 
-impl Display for Dir {
+impl Display for Dir<'_> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "{}", self.get_string(0))
     }
 }
 
-pub fn get_solution_1() -> usize {
-    let fs = parse(include_str!("../data/d07.txt"));
-    let mut sizes = Vec::new();
+// the file system in the repl, it has nothing to step through
+pub fn explore(input: &str) -> Result<Box<dyn Explore + '_>, ParseError> {
+    Ok(Box::new(parse(input)?))
+}
 
-    fs.record_all_sizes(&mut sizes);
+impl Explore for Dir<'_> {
+    fn show(&self) -> String {
+        self.to_string()
+    }
 
-    sizes.into_iter().filter(|s| *s <= 100000).sum()
+    fn query(&mut self, query: &[&str]) -> Result<String, String> {
+        let dir = |path: &str| self.find(path).ok_or_else(|| format!("there is no directory {}", path));
+        match query {
+            ["size", path] => Ok(dir(path)?.size().to_string()),
+            ["ls", path] => {
+                let dir = dir(path)?;
+                let dirs = dir.dirs.iter().map(|d| format!("dir {}", d.name));
+                let files = dir.files.iter().map(|f| format!("{} {}", f.size, f.name));
+                Ok(dirs.chain(files).collect::<Vec<_>>().join("\n"))
+            },
+            [name, ..] => Err(format!("{} takes the path of a directory, e.g. {} /a/e", name, name)),
+            [] => Err("expected a query".to_string()),
+        }
+    }
 }
 
-pub fn get_solution_2() -> usize {
-    let fs = parse(include_str!("../data/d07.txt"));
-    let mut sizes = Vec::new();
+pub struct Day07;
 
-    fs.record_all_sizes(&mut sizes);
+impl Solution for Day07 {
+    type Parsed<'a> = Dir<'a>;
 
-    let actual_size = *sizes.iter().max().unwrap();
-    let required_space = 30000000 - (70000000 - actual_size);
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, ParseError> {
+        parse(input)
+    }
+
+    fn part_1(fs: &Dir) -> Answer {
+        let mut sizes = Vec::new();
 
-    sizes.into_iter().filter(|s| *s >= required_space).min().unwrap()
+        fs.record_all_sizes(&mut sizes);
+
+        sizes.into_iter().filter(|s| *s <= 100000).sum::<usize>().into()
+    }
+
+    fn part_2(fs: &Dir) -> Answer {
+        let mut sizes = Vec::new();
+
+        let used = fs.record_all_sizes(&mut sizes);
+
+        smallest_to_delete(used, sizes.into_iter()).into()
+    }
 }
 
-// implement it via hashmap
-type _FileSystem = HashMap<PathBuf, (Vec<usize>, Vec<PathBuf>)>;
+// the smallest directory that frees enough space for the update when deleted, the root always does.
+// any directory does if there is enough space already
+fn smallest_to_delete(used: usize, sizes: impl Iterator<Item = usize>) -> Option<usize> {
+    let required_space = (used + 30000000).saturating_sub(70000000);
+    sizes.filter(|s| *s >= required_space).min()
+}
+
+// the same puzzle solved on a map from every directory's path to its file sizes and subdirectories
+pub type FileSystem = HashMap<PathBuf, (Vec<usize>, Vec<PathBuf>)>;
 
-fn _parse_hash_map(input: &'static str) -> _FileSystem {
-    let mut fs = HashMap::new();
+pub fn parse_hash_map(input: &str) -> Result<FileSystem, ParseError> {
+    let mut fs = FileSystem::new();
     let mut path = PathBuf::new();
-    for parts in input.lines().map(|l| l.split_whitespace().collect::<Vec<&str>>()) {
-        match parts[..] {
-            ["$", "cd", "..", ..] => { path.pop(); },
-            ["$", "cd", dir, ..] => path.push(dir),
-            ["$", "ls", ..] => continue,
-            [..] => { 
-                match parts[..] {
-                    ["dir", dir_name, ..] => {
-                        let mut new_dir = path.clone();
-                        new_dir.push(dir_name);
-                        let (_, sub_dirs) = fs.entry(path.clone()).or_insert((Vec::new(), Vec::new()));
-                        sub_dirs.push(new_dir)
-                    }
-                    [size, ..] => {
-                        let (files, _) = fs.entry(path.clone()).or_insert((Vec::new(), Vec::new()));
-                        files.push(size.parse().unwrap())
-                    },
-                    [..] => continue,
-                }
+    for line in error::lines(input) {
+        match line.text.split_whitespace().collect::<Vec<&str>>()[..] {
+            ["$", "cd", ".."] => { path.pop(); },
+            ["$", "cd", dir] => path.push(dir),
+            // a directory without anything in it still has a size
+            ["$", "ls"] => { fs.entry(path.clone()).or_default(); },
+            ["dir", dir_name] => {
+                let (_, sub_dirs) = fs.entry(path.clone()).or_default();
+                sub_dirs.push(path.join(dir_name));
             },
+            [size, _] if !size.starts_with('$') => {
+                let (files, _) = fs.entry(path.clone()).or_default();
+                files.push(line.number(size)?);
+            },
+            _ => return Err(line.error(line.text, "'$ cd <dir>', '$ ls', 'dir <name>' or '<size> <name>'")),
         }
     }
 
-    fs
+    match fs.contains_key(Path::new("/")) {
+        true => Ok(fs),
+        false => Err(ParseError::end_of_input(input, "'$ cd /' followed by '$ ls'")),
+    }
 }
 
-fn _get_sizes<>(fs: &_FileSystem) -> HashMap<&PathBuf, usize> {
+// the sizes of all directories, every listed subdirectory has to be listed itself as well
+fn get_sizes(fs: &FileSystem) -> HashMap<&PathBuf, usize> {
     let mut sizes = HashMap::new();
     for k in fs.keys() {
         if !sizes.contains_key(k) {
-            let size = _calculate_size(fs, k, &mut sizes);
+            let size = calculate_size(fs, k, &mut sizes);
             sizes.insert(k, size);
         }
     }
     sizes
 }
 
-fn _calculate_size<'file>(fs: &'file _FileSystem, k: &PathBuf, sizes: &mut HashMap<&'file PathBuf, usize>) -> usize {
-    let (files, children) = fs.get(k).unwrap();
+fn calculate_size<'file>(fs: &'file FileSystem, k: &PathBuf, sizes: &mut HashMap<&'file PathBuf, usize>) -> usize {
+    // a directory that was never listed counts as empty, as it does for the tree of Dirs
+    let Some((files, children)) = fs.get(k) else { return 0 };
     let mut size = files.iter().sum();
 
     for child in children {
         if sizes.contains_key(child) {
             size += sizes.get(child).unwrap();
         } else {
-            size += _calculate_size(fs, child, sizes);
+            size += calculate_size(fs, child, sizes);
         }
     }
 
     size
 }
 
-fn _get_solution_map_1() -> usize {
-    let fs = _parse_hash_map(include_str!("../data/d07.txt"));
-    _get_sizes(&fs).values().filter(|v| **v <= 100000).sum()
-}
+pub struct Day07HashMap;
+
+impl Solution for Day07HashMap {
+    type Parsed<'a> = FileSystem;
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, ParseError> {
+        parse_hash_map(input)
+    }
 
-fn _get_solution_map_2() -> usize {
-    let fs = _parse_hash_map(include_str!("../data/d07.txt"));
-    let sizes = _get_sizes(&fs);
-    let max = sizes.get(&PathBuf::from("/")).unwrap();
-    let required_space = 30000000 - (70000000 - max);
-    *sizes.values().filter(|v| **v >= required_space).min().unwrap()
-}
\ No newline at end of file
+    fn part_1(fs: &FileSystem) -> Answer {
+        get_sizes(fs).values().filter(|v| **v <= 100000).sum::<usize>().into()
+    }
+
+    fn part_2(fs: &FileSystem) -> Answer {
+        let sizes = get_sizes(fs);
+        // parse checks that the root was listed
+        let used = sizes[&PathBuf::from("/")];
+        smallest_to_delete(used, sizes.into_values()).into()
+    }
+}
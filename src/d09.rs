@@ -1,5 +1,8 @@
 use std::collections::HashMap;
-use crate::helpers::BitMap;
+use crate::helpers::BitGrid;
+use crate::error::{self, ParseError};
+use crate::repl::Explore;
+use crate::solution::{Answer, Solution};
 
 trait Knot {
     fn add(&mut self, other: &Self);
@@ -27,39 +30,120 @@ impl Knot for (isize, isize) {
     }
 }
 
-fn parse(input: &str) -> Vec<((isize, isize), usize)> {
+// a direction and how many steps to take in it
+type Steps = Vec<((isize, isize), usize)>;
+
+pub fn parse(input: &str) -> Result<Steps, ParseError> {
     let dirs = HashMap::from([("U", (0, 1)), ("D", (0, -1)), ("R", (1, 0)), ("L", (-1, 0))]);
     
-    input.lines()
-         .map(|l| l.split_ascii_whitespace().collect::<Vec<&str>>())
-         .map(|parts| (*dirs.get(parts[0]).unwrap(), parts[1].parse::<usize>().unwrap()))
+    error::lines(input)
+         .map(|line| {
+            let [dir, n] = line.words::<2>()?;
+            let dir = *dirs.get(dir).ok_or_else(|| line.error(dir, "U, D, R or L"))?;
+            Ok((dir, line.number::<usize>(n)?))
+         })
          .collect()
 }
 
-fn execute_steps<const N: usize>(steps: Vec<((isize, isize), usize)>) -> usize {
-    let mut visited = [0; 8192];
-    let mut knots = [(512, 512); N];
+// moves the head a single step, the other knots follow
+fn pull(knots: &mut [(isize, isize)], dir: &(isize, isize)) {
+    knots[0].add(dir);
+    for i in 1..knots.len() {
+        let diff = knots[i - 1].diff(&knots[i]);
+        if diff.moves() {
+            knots[i].add(&diff.ident());
+        }
+    }
+}
 
+// the rectangle the head moves in, which the other knots never leave, and where in it the rope starts
+fn bounds(steps: &[((isize, isize), usize)]) -> (BitGrid, (isize, isize)) {
+    let (mut min, mut max, mut head) = ((0, 0), (0, 0), (0, 0));
     for (dir, n_steps) in steps {
-        for _ in 0..n_steps {
-            knots[0].add(&dir);
-            for i in 1..N {
-                let diff = knots[i - 1].diff(&knots[i]);
-                if diff.moves() {
-                    knots[i].add(&diff.ident());
-                }
-            }
-            visited.set_bit(knots[N - 1].1 as usize, knots[N - 1].0 as usize);
+        head = (head.0 + dir.0 * *n_steps as isize, head.1 + dir.1 * *n_steps as isize);
+        min = (min.0.min(head.0), min.1.min(head.1));
+        max = (max.0.max(head.0), max.1.max(head.1));
+    }
+    (BitGrid::new((max.0 - min.0 + 1) as usize, (max.1 - min.1 + 1) as usize), (-min.0, -min.1))
+}
+
+fn execute_steps<const N: usize>(steps: &[((isize, isize), usize)]) -> usize {
+    let (mut visited, start) = bounds(steps);
+    let mut knots = [start; N];
+
+    for (dir, n_steps) in steps {
+        for _ in 0..*n_steps {
+            pull(&mut knots, dir);
+            visited.set(knots[N - 1].1 as usize, knots[N - 1].0 as usize);
         }
     } 
 
-    visited.count_bits()
+    visited.count()
+}
+
+// the rope of part 2 in the repl, a tick moves the head a single step
+pub struct Rope {
+    steps: Steps,
+    knots: [(isize, isize); 10],
+    visited: BitGrid,
+    start: (isize, isize),
+    // the next move and the steps already taken of it
+    next: (usize, usize),
 }
 
-pub fn get_solution_1() -> usize {
-    execute_steps::<2>(parse(include_str!("../data/d09.txt")))
+pub fn explore(input: &str) -> Result<Box<dyn Explore + '_>, ParseError> {
+    let steps = parse(input)?;
+    let (mut visited, start) = bounds(&steps);
+    visited.set(start.1 as usize, start.0 as usize);
+    Ok(Box::new(Rope { steps, knots: [start; 10], visited, start, next: (0, 0) }))
 }
 
-pub fn get_solution_2() -> usize {
-    execute_steps::<10>(parse(include_str!("../data/d09.txt")))
+impl Explore for Rope {
+    fn step(&mut self, n: usize) -> Option<String> {
+        for _ in 0..n {
+            // a move is done after its last step, which is right away for moves of 0 steps
+            while self.steps.get(self.next.0).is_some_and(|&(_, n_steps)| self.next.1 == n_steps) {
+                self.next = (self.next.0 + 1, 0);
+            }
+            let Some((dir, _)) = self.steps.get(self.next.0) else { break };
+            pull(&mut self.knots, dir);
+            self.visited.set(self.knots[9].1 as usize, self.knots[9].0 as usize);
+            self.next.1 += 1;
+        }
+        Some(match self.steps.get(self.next.0) {
+            Some((_, n_steps)) => format!("move {} of {}, {} of {} steps taken", self.next.0 + 1, self.steps.len(), self.next.1, n_steps),
+            None => "all moves done".to_string(),
+        })
+    }
+
+    // the positions the tail visited
+    fn show(&self) -> String {
+        self.visited.render()
+    }
+
+    fn query(&mut self, query: &[&str]) -> Result<String, String> {
+        match query {
+            ["knots"] => Ok(self.knots.iter().map(|(x, y)| format!("{},{}", x - self.start.0, y - self.start.1)).collect::<Vec<_>>().join(" ")),
+            ["visited"] => Ok(self.visited.count().to_string()),
+            _ => Err(format!("{} takes no arguments", query[0])),
+        }
+    }
+}
+
+pub struct Day09;
+
+impl Solution for Day09 {
+    type Parsed<'a> = Steps;
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, ParseError> {
+        parse(input)
+    }
+
+    fn part_1(steps: &Steps) -> Answer {
+        execute_steps::<2>(steps).into()
+    }
+
+    fn part_2(steps: &Steps) -> Answer {
+        execute_steps::<10>(steps).into()
+    }
 }
\ No newline at end of file
@@ -1,19 +1,22 @@
 use std::fmt::Write;
 
-fn parse(input: &str) -> Vec<(usize, i64)> {
-    input.lines()
-         .map(|l| if l.starts_with("noop") { 
-                (1, 0) 
-            } else {
-                (2, l[l.find(' ').unwrap() + 1..].parse::<i64>().unwrap() ) 
+use crate::error::{self, ParseError};
+use crate::solution::{Answer, Solution};
+
+pub fn parse(input: &str) -> Result<Vec<(usize, i64)>, ParseError> {
+    error::lines(input)
+         .map(|line| match line.text.split_once(' ') {
+                None if line.text == "noop" => Ok((1, 0)),
+                Some(("addx", n)) => Ok((2, line.number::<i64>(n)?)),
+                _ => Err(line.error(line.text, "'noop' or 'addx <n>'")),
             }
          ).collect()
 }
 
-fn execute_instr(instructions: Vec<(usize, i64)>) -> i64 {
+fn execute_instr(instructions: &[(usize, i64)]) -> i64 {
     let (mut x, mut i_count, mut signal_strengths) = (1, 0, 0);
 
-    for (cycles, n) in instructions {
+    for &(cycles, n) in instructions {
         for _ in 0..cycles {
             i_count += 1;
             if (i_count + 20) % 40 == 0 {
@@ -26,13 +29,15 @@ fn execute_instr(instructions: Vec<(usize, i64)>) -> i64 {
     signal_strengths
 }
 
-fn draw_screen(instructions: Vec<(usize, i64)>) -> [char; 240] {
-    let (mut x, mut i_count, mut screen) = (1, 0, ['.'; 240]);
+fn draw_screen(instructions: &[(usize, i64)]) -> [char; 240] {
+    let (mut x, mut i_count, mut screen) = (1_i64, 0, ['.'; 240]);
 
-    for (cycles, n) in instructions {
+    for &(cycles, n) in instructions {
         for _ in 0..cycles {
-            if (x as usize).abs_diff(i_count % 40) <= 1 {
-                screen[i_count] = '#';
+            // the screen is full after 240 cycles, the rest of the program draws nothing
+            let Some(pixel) = screen.get_mut(i_count) else { return screen };
+            if x.abs_diff((i_count % 40) as i64) <= 1 {
+                *pixel = '#';
             }
             i_count += 1;
         }
@@ -42,6 +47,40 @@ fn draw_screen(instructions: Vec<(usize, i64)>) -> [char; 240] {
     screen
 }
 
+// the letters of the crt font, each one is 4 pixels wide and 6 pixels high,
+// written row by row
+static FONT: [(char, &str); 17] = [
+    ('A', ".##.#..##..######..##..#"),
+    ('B', "###.#..####.#..##..####."),
+    ('C', ".##.#..##...#...#..#.##."),
+    ('E', "#####...###.#...#...####"),
+    ('F', "#####...###.#...#...#..."),
+    ('G', ".##.#..##...#.###..#.###"),
+    ('H', "#..##..######..##..##..#"),
+    ('I', ".###..#...#...#...#..###"),
+    ('J', "..##...#...#...##..#.##."),
+    ('K', "#..##.#.##..#.#.#.#.#..#"),
+    ('L', "#...#...#...#...#...####"),
+    ('O', ".##.#..##..##..##..#.##."),
+    ('P', "###.#..##..####.#...#..."),
+    ('R', "###.#..##..####.#.#.#..#"),
+    ('S', ".####...#....##....####."),
+    ('U', "#..##..##..##..##..#.##."),
+    ('Z', "####...#..#..#..#...####"),
+];
+
+// the screen shows 8 letters, each letter takes up 5 columns (4 + 1 space)
+fn read_letters(screen: &[char; 240]) -> Option<String> {
+    let mut letters = String::new();
+    for letter_col in (0..40).step_by(5) {
+        let glyph = screen.chunks(40)
+                          .flat_map(|row| &row[letter_col..letter_col + 4])
+                          .collect::<String>();
+        letters.push(FONT.iter().find(|(_, pixels)| *pixels == glyph)?.0);
+    }
+    Some(letters)
+}
+
 fn to_string(screen: &[char; 240]) -> String {
     let mut string = String::new();
     for line in screen.chunks(40) {
@@ -50,11 +89,31 @@ fn to_string(screen: &[char; 240]) -> String {
     string
 }
 
-pub fn get_solution_1() -> i64 {
-    execute_instr(parse(include_str!("../data/d10.txt"))) 
+pub struct Day10;
+
+impl Solution for Day10 {
+    type Parsed<'a> = Vec<(usize, i64)>;
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, ParseError> {
+        parse(input)
+    }
+
+    fn part_1(instructions: &Vec<(usize, i64)>) -> Answer {
+        execute_instr(instructions).into()
+    }
+
+    fn part_2(instructions: &Vec<(usize, i64)>) -> Answer {
+        let screen = draw_screen(instructions);
+        read_letters(&screen).unwrap_or_else(|| to_string(&screen)).into()
+    }
 }
 
-pub fn get_solution_2() -> &'static str {
-    let _screen = to_string(&draw_screen(parse(include_str!("../data/d10.txt"))));
-    "PZULBAUA" // prints this
+#[test]
+fn test_read_letters() {
+    let screen = draw_screen(&parse(include_str!("../data/d10.txt")).unwrap());
+    assert_eq!(read_letters(&screen), Some("PZULBAUA".to_string()));
+
+    // the example draws a test pattern instead of letters
+    let screen = draw_screen(&parse(include_str!("../data/d10_test.txt")).unwrap());
+    assert_eq!(read_letters(&screen), None);
 }
\ No newline at end of file
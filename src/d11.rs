@@ -1,9 +1,14 @@
 use std::cell::RefCell;
 
-static INPUT: &str = include_str!("../data/d11.txt");
+use crate::error::{self, ParseError};
+use crate::helpers;
+use crate::repl::Explore;
+use crate::solution::{Answer, Solution};
+
 static _TEST: &str = include_str!("../data/d11_test.txt");
 
-struct Monkey {
+#[derive(Clone)]
+pub struct Monkey {
     items: RefCell<Vec<u64>>,
     op: Op,
     test: u64,
@@ -29,17 +34,17 @@ impl Monkey {
     }
 }
 
-fn do_round(monkeys: &[Monkey], inspected: &mut [u64; 8], m: Option<u64>) {
+fn do_round(monkeys: &[Monkey], inspected: &mut [u64; MAX_MONKEYS], m: Option<u64>) {
     for (i, monkey) in monkeys.iter().enumerate() {
         monkey.throw_items(monkeys, inspected.get_mut(i).unwrap(), m);
     }
 }
 
-fn do_n_rounds(n: u64, with_mod: bool) -> u64 {
-    let monkeys = parse(INPUT);
-    let mut inspected = [0; 8];
+fn do_n_rounds(monkeys: Vec<Monkey>, n: u64, with_mod: bool) -> u64 {
+    let mut inspected = [0; MAX_MONKEYS];
     let m = if with_mod {
-            Some(monkeys.iter().map(|m| m.test).product())
+            // the smallest number every test divides, so worry levels modulo it divide the same
+            Some(monkeys.iter().map(|m| m.test).fold(1, helpers::lcm))
         } else {
             None
         };
@@ -53,14 +58,72 @@ fn do_n_rounds(n: u64, with_mod: bool) -> u64 {
     inspected[0..2].iter().product() 
 }
 
-pub fn get_solution_1() -> u64{
-    do_n_rounds(20, false)
+// the monkeys of part 1 in the repl, a tick is a round
+pub struct Monkeys {
+    monkeys: Vec<Monkey>,
+    inspected: [u64; MAX_MONKEYS],
+    rounds: usize,
+}
+
+pub fn explore(input: &str) -> Result<Box<dyn Explore + '_>, ParseError> {
+    Ok(Box::new(Monkeys { monkeys: parse(input)?, inspected: [0; MAX_MONKEYS], rounds: 0 }))
 }
 
-pub fn get_solution_2() -> u64{
-    do_n_rounds(10000, true)
+impl Monkeys {
+    fn items(&self, i: usize) -> String {
+        let items = self.monkeys[i].items.borrow().iter().map(u64::to_string).collect::<Vec<_>>();
+        format!("Monkey {}: {}", i, items.join(", "))
+    }
 }
 
+impl Explore for Monkeys {
+    fn step(&mut self, n: usize) -> Option<String> {
+        for _ in 0..n {
+            do_round(&self.monkeys, &mut self.inspected, None);
+        }
+        self.rounds += n;
+        Some(format!("after round {}", self.rounds))
+    }
+
+    fn show(&self) -> String {
+        (0..self.monkeys.len()).map(|i| self.items(i)).collect::<Vec<_>>().join("\n")
+    }
+
+    fn query(&mut self, query: &[&str]) -> Result<String, String> {
+        match query {
+            ["items"] => Ok(self.show()),
+            ["items", monkey] => match monkey.parse::<usize>() {
+                Ok(i) if i < self.monkeys.len() => Ok(self.items(i)),
+                _ => Err(format!("there is no monkey {}", monkey)),
+            },
+            ["inspected"] => Ok((0..self.monkeys.len())
+                .map(|i| format!("Monkey {}: {}", i, self.inspected[i]))
+                .collect::<Vec<_>>()
+                .join("\n")),
+            _ => Err(format!("unexpected arguments to {}", query[0])),
+        }
+    }
+}
+
+pub struct Day11;
+
+impl Solution for Day11 {
+    type Parsed<'a> = Vec<Monkey>;
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, ParseError> {
+        parse(input)
+    }
+
+    fn part_1(monkeys: &Vec<Monkey>) -> Answer {
+        do_n_rounds(monkeys.clone(), 20, false).into()
+    }
+
+    fn part_2(monkeys: &Vec<Monkey>) -> Answer {
+        do_n_rounds(monkeys.clone(), 10000, true).into()
+    }
+}
+
+#[derive(Clone)]
 enum Op {
     Mul(u64),
     Add(u64),
@@ -77,29 +140,41 @@ impl Op {
     }
 }
 
-fn parse(input: &'static str) -> Vec<Monkey> {
+// the number of monkeys do_round keeps track of
+const MAX_MONKEYS: usize = 8;
+
+pub fn parse(input: &str) -> Result<Vec<Monkey>, ParseError> {
     let mut monkeys = Vec::new();
 
-    for parts in input.lines().collect::<Vec<&str>>().chunks(7) {
-        let items = parts[1][parts[1].find(':').unwrap() + 2..].split(',')
-                                                               .map(|n| n.trim().parse().unwrap())
-                                                               .collect();
+    for parts in error::lines(input).collect::<Vec<_>>().chunks(7) {
+        let [_, items, op, test, nb_true, nb_false] = parts[..parts.len().min(6)] else {
+            return Err(ParseError::end_of_input(input, "the remaining lines of a monkey"));
+        };
+
+        let items = items.after("  Starting items: ")?
+                         .split(", ")
+                         .map(|n| items.number(n))
+                         .collect::<Result<_, _>>()?;
         
-        let op = match parts[2].split_whitespace().collect::<Vec<&str>>()[..] {
-            [_, _, _, _, op, n] => match (op, n) {
-                ("*", "old") => Op::Sqr,
-                ("*", n) => Op::Mul(n.parse().unwrap()),
-                ("+", n) => Op::Add(n.parse().unwrap()),
-                _ => unreachable!(),
-            },
-            _ => unreachable!(),
+        let op = match op.words::<6>()? {
+            [_, _, _, "old", "*", "old"] => Op::Sqr,
+            [_, _, _, "old", "*", n] => Op::Mul(op.number(n)?),
+            [_, _, _, "old", "+", n] => Op::Add(op.number(n)?),
+            [.., o, _] => return Err(op.error(o, "'*' or '+'")),
+        };
+        let divisor = test.after("  Test: divisible by ")?;
+        let test = match test.number(divisor)? {
+            0 => return Err(test.error(divisor, "a divisor other than 0")),
+            n => n,
         };
-        let test = parts[3][parts[3].find("by").unwrap() + 3..].parse().unwrap();
-        let nb_true = parts[4][parts[4].len() - 1..].parse().unwrap();
-        let nb_false = parts[5][parts[5].len() - 1..].parse().unwrap();
+        let nb_true = nb_true.number(nb_true.after("    If true: throw to monkey ")?)?;
+        let nb_false = nb_false.number(nb_false.after("    If false: throw to monkey ")?)?;
 
         monkeys.push(Monkey::new(RefCell::new(items), op, test, [nb_true, nb_false]))
     }
 
-    monkeys
+    if monkeys.len() > MAX_MONKEYS || monkeys.iter().flat_map(|m| m.nb).any(|nb| nb >= monkeys.len()) {
+        return Err(ParseError::new(1, 1, format!("at most {} monkeys, all throwing to one of them", MAX_MONKEYS)));
+    }
+    Ok(monkeys)
 }
\ No newline at end of file
@@ -1,164 +1,133 @@
-use std::{collections::BinaryHeap, fmt::Display, sync::Arc};
+use std::fmt::Display;
+
+use crate::error::ParseError;
+use crate::frames::{Frame, Recorder};
+use crate::helpers::{self, Grid};
+use crate::solution::{Answer, Solution};
 
-static INPUT: &str = include_str!("../data/d12.txt");
 static _TEST: &str = include_str!("../data/d12_test.txt");
 
+type Pos = (usize, usize); // row, col
+
 #[derive(Debug, Clone)]
-struct Graph {
-    start: Node,
-    goal: Node,
-    height_map: Vec<i8>,
-    dim: (usize, usize) // row, col
+pub struct Graph {
+    start: Pos,
+    goal: Pos,
+    height_map: Grid<i8>,
 }
 
 impl Display for Graph {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        for row in self.height_map.chunks(self.dim.1) {
-            let _ = writeln!(f, "{}", row.iter()
-                                         .map(|n| match *n { 
-                                            36 => '.',
-                                            n => char::from_digit(n as u32, 36).unwrap_or('#'),  
-                                         })
-                                         .collect::<String>());
-        }
-
-        Ok(())
+        write!(f, "{}", self.height_map.render(|n| match *n {
+            36 => '.',
+            n => char::from_digit(n as u32, 36).unwrap_or('#'),
+        }))
     }
 }
 
 impl Graph {
-    fn idx(&self, (row, col): (usize, usize)) -> usize {
-        self.dim.1 * row + col
+    pub fn height(&self, pos: Pos) -> Option<i8> {
+        self.height_map.get(pos).copied()
     }
 
-    fn coords(&self, idx: usize) -> (usize, usize) {
-        (idx / self.dim.1, idx % self.dim.1)
+    // return only neighbours which are same, or one level higher
+    fn neighbours(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        let height = self.height_map[pos];
+        self.height_map.neighbours(pos).filter(move |nb| self.height_map[*nb] - height < 2)
     }
 
-    fn height(&self, (row, col): (usize, usize)) -> Option<i8> {
-        if row >= self.dim.0 || col >= self.dim.1 {
-            return None;
-        } 
-        Some(self.height_map[self.idx((row, col))])
+    // the fewest steps from any of the starts to the goal, None if the goal is out of reach
+    fn walk(&self, starts: impl IntoIterator<Item = Pos>) -> Option<usize> {
+        helpers::bfs(starts, |pos| self.neighbours(*pos), |pos| *pos == self.goal).distance()
     }
 
-    // return only neighbours which are same, or one level higher
-    fn neighbours(&self, node: &Node) -> Vec<Node> {
-        let (row, col) = node.pos;
-        let mut nb = Vec::new();
-        let others_pos = [(row, col.overflowing_sub(1).0), (row, col + 1), (row.overflowing_sub(1).0, col), (row + 1, col)];
-
-        for pos in others_pos {
-            match self.height(pos) {
-                Some(other) if other - node.height < 2 => nb.push(Node { height: other, pos, cost: node.cost + 1 }),
-                _ => continue,
-            }
-        }
-        nb
+    // the fewest steps from any of the lowest squares
+    fn hike(&self) -> Option<usize> {
+        self.walk(self.height_map.iter().filter(|(_, n)| **n == 0).map(|(pos, _)| pos))
     }
-    
-    fn walk(&mut self) -> Option<usize> {
-        let mut queue = BinaryHeap::new();
-        queue.push(self.start);
- 
-        while !queue.is_empty() {
-            let cur =  queue.pop().unwrap();
-            for nb in self.neighbours(&cur) {
-                if nb.pos == self.goal.pos {
-                    return Some(nb.cost);
-                }
-                let idx = self.idx(nb.pos);
-                self.height_map[idx] = i8::MAX;        
-                queue.push(nb);
-            }
-        }
+}
 
-        None
-    }
+pub fn parse(input: &str) -> Result<Graph, ParseError> {
+    let mut start = None;
+    let mut goal = None;
 
-    fn hike(g: Self) -> usize {
-        let n_threads = 4;
-        let starting_positions = Arc::new(g.height_map.iter()
-                                        .enumerate()
-                                        .filter(|(_, n)| **n == 0)
-                                        .map(|(idx, _)| g.coords(idx))
-                                        .collect::<Vec<(usize, usize)>>());
-        let chunk_size = starting_positions.len() / n_threads;
-        let mut paths = Vec::new(); 
-        let mut handles = Vec::new();
-        
-        for id in 0..n_threads {
-            let g_thread = g.clone();
-            let thread_positions = Arc::clone(&starting_positions);
-            let handle = std::thread::spawn(move || {
-                let mut paths = Vec::new();
-                for pos in &thread_positions[id * chunk_size..id * chunk_size + chunk_size] {
-                    let mut g_iter = g_thread.clone();
-                    g_iter.start = Node { height: 0, pos: *pos, cost: 0 };
-                    if let Some(steps) = g_iter.walk() {
-                        paths.push(steps)
-                    }
-                }
-                paths
-            });
+    let height_map = Grid::parse(input, "squares", |pos, c| match c {
+        'S' if start.is_none() => { start = Some(pos); Ok(0) },
+        'E' if goal.is_none() => { goal = Some(pos); Ok(26) },
+        'a'..='z' => Ok((c as u8 - b'a') as i8),
+        _ => Err("a height from a-z, or a single S and E"),
+    })?;
 
-            handles.push(handle);
-        }
+    match (start, goal) {
+        (Some(start), Some(goal)) => Ok(Graph { start, goal, height_map }),
+        _ => Err(ParseError::end_of_input(input, "a start S and a goal E")),
+    }
+}
 
-        for handle in handles {
-            if let Some(min_cost) = handle.join().unwrap().into_iter().min() {
-                paths.push(min_cost);
+// a breadth first search from the start with a frame per step further away, the squares it reached
+// are uppercase. the last frame shows the way to the goal
+pub fn record(input: &str, recorder: &mut Recorder) -> Result<(), ParseError> {
+    let g = parse(input)?;
+    let mut reached = g.height_map.map(|_| false);
+    let mut from = g.height_map.map(|_| None);
+    reached[g.start] = true;
+    let mut layer = vec![g.start];
+
+    while !layer.is_empty() && !reached[g.goal] {
+        recorder.tick(|| g.frame(&reached, &[]));
+        let mut next = Vec::new();
+        for pos in layer {
+            for nb in g.neighbours(pos) {
+                if !reached[nb] {
+                    reached[nb] = true;
+                    from[nb] = Some(pos);
+                    next.push(nb);
+                }
             }
         }
-        
-        paths.into_iter().min().unwrap()
+        layer = next;
     }
-}
-
-#[derive(PartialEq, Eq, Hash, Clone, Copy, Debug)]
-struct Node {
-    height: i8,
-    pos: (usize, usize), // row, col
-    cost: usize,
-}
 
-impl Ord for Node {
-    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-        self.cost.cmp(&other.cost).reverse()
+    let mut path = Vec::new();
+    let mut pos = reached[g.goal].then_some(g.goal);
+    while let Some(p) = pos {
+        path.push(p);
+        pos = from[p];
     }
+    recorder.last(|| g.frame(&reached, &path));
+    Ok(())
 }
 
-impl PartialOrd for Node {
-    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        self.cost.partial_cmp(&other.cost).map(|ord| ord.reverse())
+impl Graph {
+    fn frame(&self, reached: &Grid<bool>, path: &[Pos]) -> Frame {
+        let mut frame = Frame::from_text(&self.height_map.render(|height| (b'a' + (*height).clamp(0, 25) as u8) as char));
+        for (pos, _) in reached.iter().filter(|(_, reached)| **reached) {
+            let c = frame.get(pos.0, pos.1).unwrap_or(' ');
+            frame.set(pos.0, pos.1, c.to_ascii_uppercase());
+        }
+        for &(row, col) in path {
+            frame.set(row, col, '*');
+        }
+        frame.set(self.start.0, self.start.1, '@');
+        frame.set(self.goal.0, self.goal.1, '@');
+        frame
     }
 }
 
-fn parse(input: &str) -> Graph {
-    let mut nodes = Vec::new();
-    let cols = input.find(['\r', '\n']).unwrap();
-    let rows = input.len() / cols;
-    let mut start = Node { height: 0, pos: (0, 0), cost: 0};
-    let mut goal = Node { height: 26, pos: (0, 0), cost: usize::MAX };
-
-    for (row, line) in input.lines().enumerate() {
-        for (col, c) in line.chars().enumerate() {
-            match c {
-                'S' => { start.pos = (row, col); nodes.push(0) },
-                'E' => { goal.pos = (row, col); nodes.push(26) },
-                c => nodes.push((c.to_digit(36).unwrap() - 10) as i8),
-            }
-        }
+pub struct Day12;
+
+impl Solution for Day12 {
+    type Parsed<'a> = Graph;
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, ParseError> {
+        parse(input)
     }
-    Graph { start, goal, height_map: nodes, dim: (rows, cols) }
-}
 
-pub fn get_solution_1() -> usize {
-    let mut g = parse(INPUT);
-    g.walk().unwrap_or(0)
-}
+    fn part_1(g: &Graph) -> Answer {
+        g.walk([g.start]).into()
+    }
 
-pub fn get_solution_2() -> usize {
-    let g = parse(INPUT);
-    Graph::hike(g)
+    fn part_2(g: &Graph) -> Answer {
+        g.hike().into()
+    }
 }
\ No newline at end of file
@@ -1,10 +1,12 @@
 use std::cmp::Ordering;
 
-static INPUT: &str = include_str!("../data/d13.txt");
+use crate::error::{self, Line, ParseError};
+use crate::solution::{Answer, Solution};
+
 static _TEST: &str = include_str!("../data/d13_test.txt");
 
-#[derive(Debug)]
-enum Packet {
+#[derive(Debug, Clone)]
+pub enum Packet {
     List(Vec<Packet>),
     Value(u64),
 }
@@ -40,16 +42,17 @@ impl Packet {
     }
 }
 
-struct PacketParser {
-    tokens: std::vec::IntoIter<&'static str>,
+struct PacketParser<'a> {
+    line: Line<'a>,
+    tokens: std::vec::IntoIter<&'a str>,
 }
 
-impl PacketParser {
-    fn new(input: &'static str) -> Self {
-        Self { tokens: Self::tokenize(input) }
+impl<'a> PacketParser<'a> {
+    fn new(line: Line<'a>) -> Self {
+        Self { line, tokens: Self::tokenize(line.text) }
     }
 
-    fn tokenize(input: &'static str) -> std::vec::IntoIter<&'static str> {
+    fn tokenize(input: &'a str) -> std::vec::IntoIter<&'a str> {
         let mut tokens = Vec::new();
         let mut chars = input.chars().enumerate().peekable();
         while let Some((i, c)) = chars.next() {
@@ -61,6 +64,7 @@ impl PacketParser {
                     let end = loop {
                         match chars.peek() {
                             Some((j, c)) if !c.is_numeric() => break *j,
+                            None => break input.len(),
                             _ => chars.next(),
                         };
                     };
@@ -73,37 +77,48 @@ impl PacketParser {
         tokens.into_iter()
     }
 
-    fn parse(&mut self) -> Packet {
-        assert_eq!(self.tokens.next(), Some("["));
-        Packet::List(self.parse_list())
+    fn parse(&mut self) -> Result<Packet, ParseError> {
+        match self.tokens.next() {
+            Some("[") => (),
+            Some(tk) => return Err(self.line.error(tk, "'['")),
+            None => return Err(self.line.end_error("a packet")),
+        }
+        let packet = Packet::List(self.parse_list()?);
+        match self.tokens.next() {
+            Some(tk) => Err(self.line.error(tk, "the end of the packet")),
+            None => Ok(packet),
+        }
     }
 
-    fn parse_list(&mut self) -> Vec<Packet> {
+    fn parse_list(&mut self) -> Result<Vec<Packet>, ParseError> {
         let mut list = Vec::new();
         while let Some(tk) = self.tokens.next() {
             match tk {
-                "]" => return list,
-                "[" => list.push(Packet::List(self.parse_list())),
-                n => list.push(Packet::Value(n.parse::<u64>().unwrap())),
+                "]" => return Ok(list),
+                "[" => list.push(Packet::List(self.parse_list()?)),
+                n => list.push(Packet::Value(self.line.number::<u64>(n)?)),
             }
         }
 
-        list
+        Err(self.line.end_error("']'"))
     }
 }
 
-fn parse(input: &'static str) -> Vec<(Packet, Packet)> {
+pub fn parse(input: &str) -> Result<Vec<(Packet, Packet)>, ParseError> {
     let mut packets = Vec::new();
-    for chunk in input.lines().collect::<Vec<&str>>().chunks(3) {
-        packets.push((PacketParser::new(chunk[0]).parse(), PacketParser::new(chunk[1]).parse()));
+    for chunk in error::lines(input).collect::<Vec<_>>().chunks(3) {
+        let [lhs, rhs, ..] = chunk else {
+            return Err(ParseError::end_of_input(input, "the second packet of a pair"));
+        };
+        packets.push((PacketParser::new(*lhs).parse()?, PacketParser::new(*rhs).parse()?));
     }
-    packets
+    Ok(packets)
 }
 
-fn sum_indices(pairs: Vec<(Packet, Packet)>) -> usize {
+fn sum_indices(pairs: &[(Packet, Packet)]) -> usize {
     let mut sum = 0;
-    for (i, (lhs, rhs)) in pairs.into_iter().enumerate() {
-        if let Ordering::Less = Packet::compare(&lhs, &rhs) {
+    for (i, (lhs, rhs)) in pairs.iter().enumerate() {
+        if let Ordering::Less = Packet::compare(lhs, rhs) {
             sum += i + 1;
         }
     }
@@ -111,8 +126,8 @@ fn sum_indices(pairs: Vec<(Packet, Packet)>) -> usize {
     sum
 }
 
-fn flatten_pairs(pairs: Vec<(Packet, Packet)>) -> Vec<Packet> {
-    pairs.into_iter().flat_map(|(lhs, rhs)| [lhs, rhs]).collect()
+fn flatten_pairs(pairs: &[(Packet, Packet)]) -> Vec<Packet> {
+    pairs.iter().flat_map(|(lhs, rhs)| [lhs.clone(), rhs.clone()]).collect()
 }
 
 fn add_divider_packets(packets: &mut Vec<Packet>) {
@@ -136,13 +151,23 @@ fn get_divider_indices(packets: &[Packet]) -> Vec<usize> {
     indices
 }
 
-pub fn get_solution_1() -> usize {
-    sum_indices(parse(INPUT))
-}
+pub struct Day13;
+
+impl Solution for Day13 {
+    type Parsed<'a> = Vec<(Packet, Packet)>;
 
-pub fn get_solution_2() -> usize {
-    let mut packets = flatten_pairs(parse(INPUT));
-    add_divider_packets(&mut packets);
-    packets.sort_by(Packet::compare);
-    get_divider_indices(&packets).into_iter().product()
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, ParseError> {
+        parse(input)
+    }
+
+    fn part_1(pairs: &Vec<(Packet, Packet)>) -> Answer {
+        sum_indices(pairs).into()
+    }
+
+    fn part_2(pairs: &Vec<(Packet, Packet)>) -> Answer {
+        let mut packets = flatten_pairs(pairs);
+        add_divider_packets(&mut packets);
+        packets.sort_by(Packet::compare);
+        get_divider_indices(&packets).into_iter().product::<usize>().into()
+    }
 }
\ No newline at end of file
@@ -1,6 +1,10 @@
 use std::{fmt::Display, ops::{Index, IndexMut}};
 
-static INPUT: &str = include_str!("../data/d14.txt");
+use crate::error::{self, ParseError};
+use crate::frames::{Frame, Recorder};
+use crate::repl::Explore;
+use crate::solution::{Answer, Solution};
+
 static _TEST: &str = include_str!("../data/d14_test.txt");
 
 const CAVE_ROWS: usize = 164;
@@ -8,32 +12,44 @@ const CAVE_COLS: usize = 500 ;
 const LEFT_SHIFT: usize = CAVE_COLS / 2;
 const START: usize = 500 - LEFT_SHIFT;
 
-fn parse(input: &str) -> Vec<Vec<(usize, usize)>> {
+// the rocks have to fit into the fixed size cave, leaving room for the floor below them
+pub fn parse(input: &str) -> Result<Vec<Vec<(usize, usize)>>, ParseError> {
     let mut structure = Vec::new();
 
-    for line in input.lines() {
-        let coords = line.split("->").collect::<Vec<&str>>();
+    for line in error::lines(input) {
         let mut cave_line = Vec::new();
-        for coord in coords {
-            let n_coord = coord.split(',').collect::<Vec<&str>>();
-            cave_line.push((n_coord[0].trim().parse::<usize>().unwrap() - LEFT_SHIFT, n_coord[1].trim().parse::<usize>().unwrap()));
+        for coord in line.text.split("->") {
+            let (x, y) = line.split_once(coord.trim(), ",")?;
+            let x = match line.number::<usize>(x)? {
+                n if (LEFT_SHIFT..LEFT_SHIFT + CAVE_COLS).contains(&n) => n - LEFT_SHIFT,
+                _ => return Err(line.error(x, format!("x from {} to {}", LEFT_SHIFT, LEFT_SHIFT + CAVE_COLS - 1))),
+            };
+            let y = match line.number::<usize>(y)? {
+                n if n < CAVE_ROWS - 2 => n,
+                _ => return Err(line.error(y, format!("y below {}", CAVE_ROWS - 2))),
+            };
+            cave_line.push((x, y));
         }
         structure.push(cave_line);
     }
 
-    structure
+    Ok(structure)
 }
 
 #[derive(Debug)]
-struct Cave {
+pub struct Cave {
     coords: [[bool; CAVE_COLS]; CAVE_ROWS]
 }
 
+// from the top down to the lowest and from the leftmost to the rightmost rock or sand
 impl Display for Cave {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        for row in self.coords {
-            let _ = writeln!(f, "{}", row.iter().map(|g| if *g { '#' } else { '.' }).collect::<String>());
-        }   
+        let bottom = self.coords.iter().rposition(|row| row.contains(&true)).unwrap_or(0);
+        let left = self.coords.iter().filter_map(|row| row.iter().position(|g| *g)).min().unwrap_or(START);
+        let right = self.coords.iter().filter_map(|row| row.iter().rposition(|g| *g)).max().unwrap_or(START);
+        for row in &self.coords[..=bottom] {
+            writeln!(f, "{}", row[left..=right].iter().map(|g| if *g { '#' } else { '.' }).collect::<String>())?;
+        }
         Ok(())
     }
 }
@@ -53,7 +69,7 @@ impl IndexMut<(usize, usize)> for Cave {
 }
 
 impl Cave {
-    fn new(structure: Vec<Vec<(usize, usize)>>) -> Self {
+    pub fn new(structure: &[Vec<(usize, usize)>]) -> Self {
         let mut coords = [[false; CAVE_COLS]; CAVE_ROWS];
         for line in structure {
             for ((left_x, left_y), (right_x, right_y)) in line.iter().zip(&line[1..]) {
@@ -142,10 +158,103 @@ impl Cave {
     }
 }
 
-pub fn get_solution_1() -> usize {
-    Cave::new(parse(INPUT)).simulate_p1() 
+// the cave of part 1 in the repl, a tick drops a unit of sand
+pub struct Sand {
+    cave: Cave,
+    drop_path: Vec<(usize, usize)>,
+    units: usize,
+    abyss: bool,
+}
+
+pub fn explore(input: &str) -> Result<Box<dyn Explore + '_>, ParseError> {
+    Ok(Box::new(Sand { cave: Cave::new(&parse(input)?), drop_path: vec![(START, 0)], units: 0, abyss: false }))
+}
+
+impl Explore for Sand {
+    fn step(&mut self, n: usize) -> Option<String> {
+        for _ in 0..n {
+            let Some(sand) = self.cave.drop_sand(&mut self.drop_path) else {
+                self.abyss = true;
+                break;
+            };
+            Cave::trim_path(&mut self.drop_path);
+            self.cave[sand] = true;
+            self.units += 1;
+        }
+        Some(match self.abyss {
+            true => format!("{} units of sand at rest, the next one falls into the abyss", self.units),
+            false => format!("{} units of sand at rest", self.units),
+        })
+    }
+
+    // the sand is drawn like the rocks
+    fn show(&self) -> String {
+        self.cave.to_string()
+    }
+
+    fn query(&mut self, query: &[&str]) -> Result<String, String> {
+        match query {
+            ["sand"] => Ok(self.units.to_string()),
+            _ => Err(format!("{} takes no arguments", query[0])),
+        }
+    }
+}
+
+// the sand of part 1 a unit at a time, in the rectangle around the rocks and the source of the sand
+pub fn record(input: &str, recorder: &mut Recorder) -> Result<(), ParseError> {
+    let structure = parse(input)?;
+    let rocks = Cave::new(&structure);
+    let mut cave = Cave::new(&structure);
+    let left = structure.iter().flatten().map(|(x, _)| *x).chain([START]).min().unwrap_or(START);
+    let right = structure.iter().flatten().map(|(x, _)| *x).chain([START]).max().unwrap_or(START);
+    let bottom = structure.iter().flatten().map(|(_, y)| *y).max().unwrap_or(0);
+    let frame = |cave: &Cave| {
+        let mut frame = Frame::new(right - left + 1, bottom + 1, '.');
+        for y in 0..=bottom {
+            for x in left..=right {
+                match (rocks[(x, y)], cave[(x, y)]) {
+                    (true, _) => frame.set(y, x - left, '#'),
+                    (false, true) => frame.set(y, x - left, 'o'),
+                    _ => (),
+                }
+            }
+        }
+        if !cave[(START, 0)] {
+            frame.set(0, START - left, '+');
+        }
+        frame
+    };
+
+    let mut drop_path = vec![(START, 0)];
+    recorder.tick(|| frame(&cave));
+    while let Some(sand) = cave.drop_sand(&mut drop_path) {
+        if recorder.is_full() {
+            return Ok(());
+        }
+        Cave::trim_path(&mut drop_path);
+        cave[sand] = true;
+        recorder.tick(|| frame(&cave));
+    }
+    recorder.last(|| frame(&cave));
+    Ok(())
 }
 
-pub fn get_solution_2() -> usize {
-    Cave::new(parse(INPUT)).simulate_p2(CAVE_ROWS - 1)
+pub struct Day14;
+
+impl Solution for Day14 {
+    type Parsed<'a> = Vec<Vec<(usize, usize)>>;
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, ParseError> {
+        parse(input)
+    }
+
+    fn part_1(structure: &Vec<Vec<(usize, usize)>>) -> Answer {
+        Cave::new(structure).simulate_p1().into()
+    }
+
+    fn part_2(structure: &Vec<Vec<(usize, usize)>>) -> Answer {
+        // the floor is two below the lowest rock
+        let bottom = structure.iter().flatten().map(|(_, y)| y + 2).max().unwrap_or(CAVE_ROWS - 1);
+        Cave::new(structure).simulate_p2(bottom).into()
+    }
 }
\ No newline at end of file
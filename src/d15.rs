@@ -1,29 +1,49 @@
-use std::{ops::Range, collections::{HashMap, HashSet}, sync::{Arc, mpsc}};
+use std::{ops::Range, collections::{HashMap, HashSet}, sync::atomic::{AtomicBool, Ordering}};
+
+use crate::error::{self, ParseError};
+use crate::solution::{Answer, Params, Solution};
 
-static INPUT: &str = include_str!("../data/d15.txt");
 static _TEST: &str = include_str!("../data/d15_test.txt");
 
-fn parse(input: &'static str) -> Vec<Sensor> {
-    let mut sensors = Vec::new();
+// the row part 1 counts and the largest coordinate part 2 searches. the puzzle asks about row 2000000 and
+// coordinates up to 4000000, the example about row 10 and coordinates up to 20, which its answers give
+// as the parameters row and area
+pub struct Scan {
+    sensors: Vec<Sensor>,
+    row: i64,
+    area: i64,
+}
 
-    for line in input.lines() {
-        let nums = line.split_whitespace()
-                       .filter(|part| part.contains('='))
-                       .map(|part| {
-                        let start = part.find('=').unwrap() + 1;
-                        let end = part.len() - if part.contains([',', ':']) { 1 } else { 0 };
-                        part[start..end].parse::<i64>().unwrap()
-                       })
-                       .collect::<Vec<i64>>();
+pub fn parse(input: &str, params: &Params) -> Result<Scan, ParseError> {
+    let mut sensors = Vec::new();
+    for line in error::lines(input) {
+        // Sensor at x=2, y=18: closest beacon is at x=-2, y=15
+        let (x, rest) = line.split_once(line.after("Sensor at x=")?, ", y=")?;
+        let (y, rest) = line.split_once(rest, ": closest beacon is at x=")?;
+        let (beacon_x, beacon_y) = line.split_once(rest, ", y=")?;
         
-        sensors.push(Sensor { pos: (nums[0], nums[1]), beacon: (nums[2], nums[3]) });
+        sensors.push(Sensor {
+            pos: (line.number(x)?, line.number(y)?),
+            beacon: (line.number(beacon_x)?, line.number(beacon_y)?),
+        });
+    }
+
+    if sensors.is_empty() {
+        return Err(ParseError::end_of_input(input, "a sensor"));
     }
+    Ok(Scan { sensors, row: param(params, "row", 2_000_000)?, area: param(params, "area", 4_000_000)? })
+}
 
-    sensors
+// the value of the real inputs if the parameter isn't given
+fn param(params: &Params, name: &str, real: i64) -> Result<i64, ParseError> {
+    match params.get(name) {
+        Some(value) => value.parse().map_err(|_| ParseError::param(name, "a number").found(value)),
+        None => Ok(real),
+    }
 }
 
-#[derive(Debug)]
-struct Sensor {
+#[derive(Debug, Clone)]
+pub struct Sensor {
     pos: (i64, i64), // (x, y)
     beacon: (i64, i64)
 }
@@ -51,7 +71,7 @@ impl Sensor {
     }
 
     fn add_to_map(beacon_range: Range<i64>, y: i64, pos_map: &mut HashMap<i64, Vec<Range<i64>>>) {
-        let entry = pos_map.entry(y).or_insert(Vec::new());
+        let entry = pos_map.entry(y).or_default();
         entry.push(beacon_range);
     }
 }
@@ -72,46 +92,76 @@ fn count_y(y: i64, sensors: &[Sensor], ranges: &[Range<i64>]) -> i64 {
     count - others.len() as i64
 }
 
-pub fn get_solution_1() -> i64 {
-    let sensors = parse(INPUT);
-    let row = 2_000_000;
-    let filtered = sensors.into_iter().filter(|s| ((s.pos.1 - s.beacon_dist())..(s.pos.1 + s.beacon_dist() + 1)).contains(&row)).collect::<Vec<Sensor>>();
+pub struct Day15;
+
+impl Solution for Day15 {
+    type Parsed<'a> = Scan;
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, ParseError> {
+        parse(input, &Params::new())
+    }
+
+    fn parse_with<'a>(input: &'a str, params: &Params) -> Result<Self::Parsed<'a>, ParseError> {
+        parse(input, params)
+    }
+
+    fn part_1(scan: &Scan) -> Answer {
+        count_no_beacon_row(&scan.sensors, scan.row).into()
+    }
+
+    fn part_2(scan: &Scan) -> Answer {
+        find_tuning_frequency(&scan.sensors, scan.area).into()
+    }
+}
+
+fn count_no_beacon_row(sensors: &[Sensor], row: i64) -> i64 {
+    let filtered = sensors.iter().filter(|s| ((s.pos.1 - s.beacon_dist())..(s.pos.1 + s.beacon_dist() + 1)).contains(&row)).cloned().collect::<Vec<Sensor>>();
     let mut pos_map = HashMap::new();
     for s in &filtered {
         s.insert_row(row, &mut pos_map);
     } 
-    let merged = merge_ranges(pos_map.get_mut(&row).unwrap());
+    // no sensor might reach the row
+    let Some(ranges) = pos_map.get_mut(&row) else { return 0 };
+    let merged = merge_ranges(ranges);
     count_y(row, &filtered, &merged)
 }
 
-pub fn get_solution_2() -> i64 {
-    let sensors = Arc::new(parse(INPUT));
+// None if there is no single position left for the beacon. the threads are scoped and stop as soon as
+// one of them found it, so none of them keeps running once the part is done
+fn find_tuning_frequency(sensors: &[Sensor], area: i64) -> Option<i64> {
     let n_threads = 6;
-    let chunk_size = 4_000_000 / n_threads;
-    let mut handles = Vec::new();
-    let (send, recv) = mpsc::channel();
-    for i in 0..n_threads {
-        let t_sensors = Arc::clone(&sensors);
-        let t_send = send.clone();
-
-        let handle = std::thread::spawn(move || {
-            let mut pos_map = HashMap::new();
-
-            for row in i * chunk_size..(i + 1) * chunk_size {
-                for s in &*t_sensors {
-                    s.insert_row(row, &mut pos_map)
+    let chunk_size = (area + n_threads) / n_threads;
+    let found = AtomicBool::new(false);
+
+    std::thread::scope(|scope| {
+        let mut handles = Vec::new();
+        for i in 0..n_threads {
+            let found = &found;
+            let handle = scope.spawn(move || {
+                let mut pos_map = HashMap::new();
+
+                for row in i * chunk_size..((i + 1) * chunk_size).min(area + 1) {
+                    if found.load(Ordering::Relaxed) {
+                        return None;
+                    }
+                    for s in sensors {
+                        s.insert_row(row, &mut pos_map)
+                    }
+                    let Some(ranges) = pos_map.get_mut(&row) else { continue };
+                    let ranges = merge_ranges(ranges);
+                    if let Some(col) = contains_hole(&ranges) {
+                        found.store(true, Ordering::Relaxed);
+                        return Some(col * 4000000 + row);
+                    }
                 }
-                let ranges = merge_ranges(pos_map.get_mut(&row).unwrap());
-                if let Some(col) = contains_hole(&ranges) {
-                    let _ = t_send.send(col * 4000000 + row);
-                }
-            }
-        });
+                None
+            });
 
-        handles.push(handle);
-    }
+            handles.push(handle);
+        }
 
-    recv.recv().unwrap()
+        handles.into_iter().find_map(|handle| handle.join().unwrap())
+    })
 }
 
 fn contains_hole(ranges: &[Range<i64>]) -> Option<i64> {
@@ -130,7 +180,7 @@ fn contains_hole(ranges: &[Range<i64>]) -> Option<i64> {
 
 fn merge_ranges(ranges: &mut [Range<i64>]) -> Vec<Range<i64>> {
     let mut merged = Vec::new();
-    ranges.sort_by(|a, b| a.start.cmp(&b.start));
+    ranges.sort_by_key(|r| r.start);
 
     let mut prev = ranges[0].clone();
     for cur in &ranges[1..] {
@@ -150,5 +200,27 @@ fn merge_ranges(ranges: &mut [Range<i64>]) -> Vec<Range<i64>> {
 
 #[test]
 fn test_get_1() {
-    get_solution_1();
-}
\ No newline at end of file
+    let scan = parse(include_str!("../data/d15.txt"), &Params::new()).unwrap();
+    count_no_beacon_row(&scan.sensors, scan.row);
+}
+
+#[test]
+fn test_no_hole() {
+    let params = Params::from([("row".to_string(), "1".to_string()), ("area".to_string(), "2".to_string())]);
+    let scan = parse("Sensor at x=1, y=1: closest beacon is at x=1, y=4\n", &params).unwrap();
+    assert_eq!((scan.row, scan.area), (1, 2));
+    assert_eq!(count_no_beacon_row(&scan.sensors, scan.row), 6);
+    assert_eq!(find_tuning_frequency(&scan.sensors, scan.area), None);
+}
+
+#[test]
+fn test_params() {
+    let input = "Sensor at x=1, y=1: closest beacon is at x=1, y=4\n";
+    let scan = parse(input, &Params::new()).unwrap();
+    assert_eq!((scan.row, scan.area), (2_000_000, 4_000_000));
+
+    let params = Params::from([("row".to_string(), "ten".to_string())]);
+    let error = parse(input, &params).err().unwrap();
+    assert_eq!(error.to_string(), "expected a number for the parameter row, found 'ten'");
+    assert_eq!(error.diagnostic(input), error.to_string());
+}
@@ -1,55 +1,74 @@
-use std::collections::{HashMap, BinaryHeap, HashSet};
+use std::collections::HashMap;
 
-type Valves = HashMap<&'static str, (u32, Vec<&'static str>)>;
-type ValveDistances =  HashMap<(&'static str, &'static str), u32>;
+use crate::error::{self, ParseError};
+use crate::helpers;
+use crate::solution::{Answer, Solution};
+use crate::trace;
+
+type Valves<'a> = HashMap<&'a str, (u32, Vec<&'a str>)>;
+type ValveDistances<'a> =  HashMap<(&'a str, &'a str), u32>;
 
-static INPUT: &str = include_str!("../data/d16.txt");
 static _TEST: &str = include_str!("../data/d16_test.txt"); 
 
-fn parse(input: &'static str) -> (Valves, u64, ValveTable) {
+pub fn parse(input: &str) -> Result<(Valves<'_>, u64, ValveTable<'_>), ParseError> {
     let mut valves = HashMap::new();
     let mut valve_table = Vec::new();
     let opened = 0;
     let mut mask = 1_u64; 
-    input.lines().for_each(|l| {
-        match &l.split_whitespace().collect::<Vec<&str>>()[..] {
-            [_, name, _, _, flow_rate, _, _, _, _, rest @..] => {
-                let flow_rate = flow_rate.trim_matches(|c: char| !c.is_numeric()).parse::<u32>().unwrap();
-                // valve is already open
-                if flow_rate != 0 {
-                    valve_table.push((*name, mask));
-                    // opened |= mask;
-                    mask <<= 1;
-                }
-                valves.insert(*name, (flow_rate, rest.iter().map(|v| v.trim_matches(',')).collect()));
-            },
-            _ => unreachable!(),
+    let lines = error::lines(input).collect::<Vec<_>>();
+    for line in &lines {
+        // Valve AA has flow rate=0; tunnels lead to valves DD, II, BB
+        let (name, rest) = line.split_once(line.after("Valve ")?, " has flow rate=")?;
+        let (flow_rate, rest) = line.split_once(rest, "; ")?;
+        let flow_rate = line.number::<u32>(flow_rate)?;
+        let rest = ["tunnels lead to valves ", "tunnel leads to valve "].iter()
+            .find_map(|prefix| rest.strip_prefix(prefix))
+            .ok_or_else(|| line.error(rest, "'tunnels lead to valves'"))?;
+        // valve is already open
+        if flow_rate != 0 {
+            if mask == 0 {
+                return Err(line.error(line.text, "at most 64 valves with a flow rate"));
+            }
+            valve_table.push((name, mask));
+            // opened |= mask;
+            mask <<= 1;
         }
-    });
+        valves.insert(name, (flow_rate, rest.split(", ").collect::<Vec<_>>()));
+    }
 
-    (valves, opened, ValveTable::new(valve_table))
+    for line in &lines {
+        let (name, _) = line.split_once(&line.text[6..], " ")?;
+        if let Some(unknown) = valves[name].1.iter().find(|v| !valves.contains_key(*v)) {
+            return Err(line.error(unknown, "a valve listed in the input"));
+        }
+    }
+    if !valves.contains_key("AA") {
+        return Err(ParseError::end_of_input(input, "the valve AA"));
+    }
+
+    Ok((valves, opened, ValveTable::new(valve_table)))
 }
 
-struct ValveTable {
-    _inner: Vec<(&'static str, u64)>,
+pub struct ValveTable<'a> {
+    _inner: Vec<(&'a str, u64)>,
     len: usize,
 }
 
-impl ValveTable {
-    fn new(entries: Vec<(&'static str, u64)>) -> Self {
+impl<'a> ValveTable<'a> {
+    fn new(entries: Vec<(&'a str, u64)>) -> Self {
         let len = entries.len();
         Self { _inner: entries, len }
     }
 
-    fn get_mask(&self, valve: &'static str) -> Option<u64> {
+    fn get_mask(&self, valve: &'a str) -> Option<u64> {
         self._inner.iter().find(|(valve_entry, _)| *valve_entry == valve).map(|(_, mask)| *mask)
     }
 
-    fn get_valve(&self, mask: u64) -> Option<&'static str> {
+    fn get_valve(&self, mask: u64) -> Option<&'a str> {
         self._inner.iter().find(|(_, mask_entry)| *mask_entry == mask).map(|(valve, _)| *valve)
     }
 
-    fn get_closed(&self, valves: u64) -> Vec<&'static str> {
+    fn get_closed(&self, valves: u64) -> Vec<&'a str> {
         let mut closed = Vec::new();
         let mut mask = 1;
         while mask < 2_u64.pow(self.len as u32) {
@@ -79,77 +98,48 @@ impl ValveTable {
 }
 
 #[derive(Clone, Copy, PartialEq, Eq, Hash)]
-struct State {
-    valve: &'static str,
+struct State<'a> {
+    valve: &'a str,
     opened: u64,
     time: u32,
     pressure: u32,
 }
 
-impl State {
-    fn new(valve: &'static str, time: u32, pressure: u32, opened: u64) -> Self {
+impl<'a> State<'a> {
+    fn new(valve: &'a str, time: u32, pressure: u32, opened: u64) -> Self {
         Self { valve, time, pressure, opened }
     }
 
-    fn open_valve(&mut self, valve: &'static str, valve_table: &ValveTable) {
+    fn open_valve(&mut self, valve: &'a str, valve_table: &ValveTable<'a>) {
         let mask = valve_table.get_mask(valve).unwrap();
         self.opened |= mask;
     }
 }
 
-pub fn get_solution_1() -> u32 {
-    let (valves, opened, valve_table) = parse(INPUT);
-    let state = State::new("AA", 30, 0, opened);
-    let min_distances = minimum_distance_valves(&valves);
-    find_max(state, min_distances, &valves, &valve_table)
-}
+pub struct Day16;
 
-pub fn get_solution_2() -> u32 {
-    let (valves, opened, valve_table) = parse(INPUT);
-    let min_distances = minimum_distance_valves(&valves);
-    let all_states = record_all_states(State::new("AA", 26, 0, opened), min_distances, &valves, &valve_table);
-    get_best_combination(all_states, &valve_table)
-}
-
-#[derive(Eq, PartialEq, Hash)]
-struct Node {
-    cost: u32,
-    name: &'static str,
-}
+impl Solution for Day16 {
+    type Parsed<'a> = (Valves<'a>, u64, ValveTable<'a>);
 
-impl Ord for Node {
-    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-        self.cost.cmp(&other.cost).reverse()
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, ParseError> {
+        parse(input)
     }
-}
 
-impl PartialOrd for Node {
-    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        self.cost.partial_cmp(&other.cost).map(|ordering| ordering.reverse())
+    fn part_1((valves, opened, valve_table): &Self::Parsed<'_>) -> Answer {
+        let state = State::new("AA", 30, 0, *opened);
+        let min_distances = minimum_distance_valves(valves);
+        find_max(state, min_distances, valves, valve_table).into()
     }
-}
 
-fn shortest_path(from: &'static str, to: &'static str, valves: &Valves) -> u32 {
-    let mut queue = BinaryHeap::new();
-    let mut visited = HashSet::new();
-    let node = Node { cost: 0, name: from };
-    queue.push(node);
-    while let Some(Node { cost, name }) = queue.pop() {
-        if name == to {
-            return cost;
-        }
-        if !visited.insert(name) {
-            continue;
-        }
-        for neighbour in &valves.get(name).unwrap().1 {
-            let neighbour_node = Node { cost: cost + 1, name: neighbour };
-            queue.push(neighbour_node);
-        }
+    fn part_2((valves, opened, valve_table): &Self::Parsed<'_>) -> Answer {
+        let min_distances = minimum_distance_valves(valves);
+        let all_states = record_all_states(State::new("AA", 26, 0, *opened), min_distances, valves, valve_table);
+        get_best_combination(all_states, valve_table).into()
     }
-    u32::MAX
 }
 
-fn minimum_distance_valves(valves: &Valves) -> ValveDistances {
+fn minimum_distance_valves<'a>(valves: &Valves<'a>) -> ValveDistances<'a> {
+    let _span = trace::span("minimum_distance_valves");
     let mut valve_distances = HashMap::new();
     let pressure_valves = valves.iter().filter_map(|(valve, (pressure, _))| 
         if pressure >= &0 { 
@@ -158,18 +148,21 @@ fn minimum_distance_valves(valves: &Valves) -> ValveDistances {
             None }
         ).collect::<Vec<&str>>();
     
+    // a search from every valve reaches all the others
     for from in &pressure_valves {
+        let search = helpers::bfs([*from], |valve| valves[valve].1.iter().copied(), |_| false);
         for to in &pressure_valves {
             if from == to {
                 continue;
             }
-            valve_distances.insert((*from, *to), shortest_path(from, to, valves));
+            valve_distances.insert((*from, *to), search.dist.get(to).map_or(u32::MAX, |d| *d as u32));
         }
     }
     valve_distances
 }
 
-fn find_max(state: State, min_distances: ValveDistances, valves: &Valves, valve_table: &ValveTable) -> u32 {
+fn find_max<'a>(state: State<'a>, min_distances: ValveDistances<'a>, valves: &Valves<'a>, valve_table: &ValveTable<'a>) -> u32 {
+    let _span = trace::span("find_max");
     let mut queue = vec![state];
     let mut max = 0;
     while let Some(State { valve, opened, time, pressure }) = queue.pop() {
@@ -196,7 +189,8 @@ fn find_max(state: State, min_distances: ValveDistances, valves: &Valves, valve_
     max 
 }
 
-fn record_all_states(state: State, min_distances: ValveDistances, valves: &Valves, valve_table: &ValveTable) -> HashMap<u64, u32> { // (valves, pressure)
+fn record_all_states<'a>(state: State<'a>, min_distances: ValveDistances<'a>, valves: &Valves<'a>, valve_table: &ValveTable<'a>) -> HashMap<u64, u32> { // (valves, pressure)
+    let _span = trace::span("record_all_states");
     let mut all_states = HashMap::new();
     let mut queue = vec![state];
 
@@ -223,6 +217,7 @@ fn record_all_states(state: State, min_distances: ValveDistances, valves: &Valve
 }
 
 fn get_best_combination(all_states: HashMap<u64, u32>, valve_table: &ValveTable) -> u32 {
+    let _span = trace::span("get_best_combination");
     let mut max = 0;
     for (opened_human, pressure_human) in all_states.iter() {
         for (opened_elephant, pressure_elephant) in all_states.iter() {
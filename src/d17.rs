@@ -1,10 +1,16 @@
-use std::collections::{HashMap, HashSet};
-use std::fmt::Write;
+use std::collections::HashSet;
+use std::fmt::Display;
+
+use crate::error::{self, ParseError};
+use crate::frames::{Frame, Recorder};
+use crate::helpers;
+use crate::repl::Explore;
+use crate::solution::{Answer, Solution};
+use crate::trace;
 
 type Pos = (isize, isize); // (row, col)
 
 static _TEST: &str = include_str!("../data/d17_test.txt");
-static INPUT: &str = include_str!("../data/d17.txt");
 
 static MINUS: [Pos; 4] = [(0, 2), (0, 3), (0, 4), (0, 5)];
 static PLUS: [Pos; 4] = [(2,  3), (1, 2), (1, 4), (0, 3)];
@@ -17,15 +23,47 @@ static MIN_DIST: isize = 3;
 struct Chamber {
     rocks: HashSet<Pos>,
     width: isize,
+    // the height of every column
+    tops: Vec<isize>,
 }
 
 impl Chamber {
     fn new() -> Self {
-        Chamber{ rocks: HashSet::new(), width: 7 }
+        Chamber{ rocks: HashSet::new(), width: 7, tops: vec![0; 7] }
     }
 
     fn height(&self) -> isize {
-        self.rocks.iter().map(|(row, _)| row + 1).max().unwrap_or(0)
+        self.tops.iter().copied().max().unwrap_or(0)
+    }
+
+    fn settle(&mut self, rock: Rock) {
+        for (row, col) in rock.pos {
+            self.tops[col as usize] = self.tops[col as usize].max(row + 1);
+            self.rocks.insert((row, col));
+        }
+    }
+
+    // the free tiles a falling rock could still get to, found by going down, left and right from above
+    // the tower. a row per tile below the top with a bit per column, down to the deepest one that can be
+    // reached. every tile of a falling rock is one of them, so towers with the same surface go on the
+    // same way. unlike the heights of the columns it sees overhangs
+    fn surface(&self) -> Vec<u8> {
+        let height = self.height();
+        let mut surface = Vec::new();
+        let mut todo = (0..self.width).map(|col| (height, col)).collect::<Vec<Pos>>();
+        while let Some((row, col)) = todo.pop() {
+            let depth = (height - row) as usize;
+            let free = col >= 0 && col < self.width && row >= 0 && !self.rocks.contains(&(row, col));
+            if !free || surface.get(depth).is_some_and(|tiles| tiles & (1 << col) != 0) {
+                continue;
+            }
+            if depth == surface.len() {
+                surface.push(0);
+            }
+            surface[depth] |= 1 << col;
+            todo.extend([(row - 1, col), (row, col - 1), (row, col + 1)]);
+        }
+        surface
     }
 
     fn determine_rock_pos(&self, rock: &Rock, dir: Dir) -> Option<Vec<Pos>> {
@@ -40,6 +78,32 @@ impl Chamber {
         Some(shape)
     }
 
+    // the chamber from the top of the falling rock down with its walls, and its floor once it's in sight
+    fn frame(&self, rock: &Rock, rows: usize) -> Frame {
+        let width = self.width as usize;
+        let top = rock.pos.iter().map(|(row, _)| *row).max().unwrap_or(0).max(rows as isize - 2);
+        let mut frame = Frame::new(width + 2, rows, '.');
+        for i in 0..rows {
+            let row = top - i as isize;
+            if row < 0 {
+                frame.set(i, 0, '+');
+                (1..=width).for_each(|col| frame.set(i, col, '-'));
+                frame.set(i, width + 1, '+');
+                break;
+            }
+            frame.set(i, 0, '|');
+            frame.set(i, width + 1, '|');
+            for col in 0..self.width {
+                if self.rocks.contains(&(row, col)) {
+                    frame.set(i, col as usize + 1, '#');
+                } else if rock.pos.contains(&(row, col)) {
+                    frame.set(i, col as usize + 1, '@');
+                }
+            }
+        }
+        frame
+    }
+
     fn step(&self, rock: &mut Rock, dir: Dir) -> bool {
         if let Some(positions) = self.determine_rock_pos(rock, dir) {
             rock.pos = positions;
@@ -54,17 +118,16 @@ impl Chamber {
 }
 }
 
-impl ToString for Chamber {
-    fn to_string(&self) -> String {
-        let mut s = String::new();
+impl Display for Chamber {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         for row in (0..self.height() + 1).rev() {
             let mut row_repr = Vec::new();
             for col in 0..self.width {
                 row_repr.push(if self.rocks.contains(&(row, col)) { '#' } else { '.' });
             }
-            let _ = writeln!(s, "{}", row_repr.iter().collect::<String>());
+            writeln!(f, "{}", row_repr.iter().collect::<String>())?;
         }
-        s
+        Ok(())
     }
 }
 
@@ -98,7 +161,7 @@ impl Shape {
 }
 
 #[derive(Clone, Copy)]
-enum Dir {
+pub enum Dir {
     Down,
     Left,
     Right,
@@ -117,81 +180,144 @@ impl std::ops::Add<Pos> for Dir {
     }
 }
 
-fn parse(input: &str) -> Vec<Dir> {
-    input.chars().map(|c| if c == '<' { Dir::Left } else { Dir::Right }).collect()
+// the jet pattern is the first line of the input
+pub fn parse(input: &str) -> Result<Vec<Dir>, ParseError> {
+    let line = error::lines(input).next().ok_or(ParseError::end_of_input(input, "a jet pattern"))?;
+    if line.text.is_empty() {
+        return Err(line.end_error("a jet pattern"));
+    }
+    line.text.chars()
+        .enumerate()
+        .map(|(i, c)| match c {
+            '<' => Ok(Dir::Left),
+            '>' => Ok(Dir::Right),
+            _ => Err(line.error_at(i + 1, "'<' or '>'")),
+        })
+        .collect()
 }
 
-fn shapes() -> Vec<Shape> {
-    vec![Shape::Minus, Shape::Plus, Shape::L, Shape::I, Shape::Quadrat]
-} 
+static SHAPES: [Shape; 5] = [Shape::Minus, Shape::Plus, Shape::L, Shape::I, Shape::Quadrat];
 
-fn determine_repeat_interval(dirs: &[Dir]) -> ((isize, isize), (isize, isize)) {
-    let mut chamber = Chamber::new();
-    let mut shapes = shapes().into_iter().cycle();
-    let mut rocks = HashMap::new();
-    let mut rock = shapes.next().unwrap().spawn_rock(chamber.height());
-    let mut n_blocks = 0;
-
-    for (i, dir) in dirs.iter().enumerate().cycle() {
-        if chamber.step(&mut rock, *dir) {
-            continue; 
+// the tower grows the same way again once a rock of the same shape falls at the same point of the
+// jet pattern onto the same surface
+fn height_after_n_blocks(n: u64, dirs: &[Dir]) -> isize {
+    let tower = Tower { chamber: Chamber::new(), dirs: dirs.to_vec(), jet: 0, rocks: 0 };
+    let cycle = {
+        let _span = trace::span("simulate until repeat");
+        helpers::find_cycle(
+            tower,
+            Tower::drop_rock,
+            |tower| (tower.rocks % SHAPES.len(), tower.jet, tower.chamber.surface()),
+            |tower| tower.chamber.height() as i64,
+        )
+    };
+    let _span = trace::span("extrapolate");
+    cycle.extrapolate(n) as isize
+}
+
+// the chamber in the repl, a tick drops a rock until it comes to rest
+pub struct Tower {
+    chamber: Chamber,
+    dirs: Vec<Dir>,
+    // the next jet of gas
+    jet: usize,
+    rocks: usize,
+}
+
+impl Tower {
+    // until it comes to rest
+    fn drop_rock(&mut self) {
+        let mut rock = SHAPES[self.rocks % SHAPES.len()].spawn_rock(self.chamber.height());
+        loop {
+            let dir = self.dirs[self.jet];
+            self.jet = (self.jet + 1) % self.dirs.len();
+            if !self.chamber.step(&mut rock, dir) {
+                break;
+            }
         }
+        self.chamber.settle(rock);
+        self.rocks += 1;
+    }
+}
+
+pub fn explore(input: &str) -> Result<Box<dyn Explore + '_>, ParseError> {
+    Ok(Box::new(Tower { chamber: Chamber::new(), dirs: parse(input)?, jet: 0, rocks: 0 }))
+}
 
-        if let Some((blocks_init, height_init)) = rocks.insert((rock.shape, rock.pos[0].1, i), (n_blocks, chamber.height())) {
-            return ((blocks_init, height_init), (n_blocks - blocks_init, chamber.height() - height_init));
+impl Explore for Tower {
+    fn step(&mut self, n: usize) -> Option<String> {
+        for _ in 0..n {
+            self.drop_rock();
         }
+        Some(format!("{} rocks at rest, the tower is {} units tall", self.rocks, self.chamber.height()))
+    }
 
-        rock.pos.iter().for_each(|pos| assert!(chamber.rocks.insert(*pos)));
-        n_blocks += 1;
-        rock = shapes.next().unwrap().spawn_rock(chamber.height()); 
+    fn show(&self) -> String {
+        self.chamber.to_string()
     }
-    
-    unreachable!();
-}
 
-fn build_n_blocks(n: isize, dirs: &[Dir]) -> isize {
-    if n == 0 {
-        return 0;
+    fn query(&mut self, query: &[&str]) -> Result<String, String> {
+        match query {
+            ["height"] => Ok(self.chamber.height().to_string()),
+            ["rocks"] => Ok(self.rocks.to_string()),
+            _ => Err(format!("{} takes no arguments", query[0])),
+        }
     }
+}
+
+// the rocks falling, a frame per push of the jet and fall that follows it
+pub fn record(input: &str, recorder: &mut Recorder) -> Result<(), ParseError> {
+    let dirs = parse(input)?;
     let mut chamber = Chamber::new();
-    let mut n_blocks = 0;
-    let mut shapes = shapes().into_iter().cycle();
-    let mut rock = shapes.next().unwrap().spawn_rock(chamber.height());
-
-    for dir in dirs.iter().cycle() {
-        if chamber.step(&mut rock, *dir) { 
-            continue; 
-        } 
-        
-        rock.pos.iter().for_each(|pos| assert!(chamber.rocks.insert(*pos)));
-        n_blocks += 1;
-        if n_blocks == n {
-            return chamber.height();
+    let mut jets = dirs.iter().cycle();
+
+    for shape in SHAPES.iter().cycle() {
+        let mut rock = shape.spawn_rock(chamber.height());
+        loop {
+            recorder.tick(|| chamber.frame(&rock, 30));
+            if recorder.is_full() {
+                return Ok(());
+            }
+            if !chamber.step(&mut rock, *jets.next().unwrap()) {
+                break;
+            }
         }
-        rock = shapes.next().unwrap().spawn_rock(chamber.height()); 
+        chamber.settle(rock);
     }
-
-    unreachable!();
+    Ok(())
 }
 
-fn height_after_n_blocks(n: isize, dirs: Vec<Dir>) -> isize {
-    let ((blocks_init, height_init), (blocks_repeat, height_repeat)) = determine_repeat_interval(&dirs);
-    let (blocks_rem, cur_height) = match n.cmp(&(blocks_init)) {
-        std::cmp::Ordering::Less => (n, 0),
-        std::cmp::Ordering::Equal => return height_init,
-        std::cmp::Ordering::Greater => {
-            let cur_height = (n - blocks_init) / blocks_repeat * height_repeat + height_init;
-            let blocks_rem = (n - blocks_init) % blocks_repeat;
-            (blocks_rem, cur_height)
-        },
-    };
-    build_n_blocks(blocks_rem + blocks_init, &dirs) - height_init + cur_height
-}
+pub struct Day17;
+
+impl Solution for Day17 {
+    type Parsed<'a> = Vec<Dir>;
 
-pub fn get_solution_1() -> isize {
-    height_after_n_blocks(2022, parse(INPUT))
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, ParseError> {
+        parse(input)
+    }
+
+    fn part_1(dirs: &Vec<Dir>) -> Answer {
+        height_after_n_blocks(2022, dirs).into()
+    }
+
+    fn part_2(dirs: &Vec<Dir>) -> Answer {
+        height_after_n_blocks(1_000_000_000_000, dirs).into()
+    }
 }
 
-pub fn get_solution_2() -> isize {
-    height_after_n_blocks(1_000_000_000_000, parse(INPUT))
-}
\ No newline at end of file
+#[test]
+fn test_surface() {
+    // a ledge over all columns but the last, the tiles below it are still free to fall into
+    let ledge = || Rock { shape: Shape::Minus, pos: (0..6).map(|col| (2, col)).collect() };
+    let mut chamber = Chamber::new();
+    chamber.settle(ledge());
+    assert_eq!(chamber.surface(), [0b1111111, 0b1000000, 0b1111111, 0b1111111]);
+
+    // a rock under the ledge changes the surface but none of the heights of the columns, rocks can't
+    // get past it to the left as they never move up
+    let mut covered = Chamber::new();
+    covered.settle(ledge());
+    covered.settle(Rock { shape: Shape::Minus, pos: vec![(1, 3)] });
+    assert_eq!(chamber.tops, covered.tops);
+    assert_eq!(covered.surface()[2], 0b1110000);
+}
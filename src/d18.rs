@@ -1,24 +1,34 @@
 use std::{collections::HashSet, ops::Range};
 
+use crate::error::{self, ParseError};
+use crate::solution::{Answer, Solution};
+
 static N_SIDES: u64 = 6;
-static INPUT: &str = include_str!("../data/d18.txt"); 
 static _TEST: &str = include_str!("../data/d18_test.txt"); 
 type Cube = (i64, i64, i64);
 
-fn parse(input: &str) -> HashSet<Cube> {
-    input.lines().map(|l| match &l.split(',').collect::<Vec<&str>>()[..] {
-        [x, y, z] => (x.parse().unwrap(), y.parse().unwrap(), z.parse().unwrap()),
-        _ => unreachable!(),
-    }).collect()
+pub fn parse(input: &str) -> Result<HashSet<Cube>, ParseError> {
+    let cubes = error::lines(input).map(|line| {
+        let (x, rest) = line.split_once(line.text, ",")?;
+        let (y, z) = line.split_once(rest, ",")?;
+        Ok((line.number(x)?, line.number(y)?, line.number(z)?))
+    }).collect::<Result<HashSet<Cube>, ParseError>>()?;
+
+    if cubes.is_empty() {
+        return Err(ParseError::end_of_input(input, "a cube"));
+    }
+    Ok(cubes)
 }
 
-fn count_overlapping(cubes: HashSet<Cube>) -> u64 {
+fn count_overlapping(cubes: &HashSet<Cube>) -> u64 {
     let mut n_overlapping = 0;
     let mut overlapping = Vec::new();
 
     for cube in cubes {
-        n_overlapping += N_SIDES - 2 * overlapping.iter().filter(|lhs| does_overlap(lhs, &cube)).count() as u64;
-        overlapping.push(cube);
+        // adding first keeps the sum from going below zero, a cube can touch more than three earlier ones
+        n_overlapping += N_SIDES;
+        n_overlapping -= 2 * overlapping.iter().filter(|lhs| does_overlap(lhs, cube)).count() as u64;
+        overlapping.push(*cube);
     }
 
     n_overlapping
@@ -41,8 +51,7 @@ fn flow(cubes: &HashSet<Cube>) -> u64 {
     let mut frontier = vec![(ranges.0.start, ranges.1.start, ranges.2.start)];
     let mut surface = 0;
 
-    while !frontier.is_empty() {
-        let next = frontier.pop().unwrap();
+    while let Some(next) = frontier.pop() {
         if visited.contains(&next) {
             continue;
         }
@@ -67,16 +76,25 @@ fn neighbours(cube: &Cube) -> Vec<Cube> {
         (cube.0, cube.1, cube.2 - 1), (cube.0, cube.1, cube.2 + 1)] 
 }
 
-pub fn get_solution_1() -> u64 {
-    count_overlapping(parse(INPUT))
-}   
+pub struct Day18;
 
-pub fn get_solution_2() -> u64 {
-    let cubes = parse(INPUT);
-    flow(&cubes)
+impl Solution for Day18 {
+    type Parsed<'a> = HashSet<Cube>;
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, ParseError> {
+        parse(input)
+    }
+
+    fn part_1(cubes: &HashSet<Cube>) -> Answer {
+        count_overlapping(cubes).into()
+    }
+
+    fn part_2(cubes: &HashSet<Cube>) -> Answer {
+        flow(cubes).into()
+    }
 }
 
 #[test]
 fn test() {
-    get_solution_2();
+    flow(&parse(include_str!("../data/d18.txt")).unwrap());
 }
\ No newline at end of file
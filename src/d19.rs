@@ -1,9 +1,11 @@
-use std::{collections::HashSet, sync::Arc};
+use std::collections::HashSet;
+
+use crate::error::{self, ParseError};
+use crate::solution::{Answer, Solution};
 
 // initial algorithm:
 // for each step, build the best bot that can be build right now, or wait.
 // then recurse
-static INPUT: &str = include_str!("../data/d19.txt");
 static _TEST: &str = include_str!("../data/d19_test.txt");
 
 // (ore, clay, obsidian)
@@ -60,19 +62,24 @@ impl State {
     }
 
     fn optimize_resources(&mut self, bp: &BluePrint) {
-        let max_ore = bp.iter().map(|(_, (ore, _, _))| ore).max().unwrap();
-        // only store enough resources, so that a robot could be built in every step
-        if self.ore >= self.rem_steps * max_ore - self.r_ore * (self.rem_steps - 1) {
-            self.ore = self.rem_steps * max_ore - self.r_ore * (self.rem_steps - 1); 
+        let max_ore = *bp.iter().map(|(_, (ore, _, _))| ore).max().unwrap();
+        // only store enough resources, so that a robot could be built in every step,
+        // clay is only spent on obsidian robots and obsidian on geode robots
+        if let Some(max) = self.spendable(max_ore, self.r_ore) {
+            self.ore = self.ore.min(max);
         }
-    
-        if self.clay >= self.rem_steps * bp[1].1.1 - self.r_clay * (self.rem_steps - 1) {
-            self.clay = self.rem_steps * bp[1].1.1 - self.r_clay * (self.rem_steps - 1); 
-        } 
-        
-        if self.obsidian >= self.rem_steps * bp[2].1.2 - self.r_obsidian * (self.rem_steps - 1) {    
-            self.obsidian = self.rem_steps * bp[2].1.2 - self.r_obsidian * (self.rem_steps - 1);
-        }  
+        if let Some(max) = self.spendable(bp[2].1.1, self.r_clay) {
+            self.clay = self.clay.min(max);
+        }
+        if let Some(max) = self.spendable(bp[3].1.2, self.r_obsidian) {
+            self.obsidian = self.obsidian.min(max);
+        }
+    }
+
+    // how much of a resource could still be spent on top of what the robots collect,
+    // None if they already collect more than that
+    fn spendable(&self, cost: u32, robots: u32) -> Option<u32> {
+        (self.rem_steps * cost).checked_sub(robots * self.rem_steps.saturating_sub(1))
     }
 
     fn build_robot(&mut self, (robot, (ore, clay, obsidian)): Entry) {
@@ -93,27 +100,43 @@ impl State {
 }
 
 #[derive(Debug, Clone, Copy, PartialEq)]
-enum Robot {
+pub enum Robot {
     Ore,
     Clay,
     Obsidian,
     Geode,
 }
 
-fn parse(input: &str) -> Vec<BluePrint> {
+pub fn parse(input: &str) -> Result<Vec<BluePrint>, ParseError> {
     let mut bps = Vec::new();
-    for parts in input.lines()
-                        .map(|line| line.split("Each").map(|robot| robot.split_whitespace().collect::<Vec<&str>>())
-                        .collect::<Vec<Vec<&str>>>()) {
+    for line in error::lines(input) {
+        // the costs are the numbers in between these parts of the line
+        let mut rest = line.after("Blueprint ")?;
+        let mut costs = [0; 7];
+        for (cost, sep) in costs.iter_mut().zip([
+            ": Each ore robot costs ",
+            " ore. Each clay robot costs ",
+            " ore. Each obsidian robot costs ",
+            " ore and ",
+            " clay. Each geode robot costs ",
+            " ore and ",
+            " obsidian.",
+        ]) {
+            let (n, next) = line.split_once(rest, sep)?;
+            *cost = line.number(n)?;
+            rest = next;
+        }
+
+        let [_, ore, clay, obsidian_ore, obsidian_clay, geode_ore, geode_obsidian] = costs;
         let bp = [
-            (Robot::Ore, (parts[1][3].parse().unwrap(), 0, 0)),
-            (Robot::Clay, (parts[2][3].parse().unwrap(), 0, 0)),
-            (Robot::Obsidian, (parts[3][3].parse().unwrap(), parts[3][6].parse().unwrap(), 0)),
-            (Robot::Geode, (parts[4][3].parse().unwrap(), 0, parts[4][6].parse().unwrap())),
+            (Robot::Ore, (ore, 0, 0)),
+            (Robot::Clay, (clay, 0, 0)),
+            (Robot::Obsidian, (obsidian_ore, obsidian_clay, 0)),
+            (Robot::Geode, (geode_ore, 0, geode_obsidian)),
         ];
         bps.push(bp);      
     }
-    bps
+    Ok(bps)
 }
 
 fn run(mut state: State, bp: BluePrint, max: &mut u32, visited: &mut HashSet<State>) {
@@ -146,54 +169,65 @@ fn run(mut state: State, bp: BluePrint, max: &mut u32, visited: &mut HashSet<Sta
     run(state, bp, max, visited);
 }
 
-pub fn get_solution_1() -> usize {
-    let bps = Arc::new(parse(INPUT));
-    let mut quality_levels = Vec::new();
-    let n_threads = 4;
-    let chunk_size = bps.len() / n_threads;
-    let mut handles = Vec::new();
+pub struct Day19;
 
-    for i in 0..n_threads {
-        let t_bps = Arc::clone(&bps);
-        let start = i * chunk_size;
-        let end = if i == n_threads - 1 { bps.len() } else { i * chunk_size + chunk_size };
+impl Solution for Day19 {
+    type Parsed<'a> = Vec<BluePrint>;
 
-        let handle = std::thread::spawn(move || {
-            let mut t_quality_levels = Vec::new();
-            for (id, bp) in t_bps[start..end].iter().enumerate() {
-                let mut max = 0;
-                let state = State::new(24);
-                run(state, *bp, &mut max, &mut HashSet::new());
-                t_quality_levels.push((id + i * chunk_size + 1) * max as usize);
-            }
-            t_quality_levels
-        });
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, ParseError> {
+        parse(input)
+    }
 
-        handles.push(handle);
+    fn part_1(bps: &Vec<BluePrint>) -> Answer {
+        sum_quality_levels(bps).into()
     }
 
-    for handle in handles {
-        quality_levels.append(&mut handle.join().unwrap());
+    fn part_2(bps: &Vec<BluePrint>) -> Answer {
+        multiply_max_geodes(bps).into()
     }
+}
+
+// the threads are scoped, so none of them keeps running once the part is done
+fn sum_quality_levels(bps: &[BluePrint]) -> usize {
+    let n_threads = 4;
+    let chunk_size = bps.len() / n_threads;
 
-    quality_levels.iter().sum::<usize>()
+    std::thread::scope(|scope| {
+        let mut handles = Vec::new();
+        for i in 0..n_threads {
+            let start = i * chunk_size;
+            let end = if i == n_threads - 1 { bps.len() } else { i * chunk_size + chunk_size };
+
+            let handle = scope.spawn(move || {
+                let mut t_quality_levels = Vec::new();
+                for (id, bp) in bps[start..end].iter().enumerate() {
+                    let mut max = 0;
+                    let state = State::new(24);
+                    run(state, *bp, &mut max, &mut HashSet::new());
+                    t_quality_levels.push((id + i * chunk_size + 1) * max as usize);
+                }
+                t_quality_levels
+            });
+
+            handles.push(handle);
+        }
+
+        handles.into_iter().flat_map(|handle| handle.join().unwrap()).sum()
+    })
 }
 
-pub fn get_solution_2() -> u32 {
-    let bps = parse(INPUT);
-    let mut handles = Vec::new();
-    for bp in bps.into_iter().take(3) {
-        let state = State::new(32);
-        let mut max = 0;
-        let handle = std::thread::spawn(move || {
-            run(state, bp, &mut max, &mut HashSet::new());
-            max  
-        });
-        handles.push(handle);
-    }
-    let mut product = 1;
-    for handle in handles {
-        product *= handle.join().unwrap();
-    }
-    product
-}
\ No newline at end of file
+fn multiply_max_geodes(bps: &[BluePrint]) -> u32 {
+    std::thread::scope(|scope| {
+        let mut handles = Vec::new();
+        for bp in bps.iter().copied().take(3) {
+            let state = State::new(32);
+            let handle = scope.spawn(move || {
+                let mut max = 0;
+                run(state, bp, &mut max, &mut HashSet::new());
+                max
+            });
+            handles.push(handle);
+        }
+        handles.into_iter().map(|handle| handle.join().unwrap()).product()
+    })
+}
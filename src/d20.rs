@@ -1,9 +1,16 @@
+use crate::error::{self, ParseError};
+use crate::solution::{Answer, Solution};
+
 static KEY: i64 = 811589153;
-static INPUT: &str = include_str!("../data/d20.txt");
 static _TEST: &str = include_str!("../data/d20_test.txt");
 
-fn parse(input: &str) -> Vec<(i64, usize)>{
-	input.lines().enumerate().map(|(i, n)| (n.parse().unwrap(), i)).collect()
+// the coordinates are counted from the number 0, so there has to be one
+pub fn parse(input: &str) -> Result<Vec<(i64, usize)>, ParseError> {
+	let nums = error::lines(input).enumerate().map(|(i, line)| Ok((line.number(line.text)?, i))).collect::<Result<Vec<_>, _>>()?;
+	match nums.iter().any(|(n, _)| *n == 0) {
+		true => Ok(nums),
+		false => Err(ParseError::end_of_input(input, "the number 0")),
+	}
 }
 
 fn calc_idx(n: i64, idx: i64) -> i64 {
@@ -15,6 +22,10 @@ fn calc_shift(ring_size: i64, idx: i64) -> i64 {
 }
 
 fn shift(nums: &mut Vec<(i64, usize)>, idx: usize) {
+	// a single number has nowhere to go
+	if nums.len() < 2 {
+		return;
+	}
 	let entry = nums.remove(idx);
 	let ring_size = nums.len() as i64;
 	let new_idx = calc_shift(ring_size, calc_idx(entry.0, idx as i64)) as usize;
@@ -36,7 +47,7 @@ fn mix_file(nums: &mut Vec<(i64, usize)>) {
     }
 }
  
-fn coordinates(nums: &Vec<(i64, usize)>) -> i64 {
+fn coordinates(nums: &[(i64, usize)]) -> i64 {
     let zero = nums.iter().position(|(n, _)| *n == 0).unwrap();
     let ring_size = nums.len();
     let fst = (zero + 1000) % ring_size;
@@ -58,15 +69,25 @@ fn decrypt(nums: &mut Vec<(i64, usize)>) {
     }
 }
 
-pub fn get_solution_1() -> i64 {
-    let mut nums = parse(INPUT);
-    mix_file(&mut nums);
-    coordinates(&nums)
-}        
-                        
-pub fn get_solution_2() -> i64 {
-    let mut nums = parse(INPUT);
-    add_key(&mut nums, KEY);
-    decrypt(&mut nums);
-    coordinates(&nums)
+pub struct Day20;
+
+impl Solution for Day20 {
+    type Parsed<'a> = Vec<(i64, usize)>;
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, ParseError> {
+        parse(input)
+    }
+
+    fn part_1(nums: &Vec<(i64, usize)>) -> Answer {
+        let mut nums = nums.clone();
+        mix_file(&mut nums);
+        coordinates(&nums).into()
+    }
+
+    fn part_2(nums: &Vec<(i64, usize)>) -> Answer {
+        let mut nums = nums.clone();
+        add_key(&mut nums, KEY);
+        decrypt(&mut nums);
+        coordinates(&nums).into()
+    }
 }
\ No newline at end of file
@@ -4,36 +4,47 @@ use std::ops::Sub;
 use std::ops::Mul;
 use std::ops::Div;
 
-static INPUT: &str = include_str!("../data/d21.txt");
+use crate::error::{self, ParseError};
+use crate::solution::{Answer, Solution};
+
 static _TEST: &str = include_str!("../data/d21_test.txt");
 
-type Monkeys = HashMap<&'static str, Monkey>;
+type Monkeys<'a> = HashMap<&'a str, Monkey<'a>>;
 
-fn parse(input: &'static str) -> Monkeys {
+pub fn parse(input: &str) -> Result<Monkeys<'_>, ParseError> {
     let mut monkeys = HashMap::new();
-    for parts in input.lines().map(|l| l.split_whitespace().collect::<Vec<_>>()) {
-        let name = &parts[0][0..4];
-        monkeys.insert(name, parts.into());
+    let lines = error::lines(input).collect::<Vec<_>>();
+    for line in &lines {
+        let (name, job) = line.split_once(line.text, ": ")?;
+        let monkey = match job.split_whitespace().collect::<Vec<_>>()[..] {
+            [n] => Monkey::Val(line.number(n)?),
+            [lhs, op, rhs] => Monkey::Instr(lhs, rhs, Op::try_from(op).map_err(|_| line.error(op, "+, -, * or /"))?),
+            _ => return Err(line.error(job, "a number or '<monkey> <op> <monkey>'")),
+        };
+        monkeys.insert(name, monkey);
     }
-    monkeys
-}
-
-enum Monkey {
-    Val(i64),
-    Instr(&'static str, &'static str, Op),
-}
 
-impl From<Vec<&'static str>> for Monkey {
-    fn from(parts: Vec<&'static str>) -> Self {
-        match parts.len() {
-            2 => Monkey::Val(parts[1].parse().unwrap()),
-            _ => Monkey::Instr(parts[1], parts[3], parts[2].into())
+    for line in &lines {
+        if let Some(Monkey::Instr(lhs, rhs, _)) = monkeys.get(&line.text[..line.text.find(':').unwrap_or(0)]) {
+            if let Some(unknown) = [lhs, rhs].into_iter().find(|m| !monkeys.contains_key(*m)) {
+                return Err(line.error(unknown, "a monkey listed in the input"));
+            }
         }
     }
+    match (monkeys.get("root"), monkeys.get("humn")) {
+        (Some(Monkey::Instr(..)), Some(_)) => Ok(monkeys),
+        _ => Err(ParseError::end_of_input(input, "the monkeys root and humn")),
+    }
+}
+
+#[derive(Clone)]
+pub enum Monkey<'a> {
+    Val(i64),
+    Instr(&'a str, &'a str, Op),
 }
 
 #[derive(Clone, Copy, Debug)]
-enum Op {
+pub enum Op {
     Add,
     Sub,
     Mul,
@@ -62,13 +73,16 @@ impl Op {
     }
 }
 
-impl From<&str> for Op {
-    fn from(s: &str) -> Self {
+impl TryFrom<&str> for Op {
+    type Error = ();
+
+    fn try_from(s: &str) -> Result<Self, ()> {
         match s {
-            "+" => Op::Add,
-            "-" => Op::Sub,
-            "*" => Op::Mul,
-            _ => Op::Div,
+            "+" => Ok(Op::Add),
+            "-" => Ok(Op::Sub),
+            "*" => Ok(Op::Mul),
+            "/" => Ok(Op::Div),
+            _ => Err(()),
         }
     }
 }
@@ -106,24 +120,36 @@ fn determine_num(monkey: &str, monkeys: &mut Monkeys, cur_n: f64) -> f64 {
     };
     let (humn, other) = if contains_humn(lhs, monkeys) { (lhs, rhs) } else { (rhs, lhs) };
     let result = calculate(other, monkeys) as f64;
-    let next_n = next_op.inv().exec(cur_n, result);
+    let next_n = match next_op {
+        // other - humn = cur_n and other / humn = cur_n don't invert like the other cases
+        Op::Sub | Op::Div if humn == rhs => next_op.exec(result, cur_n),
+        op => op.inv().exec(cur_n, result),
+    };
 
     determine_num(humn, monkeys, next_n)
 }
 
-pub fn get_solution_1() -> i64 {
-    let mut monkeys = parse(INPUT);
-    calculate("root", &mut monkeys)
-}
+pub struct Day21;
 
-pub fn get_solution_2() -> f64 {
-    let mut monkeys = parse(INPUT);
-    let (lhs, rhs) = match monkeys.remove("root").unwrap() {
-        Monkey::Instr(lhs, rhs, _) => (lhs, rhs),
-        _ => unreachable!(),
-    };
-    let (humn, other) = if contains_humn(lhs, &monkeys) { (lhs, rhs) } else { (rhs, lhs) }; 
-    // because i'm to lazy to fix Op::inv, i pass the negative result
-    let result = calculate(other, &mut monkeys);
-    determine_num(humn, &mut monkeys, -result as f64) 
+impl Solution for Day21 {
+    type Parsed<'a> = Monkeys<'a>;
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, ParseError> {
+        parse(input)
+    }
+
+    fn part_1(monkeys: &Monkeys) -> Answer {
+        calculate("root", &mut monkeys.clone()).into()
+    }
+
+    fn part_2(monkeys: &Monkeys) -> Answer {
+        let mut monkeys = monkeys.clone();
+        let (lhs, rhs) = match monkeys.remove("root").unwrap() {
+            Monkey::Instr(lhs, rhs, _) => (lhs, rhs),
+            _ => unreachable!(),
+        };
+        let (humn, other) = if contains_humn(lhs, &monkeys) { (lhs, rhs) } else { (rhs, lhs) }; 
+        let result = calculate(other, &mut monkeys);
+        determine_num(humn, &mut monkeys, result as f64).into()
+    }
 }
\ No newline at end of file
@@ -2,73 +2,94 @@ use std::collections::HashMap;
 use std::fmt::Display;
 use std::ops::Index;
 
-use crate::helpers::gcd;
+use crate::error::{self, Line, ParseError};
+use crate::frames::{Frame, Recorder};
+use crate::helpers::{gcd, Complex, Grid};
+use crate::solution::{Answer, Solution};
 
-static INPUT: &str = include_str!("../data/d22.txt");
 static _TEST: &str = include_str!("../data/d22_test.txt");
 
-fn parse(input: &str) -> (Maze, Vec<Instr>)  {
-    let len = input.find('\n').unwrap();
-    let mut tiles = input.lines().take_while(|l| !l.is_empty())
-        .map(|l| l.chars()
-            .map(|c| match c {
-                '.' => Tile::Floor,
-                '#' => Tile::Wall,
-                _ => Tile::None,
-            })
-            .collect())
-        .collect::<Vec<Vec<Tile>>>();
-    
-    for row in tiles.iter_mut() {
-        while row.len() < len {
-            row.push(Tile::None);
+// the rows of the map and the path below it, separated by an empty line
+fn parse_notes(input: &str) -> Result<(Vec<Line<'_>>, Vec<Instr>), ParseError> {
+    let mut lines = error::lines(input);
+    let rows = lines.by_ref().take_while(|l| !l.text.is_empty()).collect::<Vec<_>>();
+    for row in &rows {
+        if let Some(i) = row.text.chars().position(|c| !matches!(c, ' ' | '.' | '#')) {
+            return Err(row.error_at(i + 1, "' ', '.' or '#'"));
         }
     }
-    
-    let pos = (0, tiles[0].iter().position(|t| t == &Tile::Floor).unwrap());
-    (Maze { tiles, player: Player::new(pos) }, parse_instructions(input.lines().last().unwrap()))
+    match rows.first() {
+        None => return Err(ParseError::end_of_input(input, "a map")),
+        Some(row) if !row.text.contains('.') => return Err(row.end_error("an open tile to start on")),
+        _ => (),
+    }
+
+    let path = lines.next().ok_or(ParseError::end_of_input(input, "the path to follow"))?;
+    Ok((rows, parse_instructions(path)?))
 }
 
-fn parse_instructions(line: &str) -> Vec<Instr> {
+pub fn parse(input: &str) -> Result<(Maze, Vec<Instr>), ParseError> {
+    let (rows, instructions) = parse_notes(input)?;
+    // the rows are only as long as their last tile
+    let width = rows.iter().map(|l| l.text.len()).max().unwrap_or(0);
+    let tiles = Grid::from_fn(width, rows.len(), |(row, col)| match rows[row].text.as_bytes().get(col) {
+        Some(b'.') => Tile::Floor,
+        Some(b'#') => Tile::Wall,
+        _ => Tile::None,
+    });
+
+    let pos = tiles.position(|t| t == &Tile::Floor).unwrap();
+    Ok((Maze { tiles, player: Player::new(pos) }, instructions))
+}
+
+// numbers of steps with a turn between each of them, e.g. 10R5L5
+fn parse_instructions(line: Line) -> Result<Vec<Instr>, ParseError> {
     let mut instructions = Vec::new();
-    let steps: Vec<Instr> = line.split(|c: char| c.is_alphabetic()).map(|n| Instr::Step(n.parse().unwrap())).collect();
-    let dirs: Vec<Instr> = line.replace(|c: char| c.is_numeric(), "").chars().map(|s| Instr::Turn(if s == 'R' { Turn::Right } else { Turn::Left })).collect();
-    let size = steps.len().max(dirs.len());
-    
-    for i in 0..size {
-        if let Some(step) = steps.get(i) {
-            instructions.push(*step);
-        }
-        if let Some(dir) = dirs.get(i) {
-            instructions.push(*dir);
-        }
+    let mut rest = line.text;
+
+    loop {
+        let len = rest.find(|c: char| !c.is_ascii_digit()).unwrap_or(rest.len());
+        instructions.push(Instr::Step(line.number(&rest[..len])?));
+        rest = &rest[len..];
+
+        let Some(turn) = rest.chars().next() else { break };
+        let (turn, tail) = rest.split_at(turn.len_utf8());
+        instructions.push(Instr::Turn(match turn {
+            "R" => Turn::Right,
+            "L" => Turn::Left,
+            _ => return Err(line.error(turn, "'L' or 'R'")),
+        }));
+        rest = tail;
     }
 
-    instructions
+    Ok(instructions)
 }
 
-fn parse_cube(input: &str) -> (Cube, Vec<Instr>) {
-    let cube_raw = input.lines().take_while(|l| !l.is_empty()).map(|l| l.chars().collect()).collect::<Vec<Vec<_>>>();
+pub fn parse_cube(input: &str) -> Result<(Cube, Vec<Instr>), ParseError> {
+    let (rows, instructions) = parse_notes(input)?;
+    let cube_raw = rows.iter().map(|l| l.text.chars().collect()).collect::<Vec<Vec<_>>>();
+    let not_a_cube = || ParseError::new(1, 1, "a map that folds into a cube");
     // determine side length: 
     // get positions of sides on 2d plane
-    let (sides, positions) = parse_cube_sides(&cube_raw);
+    let (sides, positions) = parse_cube_sides(&cube_raw).ok_or_else(not_a_cube)?;
     let mut adj_sides = HashMap::new();
     // relative position of where an adjacent side might be
     for side in 0..6 {
-        for (dir, other_side, other_dir) in get_adj_sides(side, &positions, &adj_sides) {
+        for (dir, other_side, other_dir) in get_adj_sides(side, &positions, &adj_sides).ok_or_else(not_a_cube)? {
             adj_sides.insert((side, dir), (other_side, other_dir));
             // directions will flip if going the other way
             adj_sides.insert((other_side, ((other_dir + 2) % 4)), (side, ((dir + 2) % 4)));
         }
     }
 
-    (Cube { sides, positions, adj_sides, player: Player { pos: (0, 0), facing: 1 }, cur_side: 0 }, parse_instructions(input.lines().last().unwrap()))
+    Ok((Cube { sides, positions, adj_sides, player: Player { pos: (0, 0), facing: 1 }, cur_side: 0 }, instructions))
 }
 
 type CubeSides =[Vec<Vec<Tile>>; 6];
 type CubePositions = [(usize, usize); 6]; 
 
-fn parse_cube_sides(cube_raw: &Vec<Vec<char>>) -> (CubeSides, CubePositions) {
+// None unless the map consists of exactly 6 sides
+fn parse_cube_sides(cube_raw: &[Vec<char>]) -> Option<(CubeSides, CubePositions)> {
     const EMPTY_V: Vec<Vec<Tile>> = Vec::new();
     let height = cube_raw.len();
     let width = cube_raw.iter().max_by(|a, b| a.len().cmp(&b.len())).unwrap().len();
@@ -83,7 +104,7 @@ fn parse_cube_sides(cube_raw: &Vec<Vec<char>>) -> (CubeSides, CubePositions) {
             let col_start = col * side_len;
             if let Some(true) = cube_raw.get(row_start)
                                         .and_then(|row| row.get(col_start).map(|tile| !tile.is_whitespace())) {
-                sides[side] = cube_raw[row_start..row_start + side_len]
+                *sides.get_mut(side)? = cube_raw[row_start..row_start + side_len]
                     .iter()
                     .map(|row| row.iter().skip(col_start).take(side_len)
                         .map(|c| if *c == '.' { Tile::Floor } else { Tile::Wall })
@@ -95,23 +116,24 @@ fn parse_cube_sides(cube_raw: &Vec<Vec<char>>) -> (CubeSides, CubePositions) {
         }
     }
 
-    (sides, positions)
+    (side == 6).then_some((sides, positions))
 }
 
-fn get_adj_sides(side: usize, positions: &[(usize, usize)], adj_sides_map: &HashMap<(usize, u8), (usize, u8)>) -> Vec<(u8, usize, u8)> {
+fn get_adj_sides(side: usize, positions: &[(usize, usize)], adj_sides_map: &HashMap<(usize, u8), (usize, u8)>) -> Option<Vec<(u8, usize, u8)>> {
     // look for dist 1
     let mut adj_sides = Vec::new();
     for dir in 0..4 {
         if !adj_sides_map.contains_key(&(side, dir)) {
-            let (side, other_dir) = look_for_adj_side(side, dir, positions);
+            let (side, other_dir) = look_for_adj_side(side, dir, positions)?;
             adj_sides.push((dir, side, other_dir));
         }
     }
-    adj_sides
+    Some(adj_sides)
 }
 
 // returns side_id, dir
-fn look_for_adj_side(side: usize, dir: u8, positions: &[(usize, usize)]) -> (usize, u8) {
+// None if the sides don't fold into a cube
+fn look_for_adj_side(side: usize, dir: u8, positions: &[(usize, usize)]) -> Option<(usize, u8)> {
     // if dir is facing downwards, directions stay the same
     // otherwise rotate directions: 
     let candidates = get_candidates();
@@ -129,10 +151,10 @@ fn look_for_adj_side(side: usize, dir: u8, positions: &[(usize, usize)]) -> (usi
         let other_side = positions.iter()
                                   .position(|other_pos| other_pos == &(new_pos.0 as usize, new_pos.1 as usize))
                                   .unwrap();
-        return (other_side, rotate_dir(dir, other_dir)); 
+        return Some((other_side, rotate_dir(dir, other_dir))); 
     }
     
-    unreachable!()
+    None
 }
 
 fn rotate_dir(dir: u8, other_dir: u8) -> u8 {
@@ -159,19 +181,19 @@ fn get_candidates() -> [(Vec<u8>, u8); 18]{
     ]
 }
 
-#[derive(Debug)]
-struct Maze{
-    tiles: Vec<Vec<Tile>>,
+#[derive(Debug, Clone)]
+pub struct Maze{
+    tiles: Grid<Tile>,
     player: Player,
 }
 
 impl Maze {
-    fn height(&self) -> usize {
-        self.tiles.len()
+    pub fn height(&self) -> usize {
+        self.tiles.height()
     }
 
-    fn width(&self) -> usize {
-        self.tiles[0].len()
+    pub fn width(&self) -> usize {
+        self.tiles.width()
     }
 
     fn take_steps(&mut self, n_steps: u64) {
@@ -228,34 +250,23 @@ impl Index<(usize, usize)> for Maze {
     type Output = Tile;
 
     fn index(&self, index: (usize, usize)) -> &Self::Output {
-        &self.tiles[index.0][index.1]
+        &self.tiles[index]
     }
 }
 
 impl Display for Maze {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        for (row, line) in self.tiles.iter().enumerate() {
-            let _ = writeln!(f, "{}", line.iter().enumerate()
-                .map(|(col, t)| 
-                    if self.player.pos == (row, col) {
-                        char::from(&self.player)
-                    } else {
-                        match t {
-                            Tile::None => ' ',
-                            Tile::Floor => '.',
-                            Tile::Wall => '#',
-                        }
-                    }
-                )
-                .collect::<String>()
-            );
-        }
-
-        Ok(())
+        let mut tiles = self.tiles.map(|t| match t {
+            Tile::None => ' ',
+            Tile::Floor => '.',
+            Tile::Wall => '#',
+        });
+        tiles[self.player.pos] = char::from(&self.player);
+        write!(f, "{}", tiles)
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 struct Player {
     pos: (usize, usize),
     facing: u8, // 0: up, 1: right, 2: down, 3: left
@@ -283,7 +294,7 @@ impl From<&Player> for char {
 }
 
 #[derive(Debug, Clone, Copy)]
-enum Turn {
+pub enum Turn {
     Right,
     Left,
 }
@@ -297,26 +308,26 @@ impl From<Turn> for u8 {
     }
 }
 
-#[derive(Debug, PartialEq, Eq)]
-enum Tile {
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Tile {
     None,
     Floor,
     Wall,
 }
 
 #[derive(Debug, Clone, Copy)]
-enum Instr {
+pub enum Instr {
     Step(u64),
     Turn(Turn),
 }
 
 trait Dir {
-    fn is_opposite(&self, other: Self) -> bool;
+    fn _is_opposite(&self, other: Self) -> bool;
     fn adjust_side(&self, side_len: usize, pos: (usize, usize)) -> (usize, usize);
 }
 
 impl Dir for u8 {
-    fn is_opposite(&self, other: Self) -> bool {
+    fn _is_opposite(&self, other: Self) -> bool {
         matches!((self, other), (0, 2) | (2, 0) | (1, 3) | (3, 1))
     }
 
@@ -330,32 +341,6 @@ impl Dir for u8 {
     }
 }
 
-trait Complex {
-    fn c_add(&self, other: Self) -> Self;
-    fn c_mul(&self, other: Self) -> Self;
-    fn c_div(&self, other: Self) -> Self;
-}
-
-impl<T> Complex for (T, T) 
-where T: std::ops::Add<Output = T> + std::ops::Sub<Output = T> + std::ops::Mul<Output = T> + std::ops::Div<Output = T> + Copy
-{
-    fn c_add(&self, other: Self) -> Self {
-        (self.0 + other.0, self.1 + other.1)
-    }
-
-    fn c_mul(&self, other: Self) -> Self {
-        (self.0 * other.0 - self.1 * other.1, 
-         self.0 * other.1 + self.1 * other.0)
-    }
-
-    fn c_div(&self, other: Self) -> Self {
-        let divisor = self.0 * self.0 + self.1 * self.1;
-        let real = self.0 * other.0 + self.1 * other.1;
-        let complex = self.0 * other.1 - self.1 * other.0;
-        ( real / divisor, complex / divisor)
-    }
-}
-
 fn handle_instr(instr: Instr, maze: &mut Maze) {
     match instr {
         Instr::Step(n_steps) => maze.take_steps(n_steps),
@@ -363,8 +348,8 @@ fn handle_instr(instr: Instr, maze: &mut Maze) {
     }
 }
 
-fn execute_all(instructions: Vec<Instr>, maze: &mut Maze) -> (usize, usize, usize) {
-    for instr in instructions {
+fn execute_all(instructions: &[Instr], maze: &mut Maze) -> (usize, usize, usize) {
+    for instr in instructions.iter().copied() {
         // println!("{}", maze);
         handle_instr(instr, maze);
     }
@@ -372,8 +357,8 @@ fn execute_all(instructions: Vec<Instr>, maze: &mut Maze) -> (usize, usize, usiz
     (maze.player.pos.0 + 1, maze.player.pos.1 + 1, (maze.player.facing + 3) as usize % 4)
 }
 
-#[derive(Debug)]
-struct Cube {
+#[derive(Debug, Clone)]
+pub struct Cube {
     sides: CubeSides,
     positions: CubePositions,
     adj_sides: HashMap<(usize, u8), (usize, u8)>,
@@ -382,8 +367,8 @@ struct Cube {
 }
 
 impl Cube {
-    fn execute_all(&mut self, instructions: Vec<Instr>) -> (usize, usize, usize) {
-        for instr in instructions {
+    fn execute_all(&mut self, instructions: &[Instr]) -> (usize, usize, usize) {
+        for instr in instructions.iter().copied() {
             self.execute_instr(instr);
         }
 
@@ -460,13 +445,67 @@ impl Cube {
     }
 }
 
-pub fn get_solution_1() -> usize {
-    let (mut maze, instructions) = parse(INPUT);
-    let (row, col, facing) = execute_all(instructions, &mut maze);
+// the walk on the flat map of part 1, a frame per step. the tiles it passed show the way it was facing
+pub fn record(input: &str, recorder: &mut Recorder) -> Result<(), ParseError> {
+    let (mut maze, instructions) = parse(input)?;
+    let mut trail = HashMap::new();
+    let frame = |maze: &Maze, trail: &HashMap<(usize, usize), char>| {
+        let mut frame = Frame::from_text(&maze.to_string());
+        for (&(row, col), &c) in trail {
+            frame.set(row, col, c);
+        }
+        frame.set(maze.player.pos.0, maze.player.pos.1, '@');
+        frame
+    };
+
+    recorder.tick(|| frame(&maze, &trail));
+    for instr in instructions {
+        trail.insert(maze.player.pos, char::from(&maze.player));
+        match instr {
+            Instr::Step(n_steps) => for _ in 0..n_steps {
+                if recorder.is_full() {
+                    return Ok(());
+                }
+                if !maze.update_player_pos() {
+                    break;
+                }
+                trail.insert(maze.player.pos, char::from(&maze.player));
+                recorder.tick(|| frame(&maze, &trail));
+            },
+            Instr::Turn(dir) => maze.player.turn(dir),
+        }
+    }
+    recorder.last(|| frame(&maze, &trail));
+    Ok(())
+}
+
+pub struct Day22;
+
+impl Solution for Day22 {
+    // part 1 walks on the flat map, part 2 on the folded cube
+    type Parsed<'a> = (Maze, Cube, Vec<Instr>);
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, ParseError> {
+        let (maze, instructions) = parse(input)?;
+        let (cube, _) = parse_cube(input)?;
+        Ok((maze, cube, instructions))
+    }
+
+    fn part_1((maze, _, instructions): &Self::Parsed<'_>) -> Answer {
+        walk_maze(&mut maze.clone(), instructions).into()
+    }
+
+    fn part_2((_, cube, instructions): &Self::Parsed<'_>) -> Answer {
+        walk_cube(&mut cube.clone(), instructions).into()
+    }
+}
+
+fn walk_maze(maze: &mut Maze, instructions: &[Instr]) -> usize {
+    let (row, col, facing) = execute_all(instructions, maze);
     1000 * row + 4 * col + facing
 }
 
-pub fn get_solution_2() -> usize {
+fn walk_cube(cube: &mut Cube, instructions: &[Instr]) -> usize {
     // all coordinates are stored relatively
     // store shape of cube as hashmap, by checking where a player ends up at,
     // store positions of sides in a hashmap also
@@ -475,15 +514,14 @@ pub fn get_solution_2() -> usize {
     // only work with relative position
     // at the last step, translate relative position to absolute
     // store cube sides like [s1, s2, s3, s4, s5, s6]
-    let (mut cube, instructions) = parse_cube(INPUT);
     let (row, col ,facing) = cube.execute_all(instructions);
-    1000 * row + 4 * col + (facing + 3) as usize % 4
+    1000 * row + 4 * col + (facing + 3) % 4
 }
 
 
 #[test]
 fn test_init_maze() {
-    let (maze, instr) = parse(_TEST);
+    let (maze, instr) = parse(_TEST).unwrap();
     assert_eq!(maze.player.pos, (0, 8));
     println!("{}", maze);
     println!("{:?}", instr);
@@ -491,7 +529,7 @@ fn test_init_maze() {
 
 #[test]
 fn test_update_pos() {
-    let (mut maze, _) = parse(_TEST);
+    let (mut maze, _) = parse(_TEST).unwrap();
     assert!(maze.update_player_pos());
     assert_eq!(maze.player.pos, (0, 9));
 
@@ -517,8 +555,8 @@ fn test_update_pos() {
 
 #[test]
 fn test_execute_all() {
-    let (mut maze, instructions) = parse(INPUT);
-    execute_all(instructions, &mut maze);
+    let (mut maze, instructions) = parse(include_str!("../data/d22.txt")).unwrap();
+    execute_all(&instructions, &mut maze);
 }
 
 #[test]
@@ -561,5 +599,6 @@ fn test_rotate_dir() {
 
 #[test]
 fn test_parse_cube() {
-    println!("{}", get_solution_2());
+    let (mut cube, instructions) = parse_cube(include_str!("../data/d22.txt")).unwrap();
+    println!("{}", walk_cube(&mut cube, &instructions));
 }
\ No newline at end of file
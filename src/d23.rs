@@ -11,11 +11,14 @@
 use std::collections::{HashSet, HashMap};
 use std::fmt::Write;
 
-use crate::helpers::Complex;
+use crate::error::{self, ParseError};
+use crate::frames::{Frame, Recorder};
+use crate::helpers::{BitGrid, Complex};
+use crate::repl::Explore;
+use crate::solution::{Answer, Solution};
 
 type Pos = (i64, i64);
 
-static INPUT: &str = include_str!("../data/d23.txt");
 static _TEST: &str = include_str!("../data/d23_test.txt");
 
 // coords: (x, y)
@@ -28,16 +31,22 @@ const SW: Pos = (-1, -1);
 const W: Pos = (-1, 0);
 const NW: Pos = (-1, 1);
 
-fn parse(input: &str) -> HashSet::<Pos> {
+pub fn parse(input: &str) -> Result<HashSet::<Pos>, ParseError> {
     let mut elves = HashSet::new(); 
-    for (y, line) in input.lines().rev().enumerate() {
-        for (x, cell) in line.chars().enumerate() {
-            if cell == '#' {
-                elves.insert((x as i64, y as i64));
+    for (y, line) in error::lines(input).collect::<Vec<_>>().into_iter().rev().enumerate() {
+        for (x, cell) in line.text.chars().enumerate() {
+            match cell {
+                '#' => { elves.insert((x as i64, y as i64)); },
+                '.' => (),
+                _ => return Err(line.error_at(x + 1, "'#' or '.'")),
             }
         }
     }
-    elves
+    // the empty ground is counted within the rectangle around the elves
+    if elves.is_empty() {
+        return Err(ParseError::end_of_input(input, "at least one elf"));
+    }
+    Ok(elves)
 }
 
 fn check_dir(elf: Pos, elves: &HashSet<Pos>, dirs: &[Pos; 3]) -> bool {
@@ -118,13 +127,18 @@ fn do_round(elves: HashSet<Pos>, moves: &[[Pos; 3]]) -> (HashSet<Pos>, bool) {
     (execute_moves(proposed_moves), false)
 }
 
-fn move_elves(mut elves: HashSet<Pos>, n_rounds: u64) -> HashSet<Pos> {
-    let mut moves = vec![
+// the directions the elves consider in the first round
+fn moves() -> Vec<[Pos; 3]> {
+    vec![
         [N, NE, NW],
         [S, SE, SW],
         [W, NW, SW],
         [E, NE, SE],
-    ];
+    ]
+}
+
+fn move_elves(mut elves: HashSet<Pos>, n_rounds: u64) -> HashSet<Pos> {
+    let mut moves = moves();
 
     for _ in 0..n_rounds {
         (elves, _) = do_round(elves, &moves);
@@ -135,12 +149,7 @@ fn move_elves(mut elves: HashSet<Pos>, n_rounds: u64) -> HashSet<Pos> {
     elves
 }
 fn move_elves_p2(mut elves: HashSet<Pos>) -> u64 {
-    let mut moves = vec![
-        [N, NE, NW],
-        [S, SE, SW],
-        [W, NW, SW],
-        [E, NE, SE],
-    ];
+    let mut moves = moves();
     let mut none_moved;
     for i in 1.. {
         (elves, none_moved) = do_round(elves, &moves);
@@ -153,7 +162,7 @@ fn move_elves_p2(mut elves: HashSet<Pos>) -> u64 {
     unreachable!();
 }
 
-fn _print_grid(elves: &HashSet<Pos>) {
+fn render_grid(elves: &HashSet<Pos>) -> String {
     let mut grid = Vec::new();
     let (width, height) = determine_grid_size(elves);
     for _ in height.0..height.1 {
@@ -166,17 +175,199 @@ fn _print_grid(elves: &HashSet<Pos>) {
     for line in grid.iter().rev() {
         let _ = writeln!(s, "{}", line.iter().collect::<String>());
     }
-    println!("{}", s);
+    s
+}
+
+// the elves in the repl, a tick is a round
+pub struct Grove {
+    elves: HashSet<Pos>,
+    moves: Vec<[Pos; 3]>,
+    rounds: usize,
+    // no elf moved in the last round
+    settled: bool,
 }
 
-pub fn get_solution_1() -> u64 {
-    let mut elves = parse(INPUT);
-    elves = move_elves(elves, 10);
-    let (width, height) = determine_grid_size(&elves);
-    count_empty_cells(&elves, (width.0.abs_diff(width.1), height.0.abs_diff(height.1)))
+pub fn explore(input: &str) -> Result<Box<dyn Explore + '_>, ParseError> {
+    Ok(Box::new(Grove { elves: parse(input)?, moves: moves(), rounds: 0, settled: false }))
 }
 
-pub fn get_solution_2() -> u64 {
-    let elves = parse(INPUT);
-    move_elves_p2(elves)
-}
\ No newline at end of file
+impl Grove {
+    fn empty(&self) -> u64 {
+        let (width, height) = determine_grid_size(&self.elves);
+        count_empty_cells(&self.elves, (width.0.abs_diff(width.1), height.0.abs_diff(height.1)))
+    }
+}
+
+impl Explore for Grove {
+    fn step(&mut self, n: usize) -> Option<String> {
+        for _ in 0..n {
+            if self.settled {
+                break;
+            }
+            (self.elves, self.settled) = do_round(std::mem::take(&mut self.elves), &self.moves);
+            self.moves.rotate_left(1);
+            self.rounds += 1;
+        }
+        Some(match self.settled {
+            true => format!("after round {}, in which no elf moved", self.rounds),
+            false => format!("after round {}", self.rounds),
+        })
+    }
+
+    fn show(&self) -> String {
+        render_grid(&self.elves)
+    }
+
+    fn query(&mut self, query: &[&str]) -> Result<String, String> {
+        match query {
+            ["empty"] => Ok(self.empty().to_string()),
+            ["elves"] => Ok(self.elves.len().to_string()),
+            _ => Err(format!("{} takes no arguments", query[0])),
+        }
+    }
+}
+
+// a frame per round, placed where the elves are as the grove spreads out in every direction
+pub fn record(input: &str, recorder: &mut Recorder) -> Result<(), ParseError> {
+    let mut elves = parse(input)?;
+    let mut moves = moves();
+    // the grid is rendered from the top, y grows upwards
+    let frame = |elves: &HashSet<Pos>| {
+        let ((left, _), (_, top)) = determine_grid_size(elves);
+        Frame::from_text(&render_grid(elves)).at(1 - top, left)
+    };
+
+    loop {
+        recorder.tick(|| frame(&elves));
+        if recorder.is_full() {
+            return Ok(());
+        }
+        let settled;
+        (elves, settled) = do_round(elves, &moves);
+        moves.rotate_left(1);
+        if settled {
+            break;
+        }
+    }
+    recorder.last(|| frame(&elves));
+    Ok(())
+}
+
+// the elves as a bit grid with row 0 in the north, a round moves all of them at once by shifting
+// the grid. it grows whenever an elf reaches its border
+fn to_bit_grid(elves: &HashSet<Pos>) -> BitGrid {
+    let ((left, right), (bottom, top)) = determine_grid_size(elves);
+    let mut grid = BitGrid::new((right - left) as usize, (top - bottom) as usize);
+    for (x, y) in elves {
+        grid.set((top - 1 - y) as usize, (x - left) as usize);
+    }
+    grid
+}
+
+// the grid with room for the elves to spread out on every side
+fn grow(elves: &BitGrid) -> BitGrid {
+    const MARGIN: usize = 16;
+    let mut grown = BitGrid::new(elves.width() + 2 * MARGIN, elves.height() + 2 * MARGIN);
+    for (row, col) in elves.iter() {
+        grown.set(row + MARGIN, col + MARGIN);
+    }
+    grown
+}
+
+fn touches_border(elves: &BitGrid) -> bool {
+    let (last_row, last_col) = (elves.height() - 1, elves.width() - 1);
+    elves.count_row(0) > 0 || elves.count_row(last_row) > 0 || elves.count_col(0) > 0 || elves.count_col(last_col) > 0
+}
+
+// the order of the directions rotates like in do_round, the offsets are (rows, cols)
+fn bit_round(elves: &BitGrid, round: usize) -> (BitGrid, bool) {
+    let elves = if touches_border(elves) { grow(elves) } else { elves.clone() };
+    // the cells whose neighbour in a direction is an elf
+    let taken = |(rows, cols): (isize, isize)| elves.shifted(-rows, -cols);
+    let [n, s, w, e] = [(-1, 0), (1, 0), (0, -1), (0, 1)].map(taken);
+    let [nw, ne, sw, se] = [(-1, -1), (-1, 1), (1, -1), (1, 1)].map(taken);
+
+    let crowded = n.clone() | &s | &w | &e | &nw | &ne | &sw | &se;
+    let free = [!&(n | &nw | &ne), !&(s | &sw | &se), !&(w | &nw | &sw), !&(e | &ne | &se)];
+    let offsets = [(-1, 0), (1, 0), (0, -1), (0, 1)];
+
+    let mut undecided = &elves & &crowded;
+    let mut next = &elves & &!&crowded;
+    // the cells the elves propose to move to, in the order n, s, w, e
+    let mut targets = Vec::new();
+    for i in (0..4).map(|i| (i + round) % 4) {
+        let proposing = &undecided & &free[i];
+        undecided &= &!&free[i];
+        targets.push((i, proposing.shifted(offsets[i].0, offsets[i].1)));
+    }
+    targets.sort_by_key(|(i, _)| *i);
+    next |= &undecided;
+
+    // only elves coming from opposite directions can propose the same cell, they both stay
+    for (a, b) in [(0, 1), (2, 3)] {
+        let clash = &targets[a].1 & &targets[b].1;
+        next |= &(&targets[a].1 ^ &clash);
+        next |= &(&targets[b].1 ^ &clash);
+        next |= &clash.shifted(-offsets[a].0, -offsets[a].1);
+        next |= &clash.shifted(-offsets[b].0, -offsets[b].1);
+    }
+
+    let settled = next == elves;
+    (next, settled)
+}
+
+fn count_empty_bits(elves: &BitGrid) -> usize {
+    let rows = elves.iter().map(|(row, _)| row).min().zip(elves.iter().map(|(row, _)| row).max());
+    let cols = elves.iter().map(|(_, col)| col).min().zip(elves.iter().map(|(_, col)| col).max());
+    match (rows, cols) {
+        (Some((top, bottom)), Some((left, right))) => (bottom - top + 1) * (right - left + 1) - elves.count(),
+        _ => 0,
+    }
+}
+
+pub struct Day23;
+
+impl Solution for Day23 {
+    type Parsed<'a> = HashSet<Pos>;
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, ParseError> {
+        parse(input)
+    }
+
+    fn part_1(elves: &HashSet<Pos>) -> Answer {
+        let elves = move_elves(elves.clone(), 10);
+        let (width, height) = determine_grid_size(&elves);
+        count_empty_cells(&elves, (width.0.abs_diff(width.1), height.0.abs_diff(height.1))).into()
+    }
+
+    fn part_2(elves: &HashSet<Pos>) -> Answer {
+        move_elves_p2(elves.clone()).into()
+    }
+}
+
+pub struct Day23BitGrid;
+
+impl Solution for Day23BitGrid {
+    type Parsed<'a> = BitGrid;
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, ParseError> {
+        parse(input).map(|elves| to_bit_grid(&elves))
+    }
+
+    fn part_1(elves: &BitGrid) -> Answer {
+        let elves = (0..10).fold(elves.clone(), |elves, round| bit_round(&elves, round).0);
+        (count_empty_bits(&elves) as u64).into()
+    }
+
+    fn part_2(elves: &BitGrid) -> Answer {
+        let mut elves = elves.clone();
+        for round in 0.. {
+            let settled;
+            (elves, settled) = bit_round(&elves, round);
+            if settled {
+                return (round as u64 + 1).into();
+            }
+        }
+        unreachable!();
+    }
+}
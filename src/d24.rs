@@ -1,34 +1,55 @@
-use std::{fmt::Write, collections::{BinaryHeap, HashSet}};
+use std::{fmt::Write, collections::HashSet};
+
+use crate::error::{self, ParseError};
+use crate::frames::{Frame, Recorder};
+use crate::helpers;
+use crate::repl::Explore;
+use crate::solution::{Answer, Solution};
+use crate::trace;
 
-static INPUT: &str = include_str!("../data/d24.txt");
 static _TEST: &str = include_str!("../data/d24_test.txt");
 static _TEST_MIN: &str = include_str!("../data/d24_test_min.txt");
 
 type Winds = Vec<Vec<Wind>>;
 
-fn parse(input: &str) -> (State, Winds) {
+pub fn parse(input: &str) -> Result<(State, Winds), ParseError> {
     let mut winds = Vec::new();
-    let width = input.find('\n').unwrap();
-    let height = input.len() / width;
-    for (row, line) in input.lines().enumerate() {
-        for (col, c) in line.chars().enumerate() {
+    let lines = error::lines(input).collect::<Vec<_>>();
+    let width = lines.first().map_or(0, |l| l.text.len());
+    let height = lines.len();
+    if width < 3 || height < 3 {
+        return Err(ParseError::end_of_input(input, "a valley of at least 3x3 tiles"));
+    }
+
+    for (row, line) in lines.iter().enumerate() {
+        if line.text.len() != width {
+            return Err(line.end_error(format!("a row of {} tiles", width)));
+        }
+        for (col, c) in line.text.chars().enumerate() {
+            // the valley is surrounded by walls, except for the entrance at the top and the exit at the bottom
+            let wall = (row == 0 || row == height - 1 || col == 0 || col == width - 1)
+                && (row, col) != (0, 1) && (row, col) != (height - 1, width - 2);
             let dir = match c {
+                '#' if wall => continue,
+                _ if wall => return Err(line.error_at(col + 1, "'#'")),
+                '.' => continue,
                 '^' => (-1, 0),
                 '>' => (0, 1),
                 'v' => (1, 0),
                 '<' => (0, -1),
-                _ => continue
+                _ => return Err(line.error_at(col + 1, "'.', '^', '>', 'v' or '<'")),
             };
-                winds.push(Wind { dir, pos: (row, col) });
-            }
+            winds.push(Wind { dir, pos: (row, col) });
         }
+    }
     
     let goal = (height - 1, width - 2);
-    (State { player: (0, 1), goal, dist: manhattan((0, 1), goal), steps: 0, dim: (height, width) }, vec![winds])
+    let period = helpers::lcm(height - 2, width - 2);
+    Ok((State { player: (0, 1), goal, steps: 0, dim: (height, width), period }, vec![winds]))
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
-struct Wind {
+pub struct Wind {
     dir: (isize, isize),
     pos: (usize, usize),
 }
@@ -36,8 +57,8 @@ struct Wind {
 impl Wind {
     fn update(wind: &Wind, width: usize, height: usize) -> Self {
         let mut pos = (
-            (wind.pos.0 as isize + wind.dir.0 as isize) as usize, 
-            (wind.pos.1 as isize + wind.dir.1 as isize) as usize
+            (wind.pos.0 as isize + wind.dir.0) as usize, 
+            (wind.pos.1 as isize + wind.dir.1) as usize
         );
         if pos.0 == 0 { pos.0 = height - 2 }
         if pos.0 == height - 1 { pos.0 = 1 }
@@ -48,64 +69,56 @@ impl Wind {
 }
 
 #[derive(Debug, Clone, Copy)]
-struct State {
+pub struct State {
     player: (usize, usize),
     goal: (usize, usize),
-    dist: usize,
     steps: usize,
     dim: (usize, usize),
+    // the winds repeat after the lcm of the width and height of the valley
+    period: usize,
 }
 
+// the winds are the same a period later, so are the ways on from a position. this keeps the search
+// finite when there is no way to the goal
 impl PartialEq for State {
     fn eq(&self, other: &Self) -> bool {
-        self.player == other.player && self.steps == other.steps
+        self.player == other.player && self.steps % self.period == other.steps % other.period
     }
 }
 
 impl std::hash::Hash for State {
     fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
         self.player.hash(state);
-        self.steps.hash(state);
+        (self.steps % self.period).hash(state);
     }
 }
 
 impl Eq for State {}
 
-impl PartialOrd for State {
-    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        self.dist.partial_cmp(&other.dist).map(|o| o.reverse())
-    }
-}
-
-impl Ord for State {
-    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-        self.dist.cmp(&other.dist).reverse()
-    }
-}
-
 impl State {
     fn neighbours(&self, winds: &mut Winds) -> Vec<Self> {
         let mut neighbours = Vec::new();
 
-        if winds.len() - 1  == self.steps {
-            let new_winds = winds[self.steps].iter()
-            .map(|w| Wind::update(w, self.dim.1, self.dim.0))
-            .collect();
-            winds.push(new_winds);
-        }
+        self.blow(winds);
 
-        for m in Self::filter_moves(&self.get_moves(), &winds[self.steps + 1]) {
-            let mut n = *self;
-            let to_goal = manhattan(m, n.goal);
-            n.steps += 1;
-            n.player = m;
-            n.dist = to_goal + n.steps;
-            neighbours.push(n)
+        for m in Self::filter_moves(&self.get_moves(), winds_at(winds, self.steps + 1)) {
+            neighbours.push(State { player: m, steps: self.steps + 1, ..*self })
         }
 
         neighbours
     }
 
+    // the winds of the next minute, they are only calculated once and no more minutes than a period
+    // are kept
+    fn blow(&self, winds: &mut Winds) {
+        while winds.len() <= self.steps + 1 && winds.len() < self.period {
+            let new_winds = winds[winds.len() - 1].iter()
+            .map(|w| Wind::update(w, self.dim.1, self.dim.0))
+            .collect();
+            winds.push(new_winds);
+        }
+    }
+
     fn get_moves(&self) -> Vec<(usize, usize)> {
         let mut moves = Vec::new();
 
@@ -136,8 +149,8 @@ impl State {
         self.player == self.goal
     }
 
-    fn _to_string(&self, wind_states: &Winds) -> String {
-        let winds = &wind_states[self.steps];
+    fn render(&self, wind_states: &Winds) -> String {
+        let winds = winds_at(wind_states, self.steps);
         let mut chars = Vec::new();
         let mut s = String::new();
         for row in 0..self.dim.0 {
@@ -177,46 +190,123 @@ fn manhattan(start: (usize, usize), goal: (usize, usize)) -> usize {
     start.0.abs_diff(goal.0) + start.1.abs_diff(goal.1)
 }
 
+// the winds in a minute after they were blown up to it, either all minutes so far are kept or a full period
+fn winds_at(winds: &Winds, minute: usize) -> &[Wind] {
+    &winds[minute % winds.len()]
+}
+
 // only calculate winds once
 // winds are not part of state, but are calculated separately
 // store winds in hashmap
-fn a_star(state: State, winds: &mut Winds) -> State {
-    let mut queue = BinaryHeap::new();
-    let mut visited = HashSet::from([(state.steps, state.player)]);
-
-    queue.push(state);
-    while !queue.is_empty() {
-        let state = queue.pop().unwrap();
-        
-        if state.is_goal() {
-            return state;
+// the state in which the expedition first reaches its goal, every minute is a step. None if the
+// winds block every way to it
+fn a_star(state: State, winds: &mut Winds) -> Option<State> {
+    let _span = trace::span("a_star");
+    helpers::a_star([state], |state| state.neighbours(winds), |_, _| 1, |state| manhattan(state.player, state.goal), State::is_goal).goal
+}
+
+// the valley in the repl, a tick is a minute the expedition waits at the entrance
+pub struct Valley {
+    state: State,
+    winds: Winds,
+}
+
+pub fn explore(input: &str) -> Result<Box<dyn Explore + '_>, ParseError> {
+    let (state, winds) = parse(input)?;
+    Ok(Box::new(Valley { state, winds }))
+}
+
+impl Explore for Valley {
+    fn step(&mut self, n: usize) -> Option<String> {
+        for _ in 0..n {
+            self.state.blow(&mut self.winds);
+            self.state.steps += 1;
         }
-        
-        for n in state.neighbours(winds) {
-            if !visited.contains(&(n.steps, n.player)) {
-                visited.insert((n.steps, n.player));
-                queue.push(n);
-            }
+        Some(format!("minute {}", self.state.steps))
+    }
+
+    fn show(&self) -> String {
+        self.state.render(&self.winds)
+    }
+
+    fn query(&mut self, query: &[&str]) -> Result<String, String> {
+        match query {
+            ["fastest"] => {
+                let state = self.state;
+                let arrival = a_star(state, &mut self.winds).ok_or("the winds block every way to the exit")?.steps;
+                Ok(format!("{} minutes, arriving in minute {}", arrival - state.steps, arrival))
+            },
+            _ => Err(format!("{} takes no arguments", query[0])),
         }
     }
+}
 
-    unreachable!();
+// a fastest way to the exit of part 1 with a frame per minute. the positions the expedition can be
+// at are found minute by minute until the exit is one of them, then the way is traced back from it
+pub fn record(input: &str, recorder: &mut Recorder) -> Result<(), ParseError> {
+    let (start, mut winds) = parse(input)?;
+    let mut reachable = vec![HashSet::from([start.player])];
+    while !reachable[reachable.len() - 1].contains(&start.goal) {
+        let state = State { steps: reachable.len() - 1, ..start };
+        // waiting a period at the entrance leads to the same positions again, so they only ever grow.
+        // once they stop growing the winds block every way to the exit
+        if state.steps >= start.period && reachable[state.steps] == reachable[state.steps - start.period] {
+            recorder.tick(|| Frame::from_text(&start.render(&winds)));
+            return Ok(());
+        }
+        state.blow(&mut winds);
+        let next = reachable[state.steps].iter()
+            .flat_map(|&player| State::filter_moves(&State { player, ..state }.get_moves(), winds_at(&winds, state.steps + 1)))
+            .collect();
+        reachable.push(next);
+    }
+
+    let mut path = vec![start.goal];
+    for positions in reachable.iter().rev().skip(1) {
+        let next = path[path.len() - 1];
+        path.push(*positions.iter().filter(|&&player| manhattan(player, next) <= 1).min().unwrap());
+    }
+    for (steps, player) in path.into_iter().rev().enumerate() {
+        if recorder.is_full() {
+            break;
+        }
+        recorder.tick(|| Frame::from_text(&State { player, steps, ..start }.render(&winds)));
+    }
+    Ok(())
 }
 
-pub fn get_solution_1() -> usize {
-    let (state, mut winds) = parse(INPUT);
-    a_star(state, &mut winds).steps
+pub struct Day24;
+
+impl Solution for Day24 {
+    type Parsed<'a> = (State, Winds);
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, ParseError> {
+        parse(input)
+    }
+
+    fn part_1((state, winds): &(State, Winds)) -> Answer {
+        a_star(*state, &mut winds.clone()).map(|state| state.steps).into()
+    }
+
+    fn part_2((state, winds): &(State, Winds)) -> Answer {
+        there_and_back_again(*state, &mut winds.clone()).into()
+    }
 }
 
-pub fn get_solution_2() -> usize {
-    let (mut state, mut winds) = parse(INPUT);
+fn there_and_back_again(mut state: State, winds: &mut Winds) -> Option<usize> {
     // go to exit
-    state = a_star(state, &mut winds);
+    state = {
+        let _span = trace::span("to the exit");
+        a_star(state, winds)?
+    };
     // go back
     state.goal = (0, 1);
-    state.dist = manhattan(state.player, state.goal) + state.steps;
-    state = a_star(state, &mut winds);
+    state = {
+        let _span = trace::span("back to the start");
+        a_star(state, winds)?
+    };
     // go to exit again
     state.goal = (state.dim.0 - 1, state.dim.1 - 2);
-    a_star(state, &mut winds).steps
+    let _span = trace::span("to the exit again");
+    Some(a_star(state, winds)?.steps)
 }
\ No newline at end of file
@@ -1,15 +1,28 @@
-static INPUT: &str = include_str!("../data/d25.txt");
+use crate::error::{self, ParseError};
+use crate::solution::{Answer, Solution};
+
 static _TEST: &str = include_str!("../data/d25_test.txt");
 
-fn parse(input: &str) -> Vec<Vec<char>> {
-    input.lines().map(|line| line.trim().chars().collect()).collect()
+pub fn parse(input: &str) -> Result<Vec<Vec<char>>, ParseError> {
+    error::lines(input)
+        .map(|line| {
+            let number = line.text.trim();
+            if number.is_empty() {
+                return Err(line.error(number, "a SNAFU number"));
+            }
+            match number.find(|c| !matches!(c, '=' | '-' | '0' | '1' | '2')) {
+                Some(i) => Err(line.error_at(line.column(&number[i..]), "a SNAFU digit")),
+                None => Ok(number.chars().collect()),
+            }
+        })
+        .collect()
 }
 
-fn to_dec(snafu: Vec<char>) -> u64 {
+fn to_dec(snafu: &[char]) -> u64 {
     let mut exp = snafu.len() - 1;
     let mut n = 0;
     for c in snafu {
-        match c {
+        match *c {
             '1' => n += 5_u64.pow(exp as u32),
             '2' => n += 2 * 5_u64.pow(exp as u32),
             '-' => n -= 5_u64.pow(exp as u32),
@@ -61,6 +74,22 @@ fn to_snafu(mut n_base_5: Vec<u32>) -> String {
     snafu.iter().rev().collect()
 }
 
-pub fn get_solution_1() -> String {
-    to_snafu(to_base_5(parse(INPUT).into_iter().map(to_dec).sum()))
+pub struct Day25;
+
+impl Solution for Day25 {
+    const PARTS: u8 = 1;
+
+    type Parsed<'a> = Vec<Vec<char>>;
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, ParseError> {
+        parse(input)
+    }
+
+    fn part_1(numbers: &Vec<Vec<char>>) -> Answer {
+        to_snafu(to_base_5(numbers.iter().map(|n| to_dec(n)).sum())).into()
+    }
+
+    fn part_2(_: &Vec<Vec<char>>) -> Answer {
+        unreachable!("day 25 only has a single part")
+    }
 }
\ No newline at end of file
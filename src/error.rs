@@ -0,0 +1,167 @@
+use std::{fmt::Display, str::FromStr};
+
+// a puzzle input that doesn't look the way a day's parser expects it to
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    // set by whoever knows which day was parsed, the parsers themselves don't
+    pub day: Option<u8>,
+    // line and column start at 1, both are 0 for parameters that aren't part of the input
+    pub line: usize,
+    pub column: usize,
+    pub expected: String,
+    pub found: Option<Found>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Found {
+    Text(String),
+    EndOfLine,
+    EndOfInput,
+}
+
+impl ParseError {
+    pub fn new(line: usize, column: usize, expected: impl Into<String>) -> Self {
+        Self { day: None, line, column, expected: expected.into(), found: None }
+    }
+
+    // for inputs that stop before everything was read, e.g. a monkey missing its last lines
+    pub fn end_of_input(input: &str, expected: impl Into<String>) -> Self {
+        Self { found: Some(Found::EndOfInput), ..Self::new(input.lines().count() + 1, 1, expected) }
+    }
+
+    // for the values a puzzle gives outside of the input, see solution::Params
+    pub fn param(name: &str, expected: impl Into<String>) -> Self {
+        Self::new(0, 0, format!("{} for the parameter {}", expected.into(), name))
+    }
+
+    // what was there instead, nothing at all is the end of the line
+    pub fn found(mut self, found: &str) -> Self {
+        self.found = Some(if found.is_empty() { Found::EndOfLine } else { Found::Text(found.to_string()) });
+        self
+    }
+
+    pub fn in_day(mut self, day: u8) -> Self {
+        self.day = Some(day);
+        self
+    }
+
+    // the message followed by the offending line of input with a marker below the column
+    pub fn diagnostic(&self, input: &str) -> String {
+        let Some(text) = self.line.checked_sub(1).and_then(|i| input.lines().nth(i)) else { return self.to_string() };
+        let number = self.line.to_string();
+        let margin = " ".repeat(number.len());
+        format!("{}\n {} | {}\n {} | {}^", self, number, text, margin, " ".repeat(self.column - 1))
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Some(day) = self.day {
+            write!(f, "day {}, ", day)?;
+        }
+        if self.line > 0 {
+            write!(f, "line {}, column {}: ", self.line, self.column)?;
+        }
+        write!(f, "expected {}", self.expected)?;
+        match &self.found {
+            Some(Found::Text(found)) => write!(f, ", found '{}'", found),
+            Some(Found::EndOfLine) => write!(f, ", found end of line"),
+            Some(Found::EndOfInput) => write!(f, ", found end of input"),
+            None => Ok(()),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+// a line of the input together with its number, so parsers can point at what went wrong
+#[derive(Debug, Clone, Copy)]
+pub struct Line<'a> {
+    pub number: usize,
+    pub text: &'a str,
+}
+
+pub fn lines(input: &str) -> impl Iterator<Item = Line<'_>> {
+    input.lines().enumerate().map(|(i, text)| Line { number: i + 1, text })
+}
+
+impl<'a> Line<'a> {
+    // column at which part starts, part has to be a slice of the line's text
+    pub fn column(&self, part: &str) -> usize {
+        let offset = (part.as_ptr() as usize).wrapping_sub(self.text.as_ptr() as usize);
+        match self.text.get(..offset) {
+            Some(before) => before.chars().count() + 1,
+            None => 1,
+        }
+    }
+
+    pub fn error(&self, part: &str, expected: impl Into<String>) -> ParseError {
+        ParseError::new(self.number, self.column(part), expected).found(part)
+    }
+
+    pub fn error_at(&self, column: usize, expected: impl Into<String>) -> ParseError {
+        let found = self.text.chars().nth(column - 1).map_or(String::new(), String::from);
+        ParseError::new(self.number, column, expected).found(&found)
+    }
+
+    // points just past the last character
+    pub fn end_error(&self, expected: impl Into<String>) -> ParseError {
+        self.error(&self.text[self.text.len()..], expected)
+    }
+
+    pub fn number<T: FromStr>(&self, part: &'a str) -> Result<T, ParseError> {
+        part.parse().map_err(|_| self.error(part, "a number"))
+    }
+
+    // the rest of the line after prefix, e.g. the items after "  Starting items: "
+    pub fn after(&self, prefix: &str) -> Result<&'a str, ParseError> {
+        self.text.strip_prefix(prefix).ok_or_else(|| self.error(self.text, format!("'{}'", prefix.trim())))
+    }
+
+    // splits part at the first sep, which has to be there
+    pub fn split_once(&self, part: &'a str, sep: &str) -> Result<(&'a str, &'a str), ParseError> {
+        part.split_once(sep).ok_or_else(|| self.error(&part[part.len()..], format!("'{}'", sep)))
+    }
+
+    // the whitespace separated words of the line, there have to be exactly N of them
+    pub fn words<const N: usize>(&self) -> Result<[&'a str; N], ParseError> {
+        let mut words = self.text.split_whitespace();
+        let mut result = [""; N];
+        for word in result.iter_mut() {
+            *word = words.next().ok_or_else(|| self.end_error(format!("{} words", N)))?;
+        }
+        match words.next() {
+            Some(extra) => Err(self.error(extra, "the end of the line")),
+            None => Ok(result),
+        }
+    }
+}
+
+#[test]
+fn test_line() {
+    let line = lines("1000\n  Operation: new = old * 19\nabc").nth(1).unwrap();
+    assert_eq!(line.number, 2);
+
+    let rest = line.after("  Operation: new = ").unwrap();
+    assert_eq!(line.column(rest), 20);
+    assert_eq!(line.words::<6>().unwrap()[4], "*");
+    assert_eq!(line.number::<u64>(&rest[6..]), Ok(19));
+
+    let err = line.number::<u64>(&rest[..3]).unwrap_err();
+    assert_eq!(err.to_string(), "line 2, column 20: expected a number, found 'old'");
+    assert_eq!(line.split_once(rest, ",").unwrap_err().to_string(), "line 2, column 28: expected ',', found end of line");
+    assert!(line.words::<5>().is_err());
+    assert!(line.words::<7>().is_err());
+    assert!(line.after("  Test:").is_err());
+}
+
+#[test]
+fn test_parse_error() {
+    assert_eq!(ParseError::new(3, 1, "a shape").found("Q").in_day(2).to_string(), "day 2, line 3, column 1: expected a shape, found 'Q'");
+    assert_eq!(ParseError::end_of_input("a\nb\n", "a monkey").to_string(), "line 3, column 1: expected a monkey, found end of input");
+    assert_eq!(lines("ab").next().unwrap().error_at(2, "'a'").to_string(), "line 1, column 2: expected 'a', found 'b'");
+
+    let err = ParseError::new(2, 6, "a number").found("x");
+    assert_eq!(err.diagnostic("addx 3\naddx x\n"), "line 2, column 6: expected a number, found 'x'\n 2 | addx x\n   |      ^");
+    assert_eq!(ParseError::end_of_input("noop\n", "a line").diagnostic("noop\n"), "line 2, column 1: expected a line, found end of input");
+}
@@ -0,0 +1,468 @@
+// frames of the grid simulations, played back in the terminal with colors or written as PPM images
+// or an animated GIF. a frame holds characters like the days' text renderings and a palette colors them
+use std::{collections::HashMap, io, time::Duration};
+
+use crate::error::ParseError;
+use crate::{d12, d14, d17, d22, d23, d24};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Color(pub u8, pub u8, pub u8);
+
+pub type Palette = fn(char) -> Color;
+
+// a grid of characters, placed at (top, left) so that frames of a growing state line up
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Frame {
+    pub top: i64,
+    pub left: i64,
+    width: usize,
+    height: usize,
+    cells: Vec<char>,
+}
+
+impl Frame {
+    pub fn new(width: usize, height: usize, fill: char) -> Self {
+        Self { top: 0, left: 0, width, height, cells: vec![fill; width * height] }
+    }
+
+    // a row per line, shorter lines are filled up with spaces
+    pub fn from_text(text: &str) -> Self {
+        let width = text.lines().map(|line| line.chars().count()).max().unwrap_or(0);
+        let mut frame = Self::new(width, text.lines().count(), ' ');
+        for (row, line) in text.lines().enumerate() {
+            for (col, c) in line.chars().enumerate() {
+                frame.set(row, col, c);
+            }
+        }
+        frame
+    }
+
+    pub fn at(self, top: i64, left: i64) -> Self {
+        Self { top, left, ..self }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn get(&self, row: usize, col: usize) -> Option<char> {
+        (row < self.height && col < self.width).then(|| self.cells[row * self.width + col])
+    }
+
+    // cells outside of the frame are left out
+    pub fn set(&mut self, row: usize, col: usize, c: char) {
+        if row < self.height && col < self.width {
+            self.cells[row * self.width + col] = c;
+        }
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[char]> {
+        self.cells.chunks(self.width.max(1)).take(self.height)
+    }
+}
+
+// collects the frames of a simulation, which calls tick once per step it takes.
+// only every nth tick is rendered, up to a limit of frames
+pub struct Recorder {
+    every: usize,
+    limit: usize,
+    ticks: usize,
+    frames: Vec<Frame>,
+}
+
+impl Recorder {
+    pub fn new(every: usize, limit: usize) -> Self {
+        Self { every: every.max(1), limit, ticks: 0, frames: Vec::new() }
+    }
+
+    pub fn tick(&mut self, render: impl FnOnce() -> Frame) {
+        if !self.is_full() && self.ticks.is_multiple_of(self.every) {
+            self.frames.push(render());
+        }
+        self.ticks += 1;
+    }
+
+    // the state the simulation ended in, kept even if it isn't an nth tick or the recorder is full
+    pub fn last(&mut self, render: impl FnOnce() -> Frame) {
+        if self.is_full() {
+            self.frames.pop();
+        }
+        self.frames.push(render());
+    }
+
+    // the simulation can stop, no more frames are kept
+    pub fn is_full(&self) -> bool {
+        self.frames.len() >= self.limit
+    }
+
+    // the frames on canvases of the same size, covering all of them
+    pub fn finish(self, background: char) -> Vec<Frame> {
+        let top = self.frames.iter().map(|f| f.top).min().unwrap_or(0);
+        let left = self.frames.iter().map(|f| f.left).min().unwrap_or(0);
+        let bottom = self.frames.iter().map(|f| f.top + f.height as i64).max().unwrap_or(0);
+        let right = self.frames.iter().map(|f| f.left + f.width as i64).max().unwrap_or(0);
+
+        self.frames.iter()
+            .map(|frame| {
+                let mut canvas = Frame::new((right - left) as usize, (bottom - top) as usize, background).at(top, left);
+                for (row, cells) in frame.rows().enumerate() {
+                    for (col, c) in cells.iter().enumerate() {
+                        canvas.set((frame.top - top) as usize + row, (frame.left - left) as usize + col, *c);
+                    }
+                }
+                canvas
+            })
+            .collect()
+    }
+}
+
+pub struct Film {
+    pub day: u8,
+    pub background: char,
+    pub palette: Palette,
+    record: fn(&str, &mut Recorder) -> Result<(), ParseError>,
+}
+
+impl Film {
+    pub fn record(&self, input: &str, every: usize, limit: usize) -> Result<Vec<Frame>, ParseError> {
+        let mut recorder = Recorder::new(every, limit);
+        (self.record)(input, &mut recorder)?;
+        Ok(recorder.finish(self.background))
+    }
+}
+
+const BLACK: Color = Color(0, 0, 0);
+const WHITE: Color = Color(255, 255, 255);
+const GRAY: Color = Color(110, 110, 110);
+const RED: Color = Color(230, 50, 40);
+const SAND: Color = Color(230, 190, 90);
+const ICE: Color = Color(150, 200, 255);
+
+pub static FILMS: &[Film] = &[
+    Film {
+        day: 12,
+        background: ' ',
+        // lowercase heights are unexplored, uppercase ones reached, from dark to light. the start, the
+        // goal and the way between them are '@' and '*'
+        palette: |c| match c {
+            'a'..='z' => Color(20, 40 + (c as u8 - b'a') * 6, 20),
+            'A'..='Z' => Color(60 + (c as u8 - b'A') * 7, 60 + (c as u8 - b'A') * 7, 220),
+            '@' | '*' => RED,
+            _ => BLACK,
+        },
+        record: d12::record,
+    },
+    Film {
+        day: 14,
+        background: '.',
+        palette: |c| match c {
+            '#' => GRAY,
+            'o' => SAND,
+            '+' => RED,
+            _ => BLACK,
+        },
+        record: d14::record,
+    },
+    Film {
+        day: 17,
+        background: '.',
+        palette: |c| match c {
+            '#' => GRAY,
+            '@' => SAND,
+            '|' | '-' | '+' => WHITE,
+            _ => BLACK,
+        },
+        record: d17::record,
+    },
+    Film {
+        day: 22,
+        background: ' ',
+        palette: |c| match c {
+            '#' => GRAY,
+            '.' => Color(40, 40, 40),
+            '>' | 'v' | '<' | '^' => SAND,
+            '@' => RED,
+            _ => BLACK,
+        },
+        record: d22::record,
+    },
+    Film {
+        day: 23,
+        background: '.',
+        palette: |c| match c {
+            '#' => Color(80, 200, 80),
+            _ => BLACK,
+        },
+        record: d23::record,
+    },
+    Film {
+        day: 24,
+        background: ' ',
+        palette: |c| match c {
+            '#' => GRAY,
+            '>' | 'v' | '<' | '^' => ICE,
+            'E' => RED,
+            _ => BLACK,
+        },
+        record: d24::record,
+    },
+];
+
+pub fn for_day(day: u8) -> Option<&'static Film> {
+    FILMS.iter().find(|f| f.day == day)
+}
+
+// the frame in 24 bit terminal colors, the escape codes only change where the color does
+pub fn ansi(frame: &Frame, palette: Palette) -> String {
+    let mut ansi = String::new();
+    for row in frame.rows() {
+        let mut current = None;
+        for &c in row {
+            let color = palette(c);
+            if current != Some(color) {
+                ansi += &format!("\x1b[38;2;{};{};{}m", color.0, color.1, color.2);
+                current = Some(color);
+            }
+            ansi.push(c);
+        }
+        ansi += "\x1b[0m\n";
+    }
+    ansi
+}
+
+// draws the frames over each other at the top of the terminal
+pub fn play(frames: &[Frame], palette: Palette, fps: u32, out: &mut dyn io::Write) -> io::Result<()> {
+    write!(out, "\x1b[2J")?;
+    for frame in frames {
+        write!(out, "\x1b[H{}", ansi(frame, palette))?;
+        out.flush()?;
+        std::thread::sleep(Duration::from_secs_f64(1. / fps.max(1) as f64));
+    }
+    Ok(())
+}
+
+// a binary PPM image with a square of scale by scale pixels per cell
+pub fn ppm(frame: &Frame, palette: Palette, scale: usize) -> Vec<u8> {
+    let mut ppm = format!("P6\n{} {}\n255\n", frame.width * scale, frame.height * scale).into_bytes();
+    for row in frame.rows() {
+        let line = row.iter().flat_map(|c| {
+            let Color(r, g, b) = palette(*c);
+            [r, g, b].repeat(scale)
+        }).collect::<Vec<_>>();
+        for _ in 0..scale {
+            ppm.extend(&line);
+        }
+    }
+    ppm
+}
+
+// an animated GIF looping over the frames, which need to be of the same size.
+// fails if the frames are too large or use more than 256 colors
+pub fn gif(frames: &[Frame], palette: Palette, scale: usize, delay: Duration) -> Result<Vec<u8>, String> {
+    let first = frames.first().ok_or("there are no frames")?;
+    let (width, height) = (first.width * scale, first.height * scale);
+    if width > u16::MAX as usize || height > u16::MAX as usize || frames.iter().any(|f| (f.width, f.height) != (first.width, first.height)) {
+        return Err(format!("the frames have to be of the same size, up to {} pixels wide and high", u16::MAX));
+    }
+
+    let mut colors = Vec::new();
+    let mut indices = HashMap::new();
+    for c in frames.iter().flat_map(|f| &f.cells) {
+        let color = palette(*c);
+        if !indices.contains_key(c) {
+            let index = colors.iter().position(|other| *other == color).unwrap_or_else(|| {
+                colors.push(color);
+                colors.len() - 1
+            });
+            indices.insert(*c, index as u8);
+        }
+    }
+    if colors.len() > 256 {
+        return Err(format!("the frames use {} colors, a GIF has at most 256", colors.len()));
+    }
+    // the color table has 2^(bits) entries, with at least 4 of them
+    let bits = (colors.len().max(4) - 1).ilog2() as u8 + 1;
+    colors.resize(1 << bits, BLACK);
+
+    let mut gif = b"GIF89a".to_vec();
+    gif.extend((width as u16).to_le_bytes());
+    gif.extend((height as u16).to_le_bytes());
+    gif.extend([0xf0 | (bits - 1), 0, 0]);
+    gif.extend(colors.iter().flat_map(|Color(r, g, b)| [*r, *g, *b]));
+    // loop forever
+    gif.extend(b"\x21\xff\x0bNETSCAPE2.0\x03\x01\x00\x00\x00");
+
+    let delay = (delay.as_millis() / 10).min(u16::MAX as u128) as u16;
+    for frame in frames {
+        gif.extend([0x21, 0xf9, 0x04, 0x04]);
+        gif.extend(delay.to_le_bytes());
+        gif.extend([0, 0]);
+        gif.push(0x2c);
+        gif.extend([0, 0, 0, 0]);
+        gif.extend((width as u16).to_le_bytes());
+        gif.extend((height as u16).to_le_bytes());
+        gif.push(0);
+
+        let pixels = frame.rows()
+            .flat_map(|row| {
+                let line = row.iter().flat_map(|c| std::iter::repeat_n(indices[c], scale)).collect::<Vec<_>>();
+                line.repeat(scale)
+            })
+            .collect::<Vec<_>>();
+        gif.push(bits);
+        for block in lzw(&pixels, bits).chunks(255) {
+            gif.push(block.len() as u8);
+            gif.extend(block);
+        }
+        gif.push(0);
+    }
+    gif.push(0x3b);
+    Ok(gif)
+}
+
+// the variable width LZW compression of GIF images, with codes of up to 12 bits
+fn lzw(indices: &[u8], min_bits: u8) -> Vec<u8> {
+    let clear = 1_u16 << min_bits;
+    let end = clear + 1;
+    let mut bits = Bits::default();
+    let mut table = HashMap::<(u16, u8), u16>::new();
+    let (mut width, mut next) = (min_bits + 1, end);
+    bits.write(clear, width);
+
+    let Some((&first, rest)) = indices.split_first() else {
+        bits.write(end, width);
+        return bits.finish();
+    };
+    let mut code = first as u16;
+    for &index in rest {
+        if let Some(&longer) = table.get(&(code, index)) {
+            code = longer;
+            continue;
+        }
+        bits.write(code, width);
+        // the decoder adds a code after reading each but the first one and widens the codes once they run out
+        next += 1;
+        if next == 1 << width {
+            width += 1;
+        }
+        if next == 4095 {
+            bits.write(clear, width);
+            table.clear();
+            (width, next) = (min_bits + 1, end);
+        } else {
+            table.insert((code, index), next);
+        }
+        code = index as u16;
+    }
+    bits.write(code, width);
+    if next + 1 == 1 << width {
+        width += 1;
+    }
+    bits.write(end, width);
+    bits.finish()
+}
+
+// codes packed into bytes, lowest bit first
+#[derive(Default)]
+struct Bits {
+    bytes: Vec<u8>,
+    buffer: u32,
+    buffered: u8,
+}
+
+impl Bits {
+    fn write(&mut self, code: u16, width: u8) {
+        self.buffer |= (code as u32) << self.buffered;
+        self.buffered += width;
+        while self.buffered >= 8 {
+            self.bytes.push(self.buffer as u8);
+            self.buffer >>= 8;
+            self.buffered -= 8;
+        }
+    }
+
+    fn finish(mut self) -> Vec<u8> {
+        if self.buffered > 0 {
+            self.bytes.push(self.buffer as u8);
+        }
+        self.bytes
+    }
+}
+
+// decodes what lzw encoded, the textbook way
+#[cfg(test)]
+fn unlzw(data: &[u8], min_bits: u8) -> Vec<u8> {
+    let clear = 1 << min_bits;
+    let mut bits = data.iter().flat_map(|byte| (0..8).map(move |i| (byte >> i) & 1));
+    let mut read = |width: u8| (0..width).map(|i| (bits.next().unwrap() as usize) << i).sum::<usize>();
+
+    let initial = (0..clear + 2).map(|i| vec![i as u8]).collect::<Vec<_>>();
+    let (mut table, mut width, mut prev) = (initial.clone(), min_bits + 1, None::<Vec<u8>>);
+    let mut out = Vec::new();
+    loop {
+        let code = read(width);
+        if code == clear {
+            (table, width, prev) = (initial.clone(), min_bits + 1, None);
+            continue;
+        }
+        if code == clear + 1 {
+            return out;
+        }
+        let entry = match table.get(code) {
+            Some(entry) => entry.clone(),
+            None => {
+                let prev = prev.clone().unwrap();
+                [prev.clone(), vec![prev[0]]].concat()
+            },
+        };
+        if let Some(prev) = prev {
+            if table.len() < 4096 {
+                table.push([prev, vec![entry[0]]].concat());
+            }
+        }
+        out.extend(&entry);
+        prev = Some(entry);
+        if table.len() == 1 << width && width < 12 {
+            width += 1;
+        }
+    }
+}
+
+#[test]
+fn test_lzw() {
+    let mut rng = crate::generate::Rng::new(3);
+    for (len, colors) in [(0, 4), (1, 4), (10, 2), (1000, 4), (20_000, 16), (100_000, 3)] {
+        let indices = (0..len).map(|i| if i % 7 < 4 { 0 } else { rng.below(colors) as u8 }).collect::<Vec<_>>();
+        let min_bits = (colors.max(4) - 1).ilog2() as u8 + 1;
+        assert_eq!(unlzw(&lzw(&indices, min_bits), min_bits), indices, "{} indices of {} colors", len, colors);
+    }
+}
+
+#[test]
+fn test_frames() {
+    let mut recorder = Recorder::new(2, 2);
+    for i in 0..10 {
+        recorder.tick(|| Frame::from_text(&"#".repeat(i + 1)).at(-(i as i64), 0));
+    }
+    assert!(recorder.is_full());
+    let frames = recorder.finish('.');
+    let text = |frame: &Frame| frame.rows().map(|row| row.iter().collect::<String>()).collect::<Vec<_>>();
+    assert_eq!(frames.len(), 2);
+    assert_eq!(text(&frames[0]), vec!["...", "...", "#.."]);
+    assert_eq!(text(&frames[1]), vec!["###", "...", "..."]);
+
+    let palette: Palette = |c| if c == '#' { WHITE } else { BLACK };
+    let frame = Frame::from_text("#.\n");
+    assert_eq!(ansi(&frame, palette), "\x1b[38;2;255;255;255m#\x1b[38;2;0;0;0m.\x1b[0m\n");
+    assert_eq!(ppm(&frame, palette, 1), b"P6\n2 1\n255\n\xff\xff\xff\0\0\0".to_vec());
+    assert_eq!(ppm(&frame, palette, 2).len(), "P6\n4 2\n255\n".len() + 4 * 2 * 3);
+
+    let gif = gif(&frames, palette, 2, Duration::from_millis(100)).unwrap();
+    assert!(gif.starts_with(b"GIF89a\x06\x00\x06\x00"));
+    assert!(gif.ends_with(&[0x3b]));
+    assert!(super::frames::gif(&[frame, frames[0].clone()], palette, 1, Duration::ZERO).is_err());
+}
@@ -0,0 +1,352 @@
+// random puzzle inputs in the format the days' parsers expect, for measuring how the solutions
+// scale and for finding inputs they crash on. the same seed always gives the same input
+use std::{cmp::Ordering, collections::HashSet};
+use std::fmt::Write;
+
+// splitmix64, good enough for inputs and the same on every platform
+#[derive(Debug, Clone)]
+pub struct Rng(u64);
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Self(seed)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    // a number from the inclusive range, which must not be empty
+    pub fn range(&mut self, from: i64, to: i64) -> i64 {
+        let span = to.abs_diff(from) + 1;
+        from.wrapping_add((self.next_u64() % span) as i64)
+    }
+
+    pub fn below(&mut self, n: usize) -> usize {
+        (self.next_u64() % n as u64) as usize
+    }
+
+    pub fn chance(&mut self, percent: u64) -> bool {
+        self.next_u64() % 100 < percent
+    }
+
+    pub fn pick<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.below(items.len())]
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.below(i + 1));
+        }
+    }
+}
+
+pub struct Generator {
+    pub day: u8,
+    // what the size counts and its default, which is about the size of a real input
+    pub size: &'static str,
+    pub default_size: usize,
+    generate: fn(&mut Rng, usize) -> String,
+}
+
+impl Generator {
+    pub fn generate(&self, seed: u64, size: usize) -> String {
+        (self.generate)(&mut Rng::new(seed), size)
+    }
+}
+
+pub static GENERATORS: &[Generator] = &[
+    Generator { day: 7, size: "directories", default_size: 200, generate: terminal_output },
+    Generator { day: 8, size: "rows and columns", default_size: 99, generate: tree_grid },
+    Generator { day: 9, size: "moves", default_size: 2000, generate: rope_moves },
+    Generator { day: 13, size: "pairs of packets", default_size: 150, generate: packet_pairs },
+    Generator { day: 18, size: "cubes", default_size: 2800, generate: cube_cloud },
+    Generator { day: 20, size: "numbers", default_size: 5000, generate: number_ring },
+    Generator { day: 21, size: "monkeys", default_size: 2301, generate: monkey_tree },
+    Generator { day: 23, size: "rows and columns", default_size: 70, generate: elf_field },
+];
+
+pub fn for_day(day: u8) -> Option<&'static Generator> {
+    GENERATORS.iter().find(|g| g.day == day)
+}
+
+// "$ cd a", "$ ls", "dir e" and "29116 f.txt", every directory is listed once right after changing into it.
+// the files add up to between 41 and 69 million so that part 2 has something to delete
+fn terminal_output(rng: &mut Rng, size: usize) -> String {
+    let mut children = vec![Vec::new(); size.max(1)];
+    for dir in 1..children.len() {
+        let parent = rng.below(dir);
+        children[parent].push(dir);
+    }
+    let mut names = HashSet::new();
+    let names = (0..children.len())
+        .map(|dir| match dir {
+            0 => "/".to_string(),
+            _ => loop {
+                let name = letters(rng);
+                if names.insert(name.clone()) {
+                    break name;
+                }
+            },
+        })
+        .collect::<Vec<_>>();
+    // the root always has a file, the sizes are weights scaled to the disk usage below
+    let files = (0..children.len())
+        .map(|dir| (0..rng.range((dir == 0) as i64, 4)).map(|_| rng.range(1, 1000)).collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+
+    let total = files.iter().flatten().sum::<i64>();
+    let disk = rng.range(41_000_000, 69_000_000);
+    let mut input = String::new();
+    let mut stack = vec![Some(0)];
+    while let Some(next) = stack.pop() {
+        let Some(dir) = next else {
+            input += "$ cd ..\n";
+            continue;
+        };
+        let _ = write!(input, "$ cd {}\n$ ls\n", names[dir]);
+        for &child in &children[dir] {
+            let _ = writeln!(input, "dir {}", names[child]);
+        }
+        for weight in &files[dir] {
+            let name = letters(rng);
+            let _ = writeln!(input, "{} {}.{}", (weight * disk / total).max(1), name, rng.pick(&["txt", "dat", "log"]));
+        }
+        stack.extend(children[dir].iter().rev().flat_map(|&child| [None, Some(child)]));
+    }
+    // like the real ones, the output ends in the last directory
+    while let Some(rest) = input.strip_suffix("$ cd ..\n") {
+        input.truncate(rest.len());
+    }
+    input
+}
+
+// a name of one to eight lowercase letters
+fn letters(rng: &mut Rng) -> String {
+    (0..rng.range(1, 8)).map(|_| (b'a' + rng.below(26) as u8) as char).collect()
+}
+
+// digits from 0 to 9 in a square
+fn tree_grid(rng: &mut Rng, size: usize) -> String {
+    let size = size.max(3);
+    (0..size).map(|_| (0..size).map(|_| (b'0' + rng.below(10) as u8) as char).collect::<String>() + "\n").collect()
+}
+
+// "R 4", moves of 1 to 20 steps
+fn rope_moves(rng: &mut Rng, size: usize) -> String {
+    (0..size).map(|_| format!("{} {}\n", rng.pick(&["U", "D", "L", "R"]), rng.range(1, 20))).collect()
+}
+
+// "[1,[2,[]],10]", the right packet is often a copy of the left one with a single number changed
+// so that comparing them has to go deep
+fn packet_pairs(rng: &mut Rng, size: usize) -> String {
+    let mut input = String::new();
+    for i in 0..size {
+        let lhs = packet(rng, 0);
+        let numbers = lhs.match_indices(|c: char| c.is_ascii_digit()).map(|(i, _)| i).collect::<Vec<_>>();
+        let rhs = match numbers.is_empty() || rng.chance(30) {
+            true => packet(rng, 0),
+            false => {
+                let at = *rng.pick(&numbers);
+                let len = lhs[at..].find(|c: char| !c.is_ascii_digit()).unwrap_or(0);
+                format!("{}{}{}", &lhs[..at], rng.range(0, 10), &lhs[at + len..])
+            },
+        };
+        if i > 0 {
+            input.push('\n');
+        }
+        let _ = write!(input, "{}\n{}\n", lhs, rhs);
+    }
+    input
+}
+
+fn packet(rng: &mut Rng, depth: usize) -> String {
+    let items = (0..rng.range(0, 4))
+        .map(|_| match depth < 4 && rng.chance(30) {
+            true => packet(rng, depth + 1),
+            false => rng.range(0, 10).to_string(),
+        })
+        .collect::<Vec<_>>();
+    format!("[{}]", items.join(","))
+}
+
+// "2,2,2", distinct cubes filling about 40% of a box so that they enclose some air
+fn cube_cloud(rng: &mut Rng, size: usize) -> String {
+    let side = (1..).find(|side| side * side * side * 2 >= size * 5).unwrap_or(1) as i64;
+    let mut cubes = HashSet::new();
+    let mut input = String::new();
+    while cubes.len() < size {
+        let cube = (rng.range(0, side - 1), rng.range(0, side - 1), rng.range(0, side - 1));
+        if cubes.insert(cube) {
+            let _ = writeln!(input, "{},{},{}", cube.0, cube.1, cube.2);
+        }
+    }
+    input
+}
+
+// numbers from -10000 to 10000 with exactly one 0, duplicates included
+fn number_ring(rng: &mut Rng, size: usize) -> String {
+    let zero = rng.below(size.max(1));
+    (0..size.max(1))
+        .map(|i| match i == zero {
+            true => "0\n".to_string(),
+            false => format!("{}\n", rng.range(1, 10000) * rng.pick(&[-1, 1])),
+        })
+        .collect()
+}
+
+// "root: pppw + sjmn" and "dbpl: 5", a tree of monkeys with exact divisions. the sizes are odd,
+// every monkey yells a number or has two others below it
+fn monkey_tree(rng: &mut Rng, size: usize) -> String {
+    let mut tree = MonkeyTree { rng, names: HashSet::from(["root".to_string(), "humn".to_string()]), lines: Vec::new() };
+    let size = size.max(3) | 1;
+    let lhs_size = tree.split(size - 1);
+    // part 2 is answered by a humn yelling `expected`, part 1 uses another number
+    let expected = tree.rng.range(1, 5000);
+    let target = if lhs_size == 1 { expected } else { tree.rng.range(100, 100_000) };
+    let lhs = tree.monkey(lhs_size, target, Some(expected));
+    let rhs = tree.monkey(size - 1 - lhs_size, target, None);
+    tree.lines.push(format!("root: {} + {}", lhs, rhs));
+
+    let MonkeyTree { rng, mut lines, .. } = tree;
+    rng.shuffle(&mut lines);
+    lines.iter().map(|line| format!("{}\n", line)).collect()
+}
+
+struct MonkeyTree<'a> {
+    rng: &'a mut Rng,
+    names: HashSet<String>,
+    lines: Vec<String>,
+}
+
+impl MonkeyTree<'_> {
+    // keeps the tree about balanced so that the values stay within an i64
+    fn split(&mut self, size: usize) -> usize {
+        match size {
+            2 => 1,
+            size => self.rng.range(size as i64 / 4, size as i64 * 3 / 4) as usize | 1,
+        }
+    }
+
+    // four letters like the real ones while there are enough of them left
+    fn name(&mut self) -> String {
+        let len = if self.names.len() < 200_000 { 4 } else { 8 };
+        loop {
+            let name = (0..len).map(|_| (b'a' + self.rng.below(26) as u8) as char).collect::<String>();
+            if self.names.insert(name.clone()) {
+                return name;
+            }
+        }
+    }
+
+    // a monkey with `size` monkeys below it including itself that yells `value`, with `humn`
+    // somewhere below it if it yells its number
+    fn monkey(&mut self, size: usize, value: i64, humn: Option<i64>) -> String {
+        if size == 1 {
+            return match humn {
+                Some(expected) if expected == value => {
+                    let yelled = self.rng.range(1, 5000);
+                    self.lines.push(format!("humn: {}", yelled));
+                    "humn".to_string()
+                },
+                Some(_) => unreachable!("humn has to yell the expected value"),
+                None => {
+                    let name = self.name();
+                    self.lines.push(format!("{}: {}", name, value));
+                    name
+                },
+            };
+        }
+
+        let name = self.name();
+        let lhs_size = self.split(size - 1);
+        let rhs_size = size - 1 - lhs_size;
+        // the humn side can only be a leaf if it's humn itself yelling the expected value
+        let humn_left = self.rng.chance(50);
+        let humn_leaf = match humn {
+            Some(_) if humn_left => lhs_size == 1,
+            Some(_) => rhs_size == 1,
+            None => false,
+        };
+        let (op, lhs, rhs) = match humn {
+            Some(expected) if humn_leaf && humn_left => match value.cmp(&expected) {
+                Ordering::Greater => ('+', expected, value - expected),
+                Ordering::Less => ('-', expected, expected - value),
+                Ordering::Equal => ('*', expected, 1),
+            },
+            Some(expected) if humn_leaf && value > expected => ('+', value - expected, expected),
+            Some(expected) if humn_leaf => ('-', value + expected, expected),
+            _ => self.operands(value, humn.is_some() && !humn_left),
+        };
+
+        let lhs = self.monkey(lhs_size, lhs, humn.filter(|_| humn_left));
+        let rhs = self.monkey(rhs_size, rhs, humn.filter(|_| !humn_left));
+        self.lines.push(format!("{}: {} {} {}", name, lhs, op, rhs));
+        name
+    }
+
+    // lhs op rhs == value, all of them positive. a divisor with humn below it could become 0
+    // for the number humn yells in part 1
+    fn operands(&mut self, value: i64, humn_right: bool) -> (char, i64, i64) {
+        loop {
+            match self.rng.below(4) {
+                0 if value >= 2 => {
+                    let lhs = self.rng.range(1, value - 1);
+                    return ('+', lhs, value - lhs);
+                },
+                1 => {
+                    let rhs = self.rng.range(1, 100);
+                    return ('-', value + rhs, rhs);
+                },
+                2 => {
+                    let factor = (2..=9).filter(|f| value % f == 0).nth(self.rng.below(3)).unwrap_or(1);
+                    return ('*', value / factor, factor);
+                },
+                3 if !humn_right && value.abs() < 1_000_000_000 => {
+                    let divisor = self.rng.range(2, 5);
+                    return ('/', value * divisor, divisor);
+                },
+                _ => (),
+            }
+        }
+    }
+}
+
+// '#' for elves, about half of the ground is taken
+fn elf_field(rng: &mut Rng, size: usize) -> String {
+    let size = size.max(1);
+    let mut rows = (0..size)
+        .map(|_| (0..size).map(|_| if rng.chance(50) { '#' } else { '.' }).collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+    if !rows.iter().flatten().any(|&c| c == '#') {
+        rows[size / 2][size / 2] = '#';
+    }
+    rows.iter().map(|row| row.iter().collect::<String>() + "\n").collect()
+}
+
+#[test]
+fn test_rng() {
+    let mut rng = Rng::new(7);
+    let numbers = (0..1000).map(|_| rng.range(-3, 3)).collect::<Vec<_>>();
+    assert!(numbers.iter().all(|n| (-3..=3).contains(n)));
+    assert!((-3..=3).all(|n| numbers.contains(&n)));
+    assert_eq!(Rng::new(7).next_u64(), Rng::new(7).next_u64());
+    assert_ne!(Rng::new(7).next_u64(), Rng::new(8).next_u64());
+
+    let mut items = (0..10).collect::<Vec<_>>();
+    rng.shuffle(&mut items);
+    items.sort();
+    assert_eq!(items, (0..10).collect::<Vec<_>>());
+}
+
+#[test]
+fn test_monkey_tree() {
+    let input = monkey_tree(&mut Rng::new(1), 101);
+    assert_eq!(input.lines().count(), 101);
+    assert_eq!(input.lines().filter(|line| line.starts_with("humn: ")).count(), 1);
+    assert_eq!(input.lines().filter(|line| line.starts_with("root: ")).count(), 1);
+}
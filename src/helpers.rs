@@ -1,6 +1,11 @@
-use std::fmt::Write;
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+use std::fmt::Display;
+use std::hash::Hash;
+use std::ops::{Add, Index, IndexMut};
 
-pub(crate) fn gcd<T>(mut a: T, mut b: T) -> T 
+use crate::error::{self, ParseError};
+
+pub fn gcd<T>(mut a: T, mut b: T) -> T 
 where T: std::ops::Rem<Output = T> + PartialOrd + Default + Copy
 {
     if a < b {
@@ -16,6 +21,172 @@ where T: std::ops::Rem<Output = T> + PartialOrd + Default + Copy
     a
 }
 
+// the integer types the number theory below works with. it calculates in i128, which holds all of
+// them, and only results that fit back into the type are returned
+pub trait Integer: Copy + Ord + std::fmt::Debug {
+    fn to_i128(self) -> i128;
+    fn from_i128(n: i128) -> Option<Self>;
+}
+
+macro_rules! impl_integer {
+    ($($tp:ty),*) => ($(
+        impl Integer for $tp {
+            fn to_i128(self) -> i128 {
+                self as i128
+            }
+
+            fn from_i128(n: i128) -> Option<Self> {
+                Self::try_from(n).ok()
+            }
+        }
+    )*)
+}
+
+impl_integer!(i32, i64, isize, u32, u64, usize);
+
+// 0 if either is 0, panics if it doesn't fit
+pub fn lcm<T: Integer>(a: T, b: T) -> T {
+    checked_lcm(a, b).unwrap_or_else(|| panic!("the lcm of {:?} and {:?} overflows", a, b))
+}
+
+pub fn checked_lcm<T: Integer>(a: T, b: T) -> Option<T> {
+    let (a, b) = (a.to_i128(), b.to_i128());
+    if a == 0 || b == 0 {
+        return T::from_i128(0);
+    }
+    T::from_i128((a / gcd_i128(a, b)).checked_mul(b)?.abs())
+}
+
+fn gcd_i128(a: i128, b: i128) -> i128 {
+    ext_gcd_i128(a, b).0
+}
+
+// (g, x, y) with a * x + b * y = g, where g is the greatest common divisor and not negative
+fn ext_gcd_i128(a: i128, b: i128) -> (i128, i128, i128) {
+    let (mut r0, mut r1, mut x0, mut x1, mut y0, mut y1) = (a, b, 1, 0, 0, 1);
+    while r1 != 0 {
+        let q = r0 / r1;
+        (r0, r1) = (r1, r0 - q * r1);
+        (x0, x1) = (x1, x0 - q * x1);
+        (y0, y1) = (y1, y0 - q * y1);
+    }
+    if r0 < 0 { (-r0, -x0, -y0) } else { (r0, x0, y0) }
+}
+
+// the greatest common divisor g and x, y with a * x + b * y = g. the coefficients can be negative
+// even for unsigned types, so they are i128
+pub fn ext_gcd<T: Integer>(a: T, b: T) -> (T, i128, i128) {
+    let (g, x, y) = ext_gcd_i128(a.to_i128(), b.to_i128());
+    (T::from_i128(g).unwrap_or_else(|| panic!("the gcd of {:?} and {:?} overflows", a, b)), x, y)
+}
+
+// x in 0..m with a * x = 1 modulo m, None unless a and m are coprime and m is positive
+pub fn mod_inverse<T: Integer>(a: T, m: T) -> Option<T> {
+    let m = m.to_i128();
+    if m <= 0 {
+        return None;
+    }
+    let (g, x, _) = ext_gcd_i128(a.to_i128().rem_euclid(m), m);
+    (g == 1).then(|| T::from_i128(x.rem_euclid(m)))?
+}
+
+// a * b modulo m for a and b in 0..m, in u128 so that moduli up to u64::MAX don't overflow
+fn mul_mod(a: i128, b: i128, m: i128) -> i128 {
+    ((a as u128 * b as u128) % m as u128) as i128
+}
+
+// base to the power of exp modulo m, in 0..m. panics unless m is positive and exp isn't negative
+pub fn mod_pow<T: Integer>(base: T, exp: T, m: T) -> T {
+    let (m, mut exp) = (m.to_i128(), exp.to_i128());
+    assert!(m > 0 && exp >= 0, "mod_pow needs a positive modulus and an exponent of at least 0");
+    let (mut base, mut result) = (base.to_i128().rem_euclid(m), 1 % m);
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = mul_mod(result, base, m);
+        }
+        base = mul_mod(base, base, m);
+        exp >>= 1;
+    }
+    T::from_i128(result).unwrap()
+}
+
+// the x in 0..M that leaves every remainder r when divided by its modulus m, given as (r, m), and M,
+// the lcm of the moduli. the moduli needn't be coprime, None if there is no such x. panics if M
+// doesn't fit
+pub fn crt<T: Integer>(congruences: &[(T, T)]) -> Option<(T, T)> {
+    try_crt(congruences).unwrap_or_else(|| panic!("the lcm of the moduli of {:?} overflows", congruences))
+}
+
+// None if there is no solution or M doesn't fit
+pub fn checked_crt<T: Integer>(congruences: &[(T, T)]) -> Option<(T, T)> {
+    try_crt(congruences).flatten()
+}
+
+// None on overflow, Some(None) without a solution
+fn try_crt<T: Integer>(congruences: &[(T, T)]) -> Option<Option<(T, T)>> {
+    let (mut x, mut m) = (0_i128, 1_i128);
+    for (r, modulus) in congruences {
+        let modulus = modulus.to_i128();
+        assert!(modulus > 0, "crt needs positive moduli, not {}", modulus);
+        let r = r.to_i128().rem_euclid(modulus);
+        let g = gcd_i128(m, modulus);
+        let diff = (r - x).rem_euclid(modulus);
+        if diff % g != 0 {
+            return Some(None);
+        }
+        // x + m * t solves both for t = diff / g * inverse of m / g, modulo modulus / g
+        let step = modulus / g;
+        let inverse = ext_gcd_i128((m / g).rem_euclid(step), step).1.rem_euclid(step);
+        let t = mul_mod((diff / g).rem_euclid(step), inverse, step);
+        let combined = m.checked_mul(step)?;
+        T::from_i128(combined)?;
+        x = (x + m * t).rem_euclid(combined);
+        m = combined;
+    }
+    Some(Some((T::from_i128(x)?, T::from_i128(m)?)))
+}
+
+#[test]
+fn test_lcm() {
+    assert_eq!(lcm(4_u64, 6), 12);
+    assert_eq!(lcm(-4_i64, 6), 12);
+    assert_eq!(lcm(0_usize, 6), 0);
+    assert_eq!(checked_lcm(u64::MAX, u64::MAX - 1), None);
+    assert_eq!(checked_lcm(u32::MAX, 1), Some(u32::MAX));
+    assert_eq!([2_u64, 3, 5, 7, 11, 13, 17, 19].into_iter().fold(1, lcm), 9699690);
+}
+
+#[test]
+fn test_ext_gcd() {
+    assert_eq!(ext_gcd(240_i64, 46), (2, -9, 47));
+    assert_eq!(ext_gcd(46_usize, 240), (2, 47, -9));
+    assert_eq!(ext_gcd(0_u64, 5), (5, 0, 1));
+    assert_eq!(ext_gcd(-6_i64, -4).0, 2);
+    assert_eq!(mod_inverse(3_i64, 11), Some(4));
+    assert_eq!(mod_inverse(-3_i64, 11), Some(7));
+    assert_eq!(mod_inverse(6_u64, 9), None);
+    assert_eq!(mod_inverse(u64::MAX - 1, u64::MAX), Some(u64::MAX - 1));
+}
+
+#[test]
+fn test_mod_pow() {
+    assert_eq!(mod_pow(4_u64, 13, 497), 445);
+    assert_eq!(mod_pow(-2_i64, 3, 5), 2);
+    assert_eq!(mod_pow(7_usize, 0, 1), 0);
+    // (-1)^2 modulo a modulus near the top of u64
+    assert_eq!(mod_pow(u64::MAX - 1, 2, u64::MAX), 1);
+}
+
+#[test]
+fn test_crt() {
+    assert_eq!(crt(&[(2_u64, 3), (3, 5), (2, 7)]), Some((23, 105)));
+    assert_eq!(crt(&[(-1_i64, 4), (3, 6)]), Some((3, 12)));
+    assert_eq!(crt(&[(1_usize, 4), (2, 6)]), None);
+    assert_eq!(crt::<u32>(&[]), Some((0, 1)));
+    assert_eq!(checked_crt(&[(1_u32, 65537), (2, 65539)]), None);
+    assert_eq!(checked_crt(&[(1_u64, 65537), (2, 65539)]).map(|(x, m)| (x % 65537, x % 65539, m)), Some((1, 2, 65537 * 65539)));
+}
+
 pub trait Complex {
     fn c_add(&self, other: Self) -> Self;
     fn c_mul(&self, other: Self) -> Self;
@@ -42,89 +213,896 @@ where T: std::ops::Add<Output = T> + std::ops::Sub<Output = T> + std::ops::Mul<O
     }
 }
 
-pub(crate) trait BitMap {
-    const DIM: usize;
-    const N_WIDTH: usize;
+// a grid of bits sized at runtime. every row is a run of u64 words with column 0 in the lowest bit
+// of its first word, the bits past the last column are always 0. cells outside the grid read as
+// unset and setting them does nothing
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BitGrid {
+    width: usize,
+    height: usize,
+    words: Vec<u64>,
+}
+
+impl BitGrid {
+    pub fn new(width: usize, height: usize) -> Self {
+        Self { width, height, words: vec![0; width.div_ceil(64) * height] }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    // the number of words in a row
+    fn stride(&self) -> usize {
+        self.width.div_ceil(64)
+    }
+
+    // (word index, bit), None outside the grid
+    fn idx(&self, row: usize, col: usize) -> Option<(usize, u64)> {
+        (row < self.height && col < self.width).then(|| (row * self.stride() + col / 64, 1 << (col % 64)))
+    }
+
+    pub fn get(&self, row: usize, col: usize) -> bool {
+        self.idx(row, col).is_some_and(|(i, bit)| self.words[i] & bit != 0)
+    }
+
+    pub fn set(&mut self, row: usize, col: usize) {
+        if let Some((i, bit)) = self.idx(row, col) {
+            self.words[i] |= bit;
+        }
+    }
+
+    pub fn clear(&mut self, row: usize, col: usize) {
+        if let Some((i, bit)) = self.idx(row, col) {
+            self.words[i] &= !bit;
+        }
+    }
+
+    pub fn toggle(&mut self, row: usize, col: usize) {
+        if let Some((i, bit)) = self.idx(row, col) {
+            self.words[i] ^= bit;
+        }
+    }
+
+    pub fn count(&self) -> usize {
+        self.words.iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.iter().all(|w| *w == 0)
+    }
+
+    // the words of a row, column 0 in the lowest bit of the first one
+    pub fn row(&self, row: usize) -> &[u64] {
+        &self.words[row * self.stride()..(row + 1) * self.stride()]
+    }
 
-    fn set_bit(&mut self, row: usize, col: usize);
-    fn is_set(&self, row: usize, col: usize) -> bool;
-    fn idx(row: usize, col: usize) -> (usize, u32); // (array index, bit index)
-    fn count_bits(&self) -> usize;
-    fn print(&self);
+    // the words of a column, row 0 in the lowest bit of the first one
+    pub fn col(&self, col: usize) -> Vec<u64> {
+        let mut words = vec![0; self.height.div_ceil(64)];
+        for row in (0..self.height).filter(|row| self.get(*row, col)) {
+            words[row / 64] |= 1 << (row % 64);
+        }
+        words
+    }
+
+    pub fn count_row(&self, row: usize) -> usize {
+        self.row(row).iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    pub fn count_col(&self, col: usize) -> usize {
+        self.col(col).iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    // every set cell moved by the given rows and columns, the ones moved off the grid are lost
+    pub fn shifted(&self, rows: isize, cols: isize) -> Self {
+        let mut shifted = Self::new(self.width, self.height);
+        let stride = self.stride();
+        let (words, bits) = (cols.unsigned_abs() / 64, (cols.unsigned_abs() % 64) as u32);
+
+        for row in 0..self.height {
+            let Some(to) = row.checked_add_signed(rows).filter(|to| *to < self.height) else { continue };
+            let src = self.row(row);
+            let dest = &mut shifted.words[to * stride..(to + 1) * stride];
+            for (i, word) in dest.iter_mut().enumerate() {
+                // a word takes the bits of one or two words of the source row
+                let (near, far) = match (cols >= 0, bits) {
+                    (true, 0) => (i.checked_sub(words).map(|j| src[j]), None),
+                    (true, _) => (i.checked_sub(words).map(|j| src[j] << bits), i.checked_sub(words + 1).map(|j| src[j] >> (64 - bits))),
+                    (false, 0) => (src.get(i + words).copied(), None),
+                    (false, _) => (src.get(i + words).map(|w| w >> bits), src.get(i + words + 1).map(|w| w << (64 - bits))),
+                };
+                *word = near.unwrap_or(0) | far.unwrap_or(0);
+            }
+        }
+
+        shifted.mask();
+        shifted
+    }
+
+    // rows become columns
+    pub fn transpose(&self) -> Self {
+        let mut transposed = Self::new(self.height, self.width);
+        for (row, col) in self.iter() {
+            transposed.set(col, row);
+        }
+        transposed
+    }
+
+    // the set cells as (row, col), row by row
+    pub fn iter(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        let stride = self.stride();
+        self.words.iter().enumerate().flat_map(move |(i, word)| {
+            let first = (i % stride) * 64;
+            std::iter::successors(Some(*word).filter(|w| *w != 0), |w| Some(w & (w - 1)).filter(|w| *w != 0))
+                .map(move |w| (i / stride, first + w.trailing_zeros() as usize))
+        })
+    }
+
+    // the set cells as '#' and the others as '.', cropped to the rows and columns with set cells
+    pub fn render(&self) -> String {
+        let (Some(top), Some(bottom)) = (self.iter().map(|(row, _)| row).min(), self.iter().map(|(row, _)| row).max()) else {
+            return String::new();
+        };
+        let left = self.iter().map(|(_, col)| col).min().unwrap_or(0);
+        let right = self.iter().map(|(_, col)| col).max().unwrap_or(0);
+        (top..=bottom)
+            .map(|row| (left..=right).map(|col| if self.get(row, col) { '#' } else { '.' }).collect::<String>() + "\n")
+            .collect()
+    }
+
+    // clears the bits past the last column
+    fn mask(&mut self) {
+        if self.width.is_multiple_of(64) {
+            return;
+        }
+        let (stride, last) = (self.stride(), (1 << (self.width % 64)) - 1);
+        for row in self.words.chunks_mut(stride) {
+            row[stride - 1] &= last;
+        }
+    }
 }
 
-macro_rules! impl_bm {
-    ($tp:ty, $dim:expr) => (
-        impl BitMap for [$tp; $dim * ($dim / (std::mem::size_of::<$tp>() * 8))] {
-            const N_WIDTH: usize = std::mem::size_of::<$tp>() * 8;
-            const DIM: usize = $dim;
+impl std::ops::Not for &BitGrid {
+    type Output = BitGrid;
 
-            fn set_bit(&mut self, row: usize, col: usize) {
-                // do nothing on out of bounds index
-                if row >= Self::DIM || col >= Self::DIM {
-                    return;
+    fn not(self) -> BitGrid {
+        let mut not = BitGrid { words: self.words.iter().map(|w| !w).collect(), ..*self };
+        not.mask();
+        not
+    }
+}
+
+// the grids have to be the same size
+macro_rules! impl_bit_op {
+    ($op:ident, $op_fn:ident, $assign:ident, $assign_fn:ident) => (
+        impl std::ops::$assign<&BitGrid> for BitGrid {
+            fn $assign_fn(&mut self, other: &BitGrid) {
+                assert_eq!((self.width, self.height), (other.width, other.height), "bit grids of different sizes");
+                for (word, other) in self.words.iter_mut().zip(&other.words) {
+                    std::ops::$assign::$assign_fn(word, other);
                 }
-                // println!("{}, {}", Self::N_WIDTH, Self::DIM);
-                let (arr_idx, bit_idx) = Self::idx(row, col);
-                let n = self.get_mut(arr_idx).unwrap();
-                let bit = (2 as $tp).pow(bit_idx);
-                *n |= bit;
             }
+        }
 
-            fn is_set(&self, row: usize, col: usize) -> bool {
-                if row >= Self::DIM || col >= Self::DIM {
-                    return false;
-                }
+        impl std::ops::$op<&BitGrid> for BitGrid {
+            type Output = BitGrid;
 
-                let (arr_idx, bit_idx) = Self::idx(row, col);
-                let n = *self.get(arr_idx).unwrap();
-                (n >> (Self::N_WIDTH - bit_idx as usize) as $tp) == 1
+            fn $op_fn(mut self, other: &BitGrid) -> BitGrid {
+                std::ops::$assign::$assign_fn(&mut self, other);
+                self
             }
-        
-            fn idx(row: usize, col: usize) -> (usize, u32) {
-                let idx = row * Self::DIM + col;
-                (idx / Self::N_WIDTH, (Self::N_WIDTH - (idx % Self::N_WIDTH) - 1) as u32)
-            }   
-        
-            fn count_bits(&self) -> usize {
-                let mut count = 0;
-                for mut n in *self {
-                    while n > 0 {
-                        if let 1 = n % 2 {
-                            count += 1;
-                        }
-                        n >>= 1;
-                    }
-                }
-                count
-            } 
-
-            fn print(&self) {
-                let mut string_repr = String::new();
-                for row in self.chunks(Self::DIM / Self::N_WIDTH) {
-                    for n in row {
-                        let n_repr = format!("{:0width$b}", n, width = Self::N_WIDTH).chars().map(|c| if c == '1' { '#' } else { '.' }).collect::<String>();
-                        let _ = write!(string_repr, "{}", n_repr);
-                    }
-                    string_repr.push('\n');
-                }
-                println!("{}", string_repr);
+        }
+
+        impl std::ops::$op<&BitGrid> for &BitGrid {
+            type Output = BitGrid;
+
+            fn $op_fn(self, other: &BitGrid) -> BitGrid {
+                std::ops::$op::$op_fn(self.clone(), other)
             }
         }
     )
 }
 
+impl_bit_op!(BitAnd, bitand, BitAndAssign, bitand_assign);
+impl_bit_op!(BitOr, bitor, BitOrAssign, bitor_assign);
+impl_bit_op!(BitXor, bitxor, BitXorAssign, bitxor_assign);
+
+#[test]
+fn test_bit_grid() {
+    let mut grid = BitGrid::new(200, 3);
+    grid.set(0, 0);
+    grid.set(0, 1);
+    grid.set(1, 127);
+    grid.set(2, 199);
+    grid.set(3, 0);
+    grid.set(0, 200);
+    assert!(grid.get(0, 1) && grid.get(1, 127) && grid.get(2, 199));
+    assert!(!grid.get(0, 2) && !grid.get(1, 126) && !grid.get(3, 0));
+    assert_eq!(grid.row(1), &[0, 1 << 63, 0, 0]);
+    assert_eq!(grid.count(), 4);
+
+    grid.clear(0, 0);
+    grid.toggle(0, 1);
+    grid.toggle(2, 5);
+    assert_eq!(grid.iter().collect::<Vec<_>>(), vec![(1, 127), (2, 5), (2, 199)]);
+    assert_eq!((grid.count_row(2), grid.count_col(127)), (2, 1));
+    assert_eq!(grid.col(199), vec![0b100]);
+    assert_eq!(grid.transpose().iter().collect::<Vec<_>>(), vec![(5, 2), (127, 1), (199, 2)]);
+}
+
+#[test]
+fn test_shifted() {
+    let mut grid = BitGrid::new(130, 2);
+    grid.set(0, 0);
+    grid.set(0, 63);
+    grid.set(1, 129);
+    assert_eq!(grid.shifted(0, 1).iter().collect::<Vec<_>>(), vec![(0, 1), (0, 64)]);
+    assert_eq!(grid.shifted(1, 66).iter().collect::<Vec<_>>(), vec![(1, 66), (1, 129)]);
+    assert_eq!(grid.shifted(-1, -64).iter().collect::<Vec<_>>(), vec![(0, 65)]);
+    assert_eq!(grid.shifted(0, -1).iter().collect::<Vec<_>>(), vec![(0, 62), (1, 128)]);
+    assert_eq!(grid.shifted(0, 130), BitGrid::new(130, 2));
+}
+
+#[test]
+fn test_bit_ops() {
+    let mut a = BitGrid::new(70, 1);
+    let mut b = BitGrid::new(70, 1);
+    a.set(0, 3);
+    a.set(0, 69);
+    b.set(0, 69);
+    assert_eq!((&a & &b).iter().collect::<Vec<_>>(), vec![(0, 69)]);
+    assert_eq!((&a | &b).count(), 2);
+    assert_eq!((&a ^ &b).iter().collect::<Vec<_>>(), vec![(0, 3)]);
+    assert_eq!((!&a).count(), 68);
+    a &= &!&b;
+    assert_eq!(a.count(), 1);
+}
+
 #[test]
-fn test_set_bit() {
-    let mut bm = [0_u128; 512];
-    bm.set_bit(0, 0);
-    assert_eq!(bm[0], 2_u128.pow(127));
-    bm.set_bit(0, 1);
-    assert_eq!(bm[0], 2_u128.pow(127) + 2_u128.pow(126));
-    bm.set_bit(1, 200);
-    assert_eq!(bm[3], 2_u128.pow(55));
+fn test_render() {
+    let mut grid = BitGrid::new(8, 8);
+    assert_eq!(grid.render(), "");
+    grid.set(2, 3);
+    grid.set(4, 1);
+    assert_eq!(grid.render(), "..#\n...\n#..\n");
+}
 
-    assert_eq!(bm.count_bits(), 3);
+// the directions as (rows, cols), clockwise from north
+pub const DIRS_4: [(isize, isize); 4] = [(-1, 0), (0, 1), (1, 0), (0, -1)];
+pub const DIRS_8: [(isize, isize); 8] = [(-1, 0), (-1, 1), (0, 1), (1, 1), (1, 0), (1, -1), (0, -1), (-1, -1)];
+
+// a dense grid stored row by row and indexed by (row, col), row 0 is the top one
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    pub fn new(width: usize, height: usize, fill: T) -> Self
+    where T: Clone
+    {
+        Self { width, height, cells: vec![fill; width * height] }
+    }
+
+    pub fn from_fn(width: usize, height: usize, mut cell: impl FnMut((usize, usize)) -> T) -> Self {
+        Self { width, height, cells: (0..width * height).map(|i| cell((i / width, i % width))).collect() }
+    }
+
+    // the cells row by row, a multiple of width of them
+    pub fn from_vec(width: usize, cells: Vec<T>) -> Self {
+        let height = cells.len().checked_div(width).unwrap_or(0);
+        assert_eq!(width * height, cells.len(), "{} cells don't make rows of {}", cells.len(), width);
+        Self { width, height, cells }
+    }
+
+    // a row per line and a cell per character, which cell turns into a T or tells what it expected
+    // instead. every row has to be as long as the first, what names the cells in that error
+    pub fn parse<E: Into<String>>(input: &str, what: &str, mut cell: impl FnMut((usize, usize), char) -> Result<T, E>) -> Result<Self, ParseError> {
+        let mut grid = Self { width: 0, height: 0, cells: Vec::new() };
+        for (row, line) in error::lines(input).enumerate() {
+            let len = line.text.chars().count();
+            if row == 0 {
+                grid.width = len;
+            } else if len != grid.width {
+                return Err(line.end_error(format!("a row of {} {}", grid.width, what)));
+            }
+            for (col, c) in line.text.chars().enumerate() {
+                grid.cells.push(cell((row, col), c).map_err(|expected| line.error_at(col + 1, expected))?);
+            }
+            grid.height += 1;
+        }
+        Ok(grid)
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, (row, col): (usize, usize)) -> bool {
+        row < self.height && col < self.width
+    }
+
+    pub fn get(&self, pos: (usize, usize)) -> Option<&T> {
+        self.contains(pos).then(|| &self.cells[pos.0 * self.width + pos.1])
+    }
+
+    pub fn get_mut(&mut self, pos: (usize, usize)) -> Option<&mut T> {
+        self.contains(pos).then(|| &mut self.cells[pos.0 * self.width + pos.1])
+    }
+
+    pub fn row(&self, row: usize) -> &[T] {
+        &self.cells[row * self.width..(row + 1) * self.width]
+    }
+
+    pub fn col(&self, col: usize) -> impl Iterator<Item = &T> {
+        self.cells.iter().skip(col).step_by(self.width.max(1)).take(self.height)
+    }
+
+    // the cells with their positions, row by row
+    pub fn iter(&self) -> impl Iterator<Item = ((usize, usize), &T)> {
+        self.cells.iter().enumerate().map(|(i, cell)| ((i / self.width, i % self.width), cell))
+    }
+
+    pub fn positions(&self) -> impl Iterator<Item = (usize, usize)> {
+        let width = self.width;
+        (0..self.cells.len()).map(move |i| (i / width, i % width))
+    }
+
+    pub fn position(&self, pred: impl Fn(&T) -> bool) -> Option<(usize, usize)> {
+        self.iter().find(|(_, cell)| pred(cell)).map(|(pos, _)| pos)
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid { width: self.width, height: self.height, cells: self.cells.iter().map(f).collect() }
+    }
+
+    // the position a step in dir away, None off the grid
+    pub fn step(&self, (row, col): (usize, usize), (rows, cols): (isize, isize)) -> Option<(usize, usize)> {
+        let pos = (row.checked_add_signed(rows)?, col.checked_add_signed(cols)?);
+        self.contains(pos).then_some(pos)
+    }
+
+    // north, east, south and west of pos, as far as they are on the grid
+    pub fn neighbours(&self, pos: (usize, usize)) -> impl Iterator<Item = (usize, usize)> + '_ {
+        DIRS_4.into_iter().filter_map(move |dir| self.step(pos, dir))
+    }
+
+    // including the diagonal ones
+    pub fn neighbours_8(&self, pos: (usize, usize)) -> impl Iterator<Item = (usize, usize)> + '_ {
+        DIRS_8.into_iter().filter_map(move |dir| self.step(pos, dir))
+    }
+
+    // the positions from pos in dir to the edge of the grid, without pos itself. rays along rows,
+    // columns and diagonals are the ones in DIRS_8
+    pub fn ray(&self, pos: (usize, usize), dir: (isize, isize)) -> impl Iterator<Item = (usize, usize)> + '_ {
+        std::iter::successors(self.step(pos, dir), move |pos| self.step(*pos, dir))
+    }
+
+    pub fn transpose(&self) -> Self
+    where T: Clone
+    {
+        Self::from_fn(self.height, self.width, |(row, col)| self[(col, row)].clone())
+    }
+
+    // a quarter turn clockwise, the left column becomes the top row
+    pub fn rotate_right(&self) -> Self
+    where T: Clone
+    {
+        Self::from_fn(self.height, self.width, |(row, col)| self[(self.height - 1 - col, row)].clone())
+    }
+
+    pub fn rotate_left(&self) -> Self
+    where T: Clone
+    {
+        Self::from_fn(self.height, self.width, |(row, col)| self[(col, self.width - 1 - row)].clone())
+    }
+
+    // upside down
+    pub fn flip_rows(&self) -> Self
+    where T: Clone
+    {
+        Self::from_fn(self.width, self.height, |(row, col)| self[(self.height - 1 - row, col)].clone())
+    }
+
+    // mirrored, the left column becomes the right one
+    pub fn flip_cols(&self) -> Self
+    where T: Clone
+    {
+        Self::from_fn(self.width, self.height, |(row, col)| self[(row, self.width - 1 - col)].clone())
+    }
+
+    // the part of the grid with its top left corner at (top, left), cut off where the grid ends
+    pub fn view(&self, (top, left): (usize, usize), width: usize, height: usize) -> View<'_, T> {
+        let width = width.min(self.width.saturating_sub(left));
+        let height = height.min(self.height.saturating_sub(top));
+        View { grid: self, top, left, width, height }
+    }
+
+    // a line per row with a character per cell
+    pub fn render(&self, cell: impl Fn(&T) -> char) -> String {
+        self.view((0, 0), self.width, self.height).render(cell)
+    }
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: (usize, usize)) -> &T {
+        self.get(pos).unwrap_or_else(|| panic!("{:?} is outside of a {}x{} grid", pos, self.width, self.height))
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, pos: (usize, usize)) -> &mut T {
+        let (width, height) = (self.width, self.height);
+        self.get_mut(pos).unwrap_or_else(|| panic!("{:?} is outside of a {}x{} grid", pos, width, height))
+    }
+}
+
+impl Display for Grid<char> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.render(|c| *c))
+    }
+}
+
+// a rectangle of a grid, indexed from its own top left corner
+#[derive(Debug, Clone, Copy)]
+pub struct View<'a, T> {
+    grid: &'a Grid<T>,
+    top: usize,
+    left: usize,
+    width: usize,
+    height: usize,
+}
+
+impl<'a, T> View<'a, T> {
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn get(&self, (row, col): (usize, usize)) -> Option<&'a T> {
+        (row < self.height && col < self.width).then(|| &self.grid[(self.top + row, self.left + col)])
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = ((usize, usize), &'a T)> + '_ {
+        (0..self.height).flat_map(move |row| (0..self.width).map(move |col| ((row, col), &self.grid[(self.top + row, self.left + col)])))
+    }
+
+    pub fn to_grid(&self) -> Grid<T>
+    where T: Clone
+    {
+        Grid::from_fn(self.width, self.height, |(row, col)| self.grid[(self.top + row, self.left + col)].clone())
+    }
+
+    pub fn render(&self, cell: impl Fn(&T) -> char) -> String {
+        (0..self.height)
+            .map(|row| (0..self.width).map(|col| cell(&self.grid[(self.top + row, self.left + col)])).collect::<String>() + "\n")
+            .collect()
+    }
+}
+
+impl<T> Index<(usize, usize)> for View<'_, T> {
+    type Output = T;
+
+    fn index(&self, pos: (usize, usize)) -> &T {
+        self.get(pos).unwrap_or_else(|| panic!("{:?} is outside of a {}x{} view", pos, self.width, self.height))
+    }
+}
+
+#[test]
+fn test_grid_parse() {
+    let grid = Grid::parse("12\n34\n56\n", "digits", |_, c| c.to_digit(10).ok_or("a digit")).unwrap();
+    assert_eq!((grid.width(), grid.height()), (2, 3));
+    assert_eq!(grid[(2, 1)], 6);
+    assert_eq!(grid.get((3, 0)), None);
+    assert_eq!(grid.row(1), &[3, 4]);
+    assert_eq!(grid.col(1).copied().collect::<Vec<_>>(), vec![2, 4, 6]);
+    assert_eq!(grid.position(|n| *n == 5), Some((2, 0)));
+
+    let err = Grid::parse("12\n3x\n", "digits", |_, c| c.to_digit(10).ok_or("a digit")).unwrap_err();
+    assert_eq!(err.to_string(), "line 2, column 2: expected a digit, found 'x'");
+    let err = Grid::parse("12\n345\n", "digits", |_, c| c.to_digit(10).ok_or("a digit")).unwrap_err();
+    assert_eq!(err.to_string(), "line 2, column 4: expected a row of 2 digits, found end of line");
+}
+
+#[test]
+fn test_grid_neighbours() {
+    let grid = Grid::new(3, 3, '.');
+    assert_eq!(grid.neighbours((0, 0)).collect::<Vec<_>>(), vec![(0, 1), (1, 0)]);
+    assert_eq!(grid.neighbours((1, 1)).count(), 4);
+    assert_eq!(grid.neighbours_8((1, 1)).count(), 8);
+    assert_eq!(grid.neighbours_8((2, 2)).collect::<Vec<_>>(), vec![(1, 2), (2, 1), (1, 1)]);
+    assert_eq!(grid.ray((2, 0), (-1, 1)).collect::<Vec<_>>(), vec![(1, 1), (0, 2)]);
+    assert_eq!(grid.ray((1, 1), (0, -1)).collect::<Vec<_>>(), vec![(1, 0)]);
+    assert_eq!(grid.ray((1, 0), (0, -1)).count(), 0);
+}
+
+#[test]
+fn test_grid_transform() {
+    let grid = Grid::parse("abc\ndef\n", "letters", |_, c| Ok::<_, String>(c)).unwrap();
+    assert_eq!(grid.to_string(), "abc\ndef\n");
+    assert_eq!(grid.transpose().to_string(), "ad\nbe\ncf\n");
+    assert_eq!(grid.rotate_right().to_string(), "da\neb\nfc\n");
+    assert_eq!(grid.rotate_left().to_string(), "cf\nbe\nad\n");
+    assert_eq!(grid.flip_rows().to_string(), "def\nabc\n");
+    assert_eq!(grid.flip_cols().to_string(), "cba\nfed\n");
+    assert_eq!(grid.rotate_right().rotate_right().rotate_right().rotate_right(), grid);
+    assert_eq!(grid.map(|c| c.to_ascii_uppercase()).render(|c| *c), "ABC\nDEF\n");
+}
+
+#[test]
+fn test_grid_view() {
+    let grid = Grid::from_vec(4, (0..12).collect());
+    let view = grid.view((1, 2), 5, 5);
+    assert_eq!((view.width(), view.height()), (2, 2));
+    assert_eq!(view[(1, 1)], 11);
+    assert_eq!(view.get((2, 0)), None);
+    assert_eq!(view.iter().map(|(_, n)| *n).collect::<Vec<_>>(), vec![6, 7, 10, 11]);
+    assert_eq!(view.to_grid(), Grid::from_vec(2, vec![6, 7, 10, 11]));
+    assert_eq!(view.render(|n| if n % 2 == 0 { '#' } else { '.' }), "#.\n#.\n");
+}
+
+// what a search found. dist and prev hold every node it reached, following prev from a node leads
+// back to the start it was reached from
+#[derive(Debug, Clone)]
+pub struct Search<N, C> {
+    // the goal that was reached first, which is the nearest one
+    pub goal: Option<N>,
+    pub dist: HashMap<N, C>,
+    pub prev: HashMap<N, N>,
+    pub stats: SearchStats,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SearchStats {
+    // the nodes whose neighbours were looked at
+    pub expanded: usize,
+    // the most nodes waiting in the queue at once
+    pub max_frontier: usize,
+}
+
+impl<N: Clone + Eq + Hash, C: Copy> Search<N, C> {
+    fn new() -> Self {
+        Self { goal: None, dist: HashMap::new(), prev: HashMap::new(), stats: SearchStats::default() }
+    }
+
+    // of the goal, None if none was reached
+    pub fn distance(&self) -> Option<C> {
+        self.goal.as_ref().map(|goal| self.dist[goal])
+    }
+
+    // from a start to the goal, both included
+    pub fn path(&self) -> Option<Vec<N>> {
+        self.goal.as_ref().and_then(|goal| self.path_to(goal))
+    }
+
+    // from a start to node, None if the search didn't reach it
+    pub fn path_to(&self, node: &N) -> Option<Vec<N>> {
+        if !self.dist.contains_key(node) {
+            return None;
+        }
+        let mut path = vec![node.clone()];
+        while let Some(prev) = self.prev.get(&path[path.len() - 1]) {
+            path.push(prev.clone());
+        }
+        path.reverse();
+        Some(path)
+    }
+}
+
+// a breadth first search from all starts at once, every step costs 1. without a goal it reaches
+// everything it can
+pub fn bfs<N, I>(starts: impl IntoIterator<Item = N>, mut neighbours: impl FnMut(&N) -> I, mut is_goal: impl FnMut(&N) -> bool) -> Search<N, usize>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut search = Search::new();
+    let mut queue = VecDeque::new();
+    for start in starts {
+        if search.dist.insert(start.clone(), 0).is_none() {
+            queue.push_back(start);
+        }
+    }
+    search.stats.max_frontier = queue.len();
+
+    while let Some(node) = queue.pop_front() {
+        if is_goal(&node) {
+            search.goal = Some(node);
+            break;
+        }
+        search.stats.expanded += 1;
+        let dist = search.dist[&node] + 1;
+        for next in neighbours(&node) {
+            if !search.dist.contains_key(&next) {
+                search.dist.insert(next.clone(), dist);
+                search.prev.insert(next.clone(), node.clone());
+                queue.push_back(next);
+            }
+        }
+        search.stats.max_frontier = search.stats.max_frontier.max(queue.len());
+    }
+    search
+}
+
+// cost is the cost of a step from a node to its neighbour, it can't be negative
+pub fn dijkstra<N, C, I>(
+    starts: impl IntoIterator<Item = N>,
+    neighbours: impl FnMut(&N) -> I,
+    cost: impl FnMut(&N, &N) -> C,
+    is_goal: impl FnMut(&N) -> bool,
+) -> Search<N, C>
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Add<Output = C> + Default,
+    I: IntoIterator<Item = N>,
+{
+    a_star(starts, neighbours, cost, |_| C::default(), is_goal)
+}
+
+// heuristic estimates the cost from a node to the nearest goal, the distance is the shortest one
+// as long as it never estimates too much
+pub fn a_star<N, C, I>(
+    starts: impl IntoIterator<Item = N>,
+    mut neighbours: impl FnMut(&N) -> I,
+    mut cost: impl FnMut(&N, &N) -> C,
+    mut heuristic: impl FnMut(&N) -> C,
+    mut is_goal: impl FnMut(&N) -> bool,
+) -> Search<N, C>
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Add<Output = C> + Default,
+    I: IntoIterator<Item = N>,
+{
+    let mut search = Search::new();
+    let mut queue = BinaryHeap::new();
+    for start in starts {
+        if search.dist.insert(start.clone(), C::default()).is_none() {
+            queue.push(Queued { priority: heuristic(&start), cost: C::default(), node: start });
+        }
+    }
+    search.stats.max_frontier = queue.len();
+
+    while let Some(Queued { cost: dist, node, .. }) = queue.pop() {
+        // a shorter way to the node was found after it was queued
+        if search.dist[&node] < dist {
+            continue;
+        }
+        if is_goal(&node) {
+            search.goal = Some(node);
+            break;
+        }
+        search.stats.expanded += 1;
+        for next in neighbours(&node) {
+            let next_dist = dist + cost(&node, &next);
+            if search.dist.get(&next).is_none_or(|d| next_dist < *d) {
+                search.dist.insert(next.clone(), next_dist);
+                search.prev.insert(next.clone(), node.clone());
+                queue.push(Queued { priority: next_dist + heuristic(&next), cost: next_dist, node: next });
+            }
+        }
+        search.stats.max_frontier = search.stats.max_frontier.max(queue.len());
+    }
+    search
+}
+
+// the lowest priority comes out of the heap first, of those the one furthest from the start
+struct Queued<N, C> {
+    priority: C,
+    cost: C,
+    node: N,
+}
+
+impl<N, C: Ord> Ord for Queued<N, C> {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.priority.cmp(&other.priority).reverse().then(self.cost.cmp(&other.cost))
+    }
+}
+
+impl<N, C: Ord> PartialOrd for Queued<N, C> {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<N, C: Ord> PartialEq for Queued<N, C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == std::cmp::Ordering::Equal
+    }
+}
+
+impl<N, C: Ord> Eq for Queued<N, C> {}
+
+#[test]
+fn test_bfs() {
+    // from both ends of a row of 10, the middle is 4 steps away from the nearer one
+    let search = bfs([0, 9], |n: &i32| [n - 1, n + 1].into_iter().filter(|n| (0..10).contains(n)), |n| *n == 5);
+    assert_eq!(search.distance(), Some(4));
+    assert_eq!(search.path(), Some(vec![9, 8, 7, 6, 5]));
+    assert_eq!(search.path_to(&2), Some(vec![0, 1, 2]));
+    assert_eq!(search.stats.max_frontier, 2);
+
+    let search = bfs([0], |n: &i32| [n + 1].into_iter().filter(|n| *n < 10), |_| false);
+    assert_eq!((search.goal, search.dist.len(), search.stats.expanded), (None, 10, 10));
+}
+
+#[test]
+fn test_dijkstra() {
+    // the way around is cheaper than the direct edge
+    let edges = HashMap::from([('a', vec![('b', 1), ('d', 10)]), ('b', vec![('c', 2)]), ('c', vec![('d', 3)]), ('d', vec![])]);
+    let cost = |from: &char, to: &char| edges[from].iter().find(|(n, _)| n == to).unwrap().1;
+    let search = dijkstra(['a'], |n| edges[n].iter().map(|(n, _)| *n).collect::<Vec<_>>(), cost, |n| *n == 'd');
+    assert_eq!(search.distance(), Some(6));
+    assert_eq!(search.path(), Some(vec!['a', 'b', 'c', 'd']));
+    assert_eq!(search.dist[&'c'], 3);
+}
+
+#[test]
+fn test_a_star() {
+    let grid = Grid::parse("....#\n.##.#\n...#.\n.#...\n", "tiles", |_, c| Ok::<_, String>(c == '#')).unwrap();
+    let goal = (3, 4);
+    let manhattan = |(row, col): &(usize, usize)| row.abs_diff(goal.0) + col.abs_diff(goal.1);
+    let neighbours = |pos: &(usize, usize)| grid.neighbours(*pos).filter(|pos| !grid[*pos]).collect::<Vec<_>>();
+    let search = a_star([(0, 0)], neighbours, |_, _| 1, manhattan, |pos| *pos == goal);
+    assert_eq!(search.distance(), Some(7));
+    assert_eq!(search.path().unwrap()[..4], [(0, 0), (1, 0), (2, 0), (2, 1)]);
+
+    let unguided = dijkstra([(0, 0)], neighbours, |_, _| 1, |pos| *pos == goal);
+    assert_eq!(unguided.distance(), Some(7));
+    assert!(search.stats.expanded <= unguided.stats.expanded);
+}
+
+// where the states of a simulation start repeating and how a metric of them grows meanwhile, e.g.
+// the height of a tower of rocks. the state after prefix + length steps is the one after prefix
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cycle {
+    pub prefix: usize,
+    pub length: usize,
+    // the metric after 0 to prefix + length steps
+    metrics: Vec<i64>,
+}
+
+impl Cycle {
+    // runs the simulation from start for prefix + length steps to record the metric
+    fn measure<S>(mut state: S, mut step: impl FnMut(&mut S), mut metric: impl FnMut(&S) -> i64, prefix: usize, length: usize) -> Self {
+        let mut metrics = vec![metric(&state)];
+        for _ in 0..prefix + length {
+            step(&mut state);
+            metrics.push(metric(&state));
+        }
+        Self { prefix, length, metrics }
+    }
+
+    // how much the metric grows per cycle
+    pub fn delta(&self) -> i64 {
+        self.metrics[self.prefix + self.length] - self.metrics[self.prefix]
+    }
+
+    // the metric after n steps, which can be far more than the simulation could run
+    pub fn extrapolate(&self, n: u64) -> i128 {
+        let (prefix, length) = (self.prefix as u64, self.length as u64);
+        if n <= prefix + length {
+            return self.metrics[n as usize] as i128;
+        }
+        let (cycles, rest) = ((n - prefix) / length, (n - prefix) % length);
+        self.metrics[(prefix + rest) as usize] as i128 + cycles as i128 * self.delta() as i128
+    }
+}
+
+// steps the simulation until the key of a state repeats, remembering the keys it has seen. key is
+// a fingerprint of everything that decides how the simulation goes on, states with the same key
+// have to go on the same way. runs forever if the states never repeat
+pub fn find_cycle<S, K: Hash + Eq>(mut state: S, mut step: impl FnMut(&mut S), mut key: impl FnMut(&S) -> K, mut metric: impl FnMut(&S) -> i64) -> Cycle {
+    let mut seen = HashMap::new();
+    let mut metrics = Vec::new();
+    loop {
+        metrics.push(metric(&state));
+        let n = metrics.len() - 1;
+        if let Some(prefix) = seen.insert(key(&state), n) {
+            return Cycle { prefix, length: n - prefix, metrics };
+        }
+        step(&mut state);
+    }
+}
+
+// finds the cycle with two copies of the state instead of remembering the keys, the hare steps twice
+// as fast as the tortoise until they meet within the cycle
+pub fn floyd<S: Clone, K: PartialEq>(start: S, mut step: impl FnMut(&mut S), mut key: impl FnMut(&S) -> K, metric: impl FnMut(&S) -> i64) -> Cycle {
+    let (mut tortoise, mut hare) = (start.clone(), start.clone());
+    step(&mut tortoise);
+    step(&mut hare);
+    step(&mut hare);
+    while key(&tortoise) != key(&hare) {
+        step(&mut tortoise);
+        step(&mut hare);
+        step(&mut hare);
+    }
+
+    // the hare is a multiple of the cycle length ahead, so they meet where the cycle starts
+    let mut prefix = 0;
+    tortoise = start.clone();
+    while key(&tortoise) != key(&hare) {
+        step(&mut tortoise);
+        step(&mut hare);
+        prefix += 1;
+    }
+
+    let mut length = 1;
+    let tortoise_key = key(&tortoise);
+    hare = tortoise;
+    step(&mut hare);
+    while key(&hare) != tortoise_key {
+        step(&mut hare);
+        length += 1;
+    }
+    Cycle::measure(start, step, metric, prefix, length)
+}
+
+// like floyd, but the tortoise jumps to the hare at every power of two, which takes fewer steps
+pub fn brent<S: Clone, K: PartialEq>(start: S, mut step: impl FnMut(&mut S), mut key: impl FnMut(&S) -> K, metric: impl FnMut(&S) -> i64) -> Cycle {
+    let (mut power, mut length) = (1, 1);
+    let mut tortoise_key = key(&start);
+    let mut hare = start.clone();
+    step(&mut hare);
+    while key(&hare) != tortoise_key {
+        if power == length {
+            tortoise_key = key(&hare);
+            power *= 2;
+            length = 0;
+        }
+        step(&mut hare);
+        length += 1;
+    }
+
+    // with the hare a cycle ahead of the tortoise they meet where the cycle starts
+    let (mut tortoise, mut hare) = (start.clone(), start.clone());
+    for _ in 0..length {
+        step(&mut hare);
+    }
+    let mut prefix = 0;
+    while key(&tortoise) != key(&hare) {
+        step(&mut tortoise);
+        step(&mut hare);
+        prefix += 1;
+    }
+    Cycle::measure(start, step, metric, prefix, length)
+}
+
+#[test]
+fn test_cycle() {
+    // 3 steps into the cycle 3, 4, 5, 6, 7 of a counter that wraps from 8 to 3, its total grows by 25 per cycle
+    let step = |(n, total): &mut (i64, i64)| {
+        *n = if *n == 7 { 3 } else { *n + 1 };
+        *total += *n;
+    };
+    let key = |(n, _): &(i64, i64)| *n;
+    let metric = |(_, total): &(i64, i64)| *total;
+    for cycle in [find_cycle((0, 0), step, key, metric), floyd((0, 0), step, key, metric), brent((0, 0), step, key, metric)] {
+        assert_eq!((cycle.prefix, cycle.length, cycle.delta()), (3, 5, 25));
+        assert_eq!(cycle.extrapolate(2), 3);
+        assert_eq!(cycle.extrapolate(9), 1 + 2 + 3 + 4 + 5 + 6 + 7 + 3 + 4);
+        assert_eq!(cycle.extrapolate(1_000_000_000_003), 6 + 200_000_000_000 * 25);
+    }
+
+    // a state that repeats right away
+    let cycle = brent(1, |n| *n = -*n, |n| *n, |n| *n as i64);
+    assert_eq!((cycle.prefix, cycle.length, cycle.delta()), (0, 2, 0));
+    assert_eq!(cycle.extrapolate(u64::MAX), -1);
 }
 
 const fn _pow_two_int_sqrt(n: usize) -> usize {
@@ -139,20 +1117,3 @@ fn test_pow_two_int_sqrt() {
     assert_eq!(_pow_two_int_sqrt(64), 8);
 }
 
-
-
-impl_bm!(u8, 8);
-impl_bm!(u16, 16);
-impl_bm!(u32, 32);
-impl_bm!(u64, 64);
-impl_bm!(u128, 128);
-impl_bm!(u128, 256);
-impl_bm!(u128, 384);
-impl_bm!(u128, 512);
-impl_bm!(u128, 640);
-impl_bm!(u128, 768);
-impl_bm!(u128, 896);
-impl_bm!(u128, 1024);
-impl_bm!(u128, 2048);
-impl_bm!(u128, 4096);
-impl_bm!(u128, 8192);
\ No newline at end of file
@@ -0,0 +1,53 @@
+use std::{fmt::Display, io::{self, Read}, path::{Path, PathBuf}};
+
+pub static DEFAULT_DATA_DIR: &str = "data";
+
+// where a day's puzzle input is read from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Source {
+    File(PathBuf),
+    Stdin,
+}
+
+impl Source {
+    // the input checked into the data directory, e.g. data/d07.txt
+    pub fn default_for(data_dir: &Path, day: u8) -> Self {
+        Source::File(data_dir.join(format!("d{:02}.txt", day)))
+    }
+
+    pub fn read(&self) -> io::Result<String> {
+        match self {
+            Source::File(path) => std::fs::read_to_string(path),
+            Source::Stdin => {
+                let mut input = String::new();
+                io::stdin().read_to_string(&mut input)?;
+                Ok(input)
+            },
+        }
+    }
+}
+
+impl From<&str> for Source {
+    fn from(arg: &str) -> Self {
+        match arg {
+            "-" => Source::Stdin,
+            path => Source::File(PathBuf::from(path)),
+        }
+    }
+}
+
+impl Display for Source {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Source::File(path) => write!(f, "{}", path.display()),
+            Source::Stdin => write!(f, "stdin"),
+        }
+    }
+}
+
+#[test]
+fn test_source() {
+    assert_eq!(Source::from("-"), Source::Stdin);
+    assert_eq!(Source::from("inputs/d01.txt"), Source::File(PathBuf::from("inputs/d01.txt")));
+    assert_eq!(Source::default_for(Path::new("data"), 7), Source::File(PathBuf::from("data/d07.txt")));
+}
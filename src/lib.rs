@@ -0,0 +1,49 @@
+//! Solutions for Advent of Code 2022.
+//!
+//! Every day lives in its own module and implements [`solution::Solution`], the runner
+//! finds them through [`registry::REGISTRY`].
+
+pub mod answers;
+pub mod bench;
+pub mod cache;
+pub mod compare;
+pub mod error;
+pub mod frames;
+pub mod generate;
+pub mod helpers;
+pub mod input;
+pub mod matrix;
+pub mod memory;
+pub mod page;
+pub mod pool;
+pub mod registry;
+pub mod repl;
+pub mod report;
+pub mod solution;
+pub mod trace;
+mod toml;
+pub mod d01;
+pub mod d02;
+pub mod d03;
+pub mod d04;
+pub mod d05;
+pub mod d06;
+pub mod d07;
+pub mod d08;
+pub mod d09;
+pub mod d10;
+pub mod d11;
+pub mod d12;
+pub mod d13;
+pub mod d14;
+pub mod d15;
+pub mod d16;
+pub mod d17;
+pub mod d18;
+pub mod d19;
+pub mod d20;
+pub mod d21;
+pub mod d22;
+pub mod d23;
+pub mod d24;
+pub mod d25;
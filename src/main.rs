@@ -1,231 +1,733 @@
-use std::time::Instant;
-
-mod helpers;
-mod d01;
-mod d02;
-mod d03;
-mod d04;
-mod d05;
-mod d06;
-mod d07;
-mod d08;
-mod d09;
-mod d10;
-mod d11;
-mod d12;
-mod d13;
-mod d14;
-mod d15;
-mod d16;
-mod d17;
-mod d18;
-mod d19;
-mod d20;
-mod d21;
-mod d22;
-mod d23;
-mod d24;
-mod d25;
+use std::{collections::HashMap, io::{self, BufRead, Write}, path::{Path, PathBuf}, sync::Arc, time::{Duration, Instant}};
+
+use aoc22::{
+    answers::{Answers, Verdict},
+    bench::{self, Baseline, Phase, Stats},
+    cache::{self, Cache, Cached, Key, CACHE_FILE},
+    compare,
+    error::ParseError,
+    frames,
+    generate,
+    matrix::{self, Input, Matrix},
+    memory::{self, Bytes, Counting, Usage},
+    page,
+    pool::{self, Failure, Job},
+    registry::{self, Entry},
+    repl,
+    report::{self, Record},
+    solution::{Answer, Params},
+    trace,
+};
+use cli::{Animate, Command, Compare, Import, Mode, Options, Repl};
+
+mod cli;
+mod scaffold;
+
+// counts nothing unless --memory asks for it
+#[global_allocator]
+static ALLOCATOR: Counting = Counting;
 
 fn main() {
-    println!("Day\t\truntime\tresult");
+    let options = match cli::parse_args(std::env::args().skip(1)) {
+        Ok(Command::Run(options)) => options,
+        Ok(Command::Import(import)) => run_import(&import),
+        Ok(Command::NewDay(day)) => match scaffold::new_day(day) {
+            Ok(done) => {
+                done.iter().for_each(|line| println!("{}", line));
+                return;
+            },
+            Err(e) => {
+                eprintln!("error: could not add day {}: {}", day, e);
+                std::process::exit(1);
+            },
+        },
+        Ok(Command::Generate(generate)) => {
+            let input = generate.generator.generate(generate.seed, generate.size);
+            match &generate.output {
+                Some(path) => if let Err(e) = std::fs::write(path, input) {
+                    eprintln!("error: could not write {}: {}", path.display(), e);
+                    std::process::exit(1);
+                },
+                None => print!("{}", input),
+            }
+            return;
+        },
+        Ok(Command::Compare(compare)) => run_compare(&compare),
+        Ok(Command::Repl(repl)) => run_repl(&repl),
+        Ok(Command::Animate(animate)) => run_animate(&animate),
+        Ok(Command::Help) => {
+            println!("{}", cli::USAGE);
+            return;
+        },
+        Err(msg) => exit_with_usage(&msg),
+    };
+    let selection = &options.selection;
+    if !registry::REGISTRY.iter().any(|e| (1..=e.runner.parts()).any(|part| selection.contains(e.day, part))) {
+        exit_with_usage("selection does not contain any puzzle parts");
+    }
+    if options.matrix {
+        run_matrix(&options);
+    }
+
+    let mut session = Session::new(&options);
+    if session.text {
+        let mut header = match options.bench {
+            Some(_) => "Day\t\tmin\tmedian\tmean\tp95\tresult".to_string(),
+            None if options.memory => "Day\t\truntime\tallocs\tbytes\tpeak\tresult".to_string(),
+            None => "Day\t\truntime\tresult".to_string(),
+        };
+        if options.mode == Mode::Verify {
+            header += "\tstatus";
+        }
+        println!("{}", header);
+    }
+
+    if options.trace.is_some() {
+        trace::enable();
+    }
     let start_all = Instant::now();
+    match &options.pool {
+        Some(pool) => run_pool(&mut session, pool.jobs, pool.timeout),
+        None => run_serial(&mut session),
+    }
+    session.finish(start_all.elapsed());
+}
 
-    let start = Instant::now();
-    let result = d01::get_solution_1();
-    println!("Day  1.1\t{}us\t{}", start.elapsed().as_micros(), result);
-
-    let start = Instant::now();
-    let result = d01::get_solution_2();
-    println!("Day  1.2\t{}us\t{}", start.elapsed().as_micros(), result);
-
-    let start = Instant::now();
-    let result = d02::get_solution_1();
-    println!("Day  2.1\t{}us\t{}", start.elapsed().as_micros(), result);
-
-    let start = Instant::now();
-    let result = d02::get_solution_2();
-    println!("Day  2.2\t{}us\t{}", start.elapsed().as_micros(), result);
-
-    let start = Instant::now();
-    let result = d03::get_solution_1();
-    println!("Day  3.1\t{}us\t{}", start.elapsed().as_micros(), result);
-
-    let start = Instant::now();
-    let result = d03::get_solution_2();
-    println!("Day  3.2\t{}us\t{}", start.elapsed().as_micros(), result);
-
-    let start = Instant::now();
-    let result = d04::get_solution_1();
-    println!("Day  4.1\t{}us\t{}", start.elapsed().as_micros(), result);
-
-    let start = Instant::now();
-    let result = d04::get_solution_2();
-    println!("Day  4.2\t{}us\t{}", start.elapsed().as_micros(), result);
-
-    let start = Instant::now();
-    let result = d05::get_solution_1();
-    println!("Day  5.1\t{}us\t{}", start.elapsed().as_micros(), result);
-
-    let start = Instant::now();
-    let result = d05::get_solution_2();
-    println!("Day  5.2\t{}us\t{}", start.elapsed().as_micros(), result);
-
-    let start = Instant::now();
-    let result = d06::get_solution_1();
-    println!("Day  6.1\t{}us\t{}", start.elapsed().as_micros(), result);
-
-    let start = Instant::now();
-    let result = d06::get_solution_2();
-    println!("Day  6.2\t{}us\t{}", start.elapsed().as_micros(), result);
-
-    let start = Instant::now();
-    let result = d07::get_solution_1();
-    println!("Day  7.1\t{}us\t{}", start.elapsed().as_micros(), result);
-
-    let start = Instant::now();
-    let result = d07::get_solution_2();
-    println!("Day  7.2\t{}us\t{}", start.elapsed().as_micros(), result);
-
-    let start = Instant::now();
-    let result = d08::get_solution_1();
-    println!("Day  8.1\t{}us\t{}", start.elapsed().as_micros(), result);
-
-    let start = Instant::now();
-    let result = d08::get_solution_2();
-    println!("Day  8.2\t{}us\t{}", start.elapsed().as_micros(), result);
-
-    let start = Instant::now();
-    let result = d09::get_solution_1();
-    println!("Day  9.1\t{}us\t{}", start.elapsed().as_micros(), result);
-
-    let start = Instant::now();
-    let result = d09::get_solution_2();
-    println!("Day  9.2\t{}us\t{}", start.elapsed().as_micros(), result);
-
-    let start = Instant::now();
-    let result = d10::get_solution_1();
-    println!("Day 10.1\t{}us\t{}", start.elapsed().as_micros(), result);
-
-    let start = Instant::now();
-    let result = d10::get_solution_2();
-    println!("Day 10.2\t{}us\t{}", start.elapsed().as_micros(), result);
+// the selected parts of a day and its input, None if nothing of the day is selected or reading failed
+fn read_day(session: &mut Session, day: u8) -> Option<(&'static Entry, Vec<u8>, String)> {
+    let entry = session.options.entry(day)?;
+    let parts = (1..=entry.runner.parts()).filter(|part| session.options.selection.contains(day, *part)).collect::<Vec<u8>>();
+    if parts.is_empty() {
+        return None;
+    }
+
+    let source = session.options.source(day);
+    match source.read() {
+        Ok(input) => Some((entry, parts, input)),
+        Err(e) => {
+            eprintln!("Day {:2}\tcould not read input from {}: {}", day, source, e);
+            session.failed = true;
+            None
+        },
+    }
+}
 
-    let start = Instant::now();
-    let result = d11::get_solution_1();
-    println!("Day 11.1\t{}us\t{}", start.elapsed().as_micros(), result);
-    
-    let start = Instant::now();
-    let result = d11::get_solution_2();
-    println!("Day 11.2\t{}us\t{}", start.elapsed().as_micros(), result);
-    
-    let start = Instant::now();
-    let result = d12::get_solution_1();
-    println!("Day 12.1\t{}us\t{}", start.elapsed().as_micros(), result);
-    
-    let start = Instant::now();
-    let result = d12::get_solution_2();
-    println!("Day 12.2\t{}us\t{}", start.elapsed().as_micros(), result);
-    
-    let start = Instant::now();
-    let result = d13::get_solution_1();
-    println!("Day 13.1\t{}us\t{}", start.elapsed().as_micros(), result);
-    
-    let start = Instant::now();
-    let result = d13::get_solution_2();
-    println!("Day 13.2\t{}us\t{}", start.elapsed().as_micros(), result);
-    
-    let start = Instant::now();
-    let result = d14::get_solution_1();
-    println!("Day 14.1\t{}us\t{}", start.elapsed().as_micros(), result);
-    
-    let start = Instant::now();
-    let result = d14::get_solution_2();
-    println!("Day 14.2\t{}us\t{}", start.elapsed().as_micros(), result);
-    
-    let start = Instant::now();
-    let result = d15::get_solution_1();
-    println!("Day 15.1\t{}us\t{}", start.elapsed().as_micros(), result);
-    
-    let start = Instant::now();
-    let result = d15::get_solution_2();
-    println!("Day 15.2\t{}us\t{}", start.elapsed().as_micros(), result);
-    
-    let start = Instant::now();
-    let result = d16::get_solution_1();
-    println!("Day 16.1\t{}us\t{}", start.elapsed().as_micros(), result);
-    
-    let start = Instant::now();
-    let result = d16::get_solution_2();
-    println!("Day 16.2\t{}us\t{}", start.elapsed().as_micros(), result);
-    
-    let start = Instant::now();
-    let result = d17::get_solution_1();
-    println!("Day 17.1\t{}us\t{}", start.elapsed().as_micros(), result);
-    
-    let start = Instant::now();
-    let result = d17::get_solution_2();
-    println!("Day 17.2\t{}us\t{}", start.elapsed().as_micros(), result);
-    
-    let start = Instant::now();
-    let result = d18::get_solution_1();
-    println!("Day 18.1\t{}us\t{}", start.elapsed().as_micros(), result);
+// runs the days one after the other, parsing every input once for all of its parts
+fn run_serial(session: &mut Session) {
+    let bench = session.options.bench.as_ref();
+
+    for &day in session.options.selection.days() {
+        let Some((entry, parts, input)) = read_day(session, day) else { continue };
+        let params = session.answers.params(day);
+        let input_hash = input_hash(&input, &params);
+        let parts = session.take_cached(entry, parts, input_hash);
+        if parts.is_empty() {
+            continue;
+        }
+        let _span = trace::span(format!("day {}", day));
+
+        let start = Instant::now();
+        let (parsed, parse_memory) = counted(session.options.memory, || {
+            let _span = trace::span("parse");
+            entry.runner.parse_with(&input, &params)
+        });
+        let parsed = match parsed {
+            Ok(parsed) => parsed,
+            Err(e) => {
+                let runtime = start.elapsed();
+                let e = e.in_day(day);
+                report_parse_error(session, day, &input, &e);
+                for part in parts {
+                    let failure = Failure::Parse(e.clone());
+                    let row = format!("Day {:2}.{}\t{}us{}\t{}", day, part, runtime.as_micros(), memory_columns(parse_memory), failure);
+                    session.add(Record::new(day, part, Err(failure), runtime, parse_memory), row);
+                }
+                continue;
+            },
+        };
+        let parse_time = match bench {
+            Some(bench) => {
+                let stats = Stats::from_samples(&mut bench::measure(bench.runs, || entry.runner.parse_with(&input, &params).ok()));
+                let mut row = format!("Day {:2} parse\t{}", day, format_stats(&stats));
+                session.compare(day, Phase::Parse, stats.median, &mut row);
+                if session.text {
+                    println!("{}", row);
+                }
+                stats.median
+            },
+            None => start.elapsed(),
+        };
+
+        for part in parts {
+            let start = Instant::now();
+            let (answer, solve_memory) = counted(session.options.memory, || {
+                let _span = trace::span(format!("part {}", part));
+                parsed.solve(part)
+            });
+            let solve_time = start.elapsed();
+            let memory = parse_memory.zip(solve_memory).map(|(parse, solve)| parse.then(solve));
+
+            // the runtime of a part includes parsing the input, which is shared by both parts
+            let (row, runtime) = match bench {
+                Some(bench) => {
+                    let stats = Stats::from_samples(&mut bench::measure(bench.runs, || parsed.solve(part)));
+                    let mut row = format!("Day {:2}.{}\t{}\t{}", day, part, format_stats(&stats), answer);
+                    session.compare(day, Phase::Part(part), stats.median, &mut row);
+                    (row, parse_time + stats.median)
+                },
+                None => {
+                    let runtime = parse_time + solve_time;
+                    (format!("Day {:2}.{}\t{}us{}\t{}", day, part, runtime.as_micros(), memory_columns(memory), answer), runtime)
+                },
+            };
+            session.store(entry, part, input_hash, &answer, runtime);
+            session.add(Record::new(day, part, Ok(answer), runtime, memory), row);
+        }
+    }
+}
 
-    let start = Instant::now();
-    let result = d18::get_solution_2();
-    println!("Day 18.2\t{}us\t{}", start.elapsed().as_micros(), result);
-    
-    let start = Instant::now();
-    let result = d19::get_solution_1();
-    println!("Day 19.1\t{}us\t{}", start.elapsed().as_micros(), result);
+// runs f, counting its allocations with --memory
+fn counted<T>(memory: bool, f: impl FnOnce() -> T) -> (T, Option<Usage>) {
+    match memory {
+        true => {
+            let (result, usage) = memory::measure(f);
+            (result, Some(usage))
+        },
+        false => (f(), None),
+    }
+}
 
-    let start = Instant::now();
-    let result = d19::get_solution_2();
-    println!("Day 19.2\t{}us\t{}", start.elapsed().as_micros(), result);
+// the parameters change the answers as much as the input does
+fn input_hash(input: &str, params: &Params) -> u64 {
+    let params = params.iter().map(|(name, value)| format!("{}={}\n", name, value)).collect::<String>();
+    cache::hash_from(cache::hash(input.as_bytes()), params.as_bytes())
+}
 
-    let start = Instant::now();
-    let result = d20::get_solution_1();
-    println!("Day 20.1\t{}us\t{}", start.elapsed().as_micros(), result);
+// the allocs, bytes and peak columns of a row, nothing without --memory
+fn memory_columns(memory: Option<Usage>) -> String {
+    memory.map_or(String::new(), |m| format!("\t{}\t{}\t{}", m.allocations, Bytes(m.bytes), Bytes(m.peak)))
+}
 
-    let start = Instant::now();
-    let result = d20::get_solution_2();
-    println!("Day 20.2\t{}us\t{}", start.elapsed().as_micros(), result);
+// schedules every selected part on a pool of workers, the rows are printed once all parts are done
+fn run_pool(session: &mut Session, workers: usize, timeout: Option<Duration>) {
+    let mut jobs = Vec::new();
+    let mut inputs = HashMap::new();
+    let mut hashes = HashMap::new();
+    for &day in session.options.selection.days() {
+        let Some((entry, parts, input)) = read_day(session, day) else { continue };
+        let params = session.answers.params(day);
+        let input_hash = input_hash(&input, &params);
+        let parts = session.take_cached(entry, parts, input_hash);
+        if parts.is_empty() {
+            continue;
+        }
+        let input = Arc::<str>::from(input);
+        jobs.extend(parts.into_iter().map(|part| Job { day, part, runner: entry.runner, input: input.clone(), params: params.clone(), id: 0 }));
+        inputs.insert(day, input);
+        hashes.insert(day, (entry, input_hash));
+    }
+
+    for done in pool::run(jobs, workers, timeout) {
+        // every part parses the input on its own, the error is only shown once
+        if let Err(Failure::Parse(e)) = &done.answer {
+            if let Some(input) = inputs.remove(&done.day) {
+                report_parse_error(session, done.day, &input, e);
+            }
+        }
+        let row = match &done.answer {
+            Ok(answer) => {
+                let (entry, input_hash) = hashes[&done.day];
+                session.store(entry, done.part, input_hash, answer, done.runtime);
+                format!("Day {:2}.{}\t{}us\t{}", done.day, done.part, done.runtime.as_micros(), answer)
+            },
+            Err(failure) => format!("Day {:2}.{}\t{}us\t{}", done.day, done.part, done.runtime.as_micros(), failure),
+        };
+        session.add(Record::new(done.day, done.part, done.answer, done.runtime, None), row);
+    }
+}
 
-    let start = Instant::now();
-    let result = d21::get_solution_1();
-    println!("Day 21.1\t{}us\t{}", start.elapsed().as_micros(), result);
+// runs the selected parts on every input in DIR/dNN/*.txt, each checked against the answers recorded next to it
+fn run_matrix(options: &Options) -> ! {
+    let mut inputs: Vec<(Input, Answers)> = Vec::new();
+    let mut jobs = Vec::new();
+    let mut failed = false;
+
+    for &day in options.selection.days() {
+        let Some(entry) = options.entry(day) else { continue };
+        let parts = (1..=entry.runner.parts()).filter(|part| options.selection.contains(day, *part)).collect::<Vec<u8>>();
+        let day_inputs = matrix::inputs(&options.data_dir, day).unwrap_or_else(|e| {
+            eprintln!("error: could not list the inputs of day {}: {}", day, e);
+            failed = true;
+            Vec::new()
+        });
+
+        for input in day_inputs {
+            let content = match std::fs::read_to_string(&input.path) {
+                Ok(content) => Arc::<str>::from(content),
+                Err(e) => {
+                    eprintln!("error: could not read input from {}: {}", input.path.display(), e);
+                    failed = true;
+                    continue;
+                },
+            };
+            let answers = match Answers::load(&input.answers_path()) {
+                Ok(answers) => answers,
+                Err(e) => {
+                    eprintln!("error: could not load answers from {}: {}", input.answers_path().display(), e);
+                    failed = true;
+                    continue;
+                },
+            };
+            let id = inputs.len();
+            jobs.extend(parts.iter().map(|&part| Job { day, part, runner: entry.runner, input: content.clone(), params: answers.params(day), id }));
+            inputs.push((input, answers));
+        }
+    }
+    if jobs.is_empty() {
+        eprintln!("error: none of the selected days has inputs in {}", options.data_dir.join("dNN").display());
+        std::process::exit(1);
+    }
+
+    let (workers, timeout) = options.pool.as_ref().map_or((1, None), |pool| (pool.jobs, pool.timeout));
+    let start = Instant::now();
+    let mut matrix = Matrix::default();
+    let mut problems = Vec::new();
+    let mut recorded = vec![false; inputs.len()];
+
+    for done in pool::run(jobs, workers, timeout) {
+        let (input, answers) = &mut inputs[done.id];
+        let status = match (done.answer, options.mode) {
+            (Err(failure), _) => {
+                let reason = match &failure {
+                    Failure::Parse(e) => e.to_string(),
+                    failure => failure.to_string(),
+                };
+                problems.push(format!("{} part {}: {}", input.path.display(), done.part, reason));
+                failure.to_string()
+            },
+            (Ok(answer), Mode::Record) => {
+                answers.insert(done.day, done.part, answer.to_string());
+                recorded[done.id] = true;
+                "RECORDED".to_string()
+            },
+            (Ok(answer), _) => {
+                let verdict = answers.check(done.day, done.part, &answer.to_string());
+                if let Verdict::Fail(expected) = &verdict {
+                    problems.push(format!("{} part {}: expected {}, got {}", input.path.display(), done.part, expected, answer));
+                }
+                verdict.name().to_string()
+            },
+        };
+        matrix.insert(done.day, done.part, &input.name, status);
+    }
+
+    print!("{}", matrix.render());
+    problems.sort();
+    if !problems.is_empty() {
+        println!("\nFailed inputs:");
+        problems.iter().for_each(|problem| println!("  {}", problem));
+    }
+    println!("\nTotal runtime: {}ms", start.elapsed().as_millis());
+
+    for ((input, answers), _) in inputs.iter().zip(recorded).filter(|(_, recorded)| *recorded) {
+        match answers.save(&input.answers_path()) {
+            Ok(()) => println!("Recorded answers in {}", input.answers_path().display()),
+            Err(e) => {
+                eprintln!("error: could not write answers to {}: {}", input.answers_path().display(), e);
+                failed = true;
+            },
+        }
+    }
+    std::process::exit(if failed || !problems.is_empty() { 1 } else { 0 });
+}
 
-    let start = Instant::now();
-    let result = d21::get_solution_2();
-    println!("Day 21.2\t{}us\t{}", start.elapsed().as_micros(), result);
+// runs every implementation of the days on the real input, the examples and generated inputs
+fn run_compare(options: &Compare) -> ! {
+    let mut disagreements = 0;
+
+    for &day in &options.days {
+        let names = registry::implementations(day).map(|entry| entry.name).collect::<Vec<_>>();
+        let mut inputs = Vec::new();
+        let files = compare::inputs(&options.data_dir, day).unwrap_or_else(|e| {
+            eprintln!("error: could not list the inputs in {}: {}", options.data_dir.display(), e);
+            Vec::new()
+        });
+        for path in files {
+            match std::fs::read_to_string(&path) {
+                Ok(input) => inputs.push((path.display().to_string(), input)),
+                Err(e) => eprintln!("error: could not read input from {}: {}", path.display(), e),
+            }
+        }
+        if let Some(generator) = generate::for_day(day) {
+            for seed in 0..options.seeds {
+                inputs.push((format!("generated, seed {}", seed), generator.generate(seed, generator.default_size)));
+            }
+        }
+
+        let mut found = Vec::new();
+        for (source, input) in &inputs {
+            found.extend(compare::compare(day, input).into_iter().map(|disagreement| format!("  {}: {}", source, disagreement)));
+        }
+        match found.is_empty() {
+            true => println!("Day {:2}: {} agree on {} inputs", day, names.join(", "), inputs.len()),
+            false => {
+                println!("Day {:2}: {} disagree", day, names.join(", "));
+                found.iter().for_each(|line| println!("{}", line));
+            },
+        }
+        disagreements += found.len();
+    }
+    std::process::exit(if disagreements > 0 { 1 } else { 0 });
+}
 
-    let start = Instant::now();
-    let result = d22::get_solution_1();
-    println!("Day 22.1\t{}us\t{}", start.elapsed().as_micros(), result);
+// reads commands from stdin until it ends or one of them quits
+fn run_repl(options: &Repl) -> ! {
+    let day = options.explorer.day;
+    let input = options.source.read().unwrap_or_else(|e| {
+        eprintln!("error: could not read input from {}: {}", options.source, e);
+        std::process::exit(1);
+    });
+    let mut repl = repl::Repl::new(options.explorer, &input).unwrap_or_else(|e| {
+        eprintln!("error: could not parse {}: {}", options.source, e.in_day(day).diagnostic(&input));
+        std::process::exit(1);
+    });
+
+    println!("day {} loaded from {}, try help", day, options.source);
+    let mut lines = io::stdin().lock().lines();
+    loop {
+        print!("d{:02}> ", day);
+        let _ = io::stdout().flush();
+        let Some(Ok(line)) = lines.next() else {
+            println!();
+            break;
+        };
+        match repl.eval(&line) {
+            Some(reply) if reply.is_empty() => (),
+            Some(reply) => println!("{}", reply.trim_end()),
+            None => break,
+        }
+    }
+    std::process::exit(0);
+}
 
-    let start = Instant::now();
-    let result = d22::get_solution_2();
-    println!("Day 22.2\t{}us\t{}", start.elapsed().as_micros(), result);
+// records the frames of a day's simulation, then plays them or writes them to a file
+fn run_animate(options: &Animate) -> ! {
+    let day = options.film.day;
+    let input = options.source.read().unwrap_or_else(|e| {
+        eprintln!("error: could not read input from {}: {}", options.source, e);
+        std::process::exit(1);
+    });
+    let film = options.film.record(&input, options.every, options.frames).unwrap_or_else(|e| {
+        eprintln!("error: could not parse {}: {}", options.source, e.in_day(day).diagnostic(&input));
+        std::process::exit(1);
+    });
+    let palette = options.film.palette;
+    let fail = |path: &Path, e: &dyn std::fmt::Display| -> ! {
+        eprintln!("error: could not write {}: {}", path.display(), e);
+        std::process::exit(1);
+    };
+
+    match &options.output {
+        None => {
+            if let Err(e) = frames::play(&film, palette, options.fps, &mut io::stdout().lock()) {
+                eprintln!("error: {}", e);
+                std::process::exit(1);
+            }
+        },
+        Some(path) if path.extension().is_some_and(|e| e == "gif") => {
+            let delay = Duration::from_secs_f64(1.0 / options.fps as f64);
+            let gif = frames::gif(&film, palette, options.scale, delay).unwrap_or_else(|e| fail(path, &e));
+            std::fs::write(path, gif).unwrap_or_else(|e| fail(path, &e));
+            eprintln!("wrote {} frames to {}", film.len(), path.display());
+        },
+        Some(path) => {
+            let stem = path.with_extension("");
+            for (i, frame) in film.iter().enumerate() {
+                let path = PathBuf::from(format!("{}_{:04}.ppm", stem.display(), i));
+                std::fs::write(&path, frames::ppm(frame, palette, options.scale)).unwrap_or_else(|e| fail(&path, &e));
+            }
+            eprintln!("wrote {} frames to {}_NNNN.ppm", film.len(), stem.display());
+        },
+    }
+    std::process::exit(0);
+}
 
-    let start = Instant::now();
-    let result = d23::get_solution_1();
-    println!("Day 23.1\t{}us\t{}", start.elapsed().as_micros(), result);
+// copies the examples of saved puzzle pages into the data directory and records their answers
+fn run_import(import: &Import) -> ! {
+    let answers_path = import.answers_path();
+    let mut answers = Answers::load(&answers_path).unwrap_or_else(|e| {
+        eprintln!("error: could not load answers from {}: {}", answers_path.display(), e);
+        std::process::exit(1);
+    });
+    let mut recorded = false;
+    let mut failed = false;
+
+    for path in &import.pages {
+        let page = match std::fs::read_to_string(path).map_err(|e| e.to_string()).and_then(|content| page::parse(&content)) {
+            Ok(page) => page,
+            Err(e) => {
+                eprintln!("error: could not import {}: {}", path.display(), e);
+                failed = true;
+                continue;
+            },
+        };
+        println!("{}: day {}", path.display(), page.day);
+
+        // every dNN_test*.txt, including hand made ones like d24_test_min.txt
+        let prefix = format!("d{:02}_test", page.day);
+        let existing = std::fs::read_dir(&import.data_dir).into_iter().flatten().flatten()
+            .filter(|entry| entry.file_name().to_str().is_some_and(|name| name.starts_with(&prefix) && name.ends_with(".txt")))
+            .filter_map(|entry| std::fs::read_to_string(entry.path()).ok().map(|content| (entry.path(), content)))
+            .collect::<Vec<_>>();
+        let mut next = 1;
+        // the blocks the day can't parse show the puzzle's state rather than an input
+        let examples = page.examples.iter()
+            .filter(|example| registry::default_for(page.day).is_none_or(|entry| entry.runner.parse(example).is_ok()))
+            .collect::<Vec<_>>();
+        if examples.is_empty() {
+            println!("  no example input found");
+        }
+        for example in examples {
+            if let Some((file, _)) = existing.iter().find(|(_, content)| content.trim_end() == example.trim_end()) {
+                println!("  example already in {}", file.display());
+                continue;
+            }
+            while import.data_dir.join(page::example_file(page.day, next)).exists() {
+                next += 1;
+            }
+            let file = import.data_dir.join(page::example_file(page.day, next));
+            match std::fs::write(&file, example) {
+                Ok(()) => println!("  wrote example to {}", file.display()),
+                Err(e) => {
+                    eprintln!("error: could not write {}: {}", file.display(), e);
+                    failed = true;
+                },
+            }
+        }
+
+        for (part, answer) in (1..).zip(&page.answers) {
+            match answers.check(page.day, part, answer) {
+                Verdict::Pass => println!("  part {} answer {} already recorded", part, answer),
+                Verdict::Fail(expected) => {
+                    eprintln!("error: the page has {} as answer to part {}, {} has {}", answer, part, answers_path.display(), expected);
+                    failed = true;
+                },
+                Verdict::New => {
+                    println!("  recorded part {} answer {}", part, answer);
+                    answers.insert(page.day, part, answer.clone());
+                    recorded = true;
+                },
+            }
+        }
+    }
+
+    if recorded {
+        if let Err(e) = answers.save(&answers_path) {
+            eprintln!("error: could not write answers to {}: {}", answers_path.display(), e);
+            failed = true;
+        }
+    }
+    std::process::exit(if failed { 1 } else { 0 });
+}
 
-    let start = Instant::now();
-    let result = d23::get_solution_2();
-    println!("Day 23.2\t{}us\t{}", start.elapsed().as_micros(), result);
+// everything that happens with the results of the parts, independent of how they were run
+struct Session<'a> {
+    options: &'a Options,
+    // with --format the results are collected and printed at the end, so stdout only holds the report
+    text: bool,
+    answers: Answers,
+    answers_path: PathBuf,
+    baseline: Baseline,
+    // an existing baseline is compared against, otherwise this run becomes the baseline
+    compare_baseline: bool,
+    records: Vec<Record>,
+    // None with --no-cache and for runs that measure the solvers rather than want their answers
+    cache: Option<Cache>,
+    cache_changed: bool,
+    failed: bool,
+}
 
-    let start = Instant::now();
-    let result = d24::get_solution_1();
-    println!("Day 24.1\t{}us\t{}", start.elapsed().as_micros(), result);
+impl<'a> Session<'a> {
+    fn new(options: &'a Options) -> Self {
+        let answers_path = options.answers_path();
+        let answers = match options.mode {
+            // only for the parameters of the inputs
+            Mode::Run => Answers::load(&answers_path).unwrap_or_else(|e| {
+                eprintln!("warning: ignoring the answers in {}: {}", answers_path.display(), e);
+                Answers::default()
+            }),
+            Mode::Verify | Mode::Record => Answers::load(&answers_path).unwrap_or_else(|e| {
+                eprintln!("error: could not load answers from {}: {}", answers_path.display(), e);
+                std::process::exit(1);
+            }),
+        };
+
+        let baseline_path = options.bench.as_ref().and_then(|b| b.baseline.as_deref());
+        let compare_baseline = baseline_path.is_some_and(Path::exists);
+        let baseline = match baseline_path {
+            Some(path) if compare_baseline => Baseline::load(path).unwrap_or_else(|e| {
+                eprintln!("error: could not load baseline from {}: {}", path.display(), e);
+                std::process::exit(1);
+            }),
+            _ => Baseline::default(),
+        };
+
+        let measuring = options.bench.is_some() || options.memory || options.trace.is_some();
+        let cache = (options.cache && !measuring).then(|| {
+            let path = options.data_dir.join(CACHE_FILE);
+            Cache::load(&path).unwrap_or_else(|e| {
+                eprintln!("warning: ignoring the cache in {}: {}", path.display(), e);
+                Cache::default()
+            })
+        });
+
+        Self {
+            options,
+            text: options.format.is_none(),
+            answers,
+            answers_path,
+            baseline,
+            compare_baseline,
+            records: Vec::new(),
+            cache,
+            cache_changed: false,
+            failed: false,
+        }
+    }
+
+    // adds the parts whose answers are cached for this input and solver, the others are returned to be solved
+    fn take_cached(&mut self, entry: &Entry, parts: Vec<u8>, input_hash: u64) -> Vec<u8> {
+        let mut unsolved = Vec::new();
+        for part in parts {
+            let key = Key { day: entry.day, part, implementation: entry.name.to_string(), input: input_hash };
+            let Some(Cached { answer, runtime, .. }) = self.cache.as_ref().and_then(|cache| cache.get(&key, entry.version)).cloned() else {
+                unsolved.push(part);
+                continue;
+            };
+            let row = format!("Day {:2}.{}\tcached\t{}", entry.day, part, answer);
+            self.add(Record { cached: true, ..Record::new(entry.day, part, Ok(answer), runtime, None) }, row);
+        }
+        unsolved
+    }
+
+    fn store(&mut self, entry: &Entry, part: u8, input_hash: u64, answer: &Answer, runtime: Duration) {
+        if let Some(cache) = &mut self.cache {
+            let key = Key { day: entry.day, part, implementation: entry.name.to_string(), input: input_hash };
+            cache.insert(key, Cached { version: entry.version, answer: answer.clone(), runtime });
+            self.cache_changed = true;
+        }
+    }
+
+    // verifies or records the answer of a part and prints its row
+    fn add(&mut self, mut record: Record, mut row: String) {
+        let (day, part) = (record.day, record.part);
+        record.verdict = match (&record.answer, self.options.mode) {
+            (Err(_), _) => {
+                self.failed = true;
+                None
+            },
+            (Ok(answer), Mode::Verify) => {
+                let verdict = self.answers.check(day, part, &answer.to_string());
+                self.failed |= matches!(verdict, Verdict::Fail(_));
+                row += &format!("\t{}", verdict);
+                Some(verdict)
+            },
+            (Ok(answer), Mode::Record) => {
+                self.answers.insert(day, part, answer.to_string());
+                None
+            },
+            (Ok(_), Mode::Run) => None,
+        };
+
+        if self.text {
+            println!("{}", row);
+        }
+        self.records.push(record);
+    }
+
+    // records the median when creating a baseline, otherwise appends a note to the row if it got slower
+    fn compare(&mut self, day: u8, phase: Phase, median: Duration, row: &mut String) {
+        if !self.compare_baseline {
+            self.baseline.insert(day, phase, median);
+            return;
+        }
+
+        let threshold = self.options.bench.as_ref().map_or(bench::DEFAULT_THRESHOLD, |b| b.threshold);
+        if let Some(change) = self.baseline.change(day, phase, median).filter(|change| *change > threshold) {
+            *row += &format!("\tREGRESSION {:+.1}%", change);
+            self.failed = true;
+        }
+    }
+
+    fn finish(self, wall_time: Duration) -> ! {
+        match self.options.format {
+            Some(format) => print!("{}", report::render(format, &self.records)),
+            // parts running in parallel add up to more than the time that passed
+            None if self.options.pool.is_some() => println!("\nTotal runtime: {}ms wall, {}ms summed over all parts",
+                wall_time.as_millis(), self.records.iter().map(|r| r.runtime).sum::<Duration>().as_millis()),
+            None => println!("\nTotal runtime: {}ms", wall_time.as_millis()),
+        }
+
+        if self.options.mode == Mode::Record {
+            if let Err(e) = self.answers.save(&self.answers_path) {
+                eprintln!("error: could not write answers to {}: {}", self.answers_path.display(), e);
+                std::process::exit(1);
+            }
+            self.notice(&format!("Recorded answers in {}", self.answers_path.display()));
+        }
+
+        // the answers are only a shortcut, failing to save them doesn't fail the run
+        if let Some(cache) = self.cache.as_ref().filter(|_| self.cache_changed) {
+            let path = self.options.data_dir.join(CACHE_FILE);
+            if let Err(e) = cache.save(&path) {
+                eprintln!("warning: could not write the cache to {}: {}", path.display(), e);
+            }
+        }
+
+        let baseline_path = self.options.bench.as_ref().and_then(|b| b.baseline.as_ref());
+        if let Some(path) = baseline_path.filter(|_| !self.compare_baseline) {
+            if let Err(e) = self.baseline.save(path) {
+                eprintln!("error: could not write baseline to {}: {}", path.display(), e);
+                std::process::exit(1);
+            }
+            self.notice(&format!("Saved baseline in {}", path.display()));
+        }
+
+        if let Some(path) = &self.options.trace {
+            let events = trace::take();
+            let trace = match path.extension().is_some_and(|ext| ext == "json") {
+                true => trace::chrome(&events),
+                false => trace::folded(&events),
+            };
+            if let Err(e) = std::fs::write(path, trace) {
+                eprintln!("error: could not write trace to {}: {}", path.display(), e);
+                std::process::exit(1);
+            }
+            self.notice(&format!("Saved trace in {}", path.display()));
+        }
+
+        std::process::exit(if self.failed { 1 } else { 0 });
+    }
+
+    // keeps stdout clean for the report when using --format
+    fn notice(&self, msg: &str) {
+        if self.text {
+            println!("{}", msg);
+        } else {
+            eprintln!("{}", msg);
+        }
+    }
+}
 
-    let start = Instant::now();
-    let result = d24::get_solution_2();
-    println!("Day 24.2\t{}us\t{}", start.elapsed().as_micros(), result);
+fn report_parse_error(session: &Session, day: u8, input: &str, e: &ParseError) {
+    eprintln!("error: could not parse {}: {}", session.options.source(day), e.diagnostic(input));
+}
 
-    let start = Instant::now();
-    let result = d25::get_solution_1();
-    println!("Day 25.1\t{}us\t{}", start.elapsed().as_micros(), result);
+fn format_stats(stats: &Stats) -> String {
+    [stats.min, stats.median, stats.mean, stats.p95].map(bench::format_duration).join("\t")
+}
 
-    println!("\nTotal runtime: {}ms", start_all.elapsed().as_millis());
+fn exit_with_usage(msg: &str) -> ! {
+    eprintln!("error: {}\n\n{}", msg, cli::USAGE);
+    std::process::exit(2);
 }
@@ -0,0 +1,99 @@
+use std::{collections::{BTreeMap, BTreeSet}, io, path::{Path, PathBuf}};
+
+// one of several inputs of a day, e.g. data/d22/alice.txt.
+// its answers are recorded next to it in data/d22/alice.toml
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Input {
+    pub day: u8,
+    pub name: String,
+    pub path: PathBuf,
+}
+
+impl Input {
+    pub fn answers_path(&self) -> PathBuf {
+        self.path.with_extension("toml")
+    }
+}
+
+// the inputs in DIR/dNN/*.txt sorted by name, a day without a directory has none
+pub fn inputs(data_dir: &Path, day: u8) -> io::Result<Vec<Input>> {
+    let dir = data_dir.join(format!("d{:02}", day));
+    let entries = match std::fs::read_dir(&dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e),
+    };
+
+    let mut inputs = Vec::new();
+    for entry in entries {
+        let path = entry?.path();
+        if path.extension().is_some_and(|ext| ext == "txt") {
+            let name = path.file_stem().unwrap_or_default().to_string_lossy().to_string();
+            inputs.push(Input { day, name, path });
+        }
+    }
+    inputs.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(inputs)
+}
+
+// the status of every part on every input, one row per part and one column per input name
+#[derive(Debug, Default)]
+pub struct Matrix {
+    cells: BTreeMap<(u8, u8), BTreeMap<String, String>>,
+}
+
+impl Matrix {
+    pub fn insert(&mut self, day: u8, part: u8, input: &str, status: impl Into<String>) {
+        self.cells.entry((day, part)).or_default().insert(input.to_string(), status.into());
+    }
+
+    // inputs a day doesn't have are shown as '-'
+    pub fn render(&self) -> String {
+        let columns = self.cells.values().flat_map(|row| row.keys()).collect::<BTreeSet<_>>();
+        let widths = columns.iter()
+            .map(|name| self.cells.values().filter_map(|row| row.get(*name)).map(String::len).chain([name.len()]).max().unwrap_or(0))
+            .collect::<Vec<_>>();
+
+        let mut matrix = format!("{:<8}", "Day");
+        for (name, width) in columns.iter().zip(&widths) {
+            matrix += &format!("  {:<width$}", name, width = width);
+        }
+        for (&(day, part), row) in &self.cells {
+            matrix += &format!("\n{:<8}", format!("Day {:2}.{}", day, part));
+            for (name, width) in columns.iter().zip(&widths) {
+                matrix += &format!("  {:<width$}", row.get(*name).map_or("-", String::as_str), width = width);
+            }
+        }
+        matrix.lines().map(str::trim_end).collect::<Vec<_>>().join("\n") + "\n"
+    }
+}
+
+#[test]
+fn test_inputs() {
+    let dir = std::env::temp_dir().join(format!("aoc22-matrix-{}", std::process::id()));
+    std::fs::create_dir_all(dir.join("d22")).unwrap();
+    for file in ["bob.txt", "alice.txt", "alice.toml"] {
+        std::fs::write(dir.join("d22").join(file), "").unwrap();
+    }
+
+    let inputs = inputs(&dir, 22).unwrap();
+    assert_eq!(inputs.iter().map(|i| i.name.as_str()).collect::<Vec<_>>(), vec!["alice", "bob"]);
+    assert_eq!(inputs[0].answers_path(), dir.join("d22/alice.toml"));
+    assert_eq!(self::inputs(&dir, 21).unwrap(), Vec::new());
+
+    std::fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn test_render() {
+    let mut matrix = Matrix::default();
+    matrix.insert(22, 2, "alice", "PASS");
+    matrix.insert(22, 2, "bob", "TIMEOUT");
+    matrix.insert(1, 1, "alice", "FAIL");
+
+    assert_eq!(matrix.render(), "\
+Day       alice  bob
+Day  1.1  FAIL   -
+Day 22.2  PASS   TIMEOUT
+");
+}
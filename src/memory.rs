@@ -0,0 +1,137 @@
+// allocation counting for --memory, the runner installs Counting as its global allocator.
+// it counts nothing outside of measure, which keeps the other runs' timings as they were
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    fmt::Display,
+    sync::atomic::{AtomicBool, AtomicI64, AtomicU64, Ordering::Relaxed},
+};
+
+static COUNTING: AtomicBool = AtomicBool::new(false);
+static ALLOCATIONS: AtomicU64 = AtomicU64::new(0);
+static BYTES: AtomicU64 = AtomicU64::new(0);
+// live bytes since measuring started, memory allocated before and freed during it makes this negative
+static LIVE: AtomicI64 = AtomicI64::new(0);
+static PEAK: AtomicI64 = AtomicI64::new(0);
+
+pub struct Counting;
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() && COUNTING.load(Relaxed) {
+            allocated(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() && COUNTING.load(Relaxed) {
+            allocated(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        if COUNTING.load(Relaxed) {
+            LIVE.fetch_sub(layout.size() as i64, Relaxed);
+        }
+    }
+
+    // growing a vector counts as allocating its new size and freeing the old one
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() && COUNTING.load(Relaxed) {
+            LIVE.fetch_sub(layout.size() as i64, Relaxed);
+            allocated(new_size);
+        }
+        new_ptr
+    }
+}
+
+fn allocated(size: usize) {
+    ALLOCATIONS.fetch_add(1, Relaxed);
+    BYTES.fetch_add(size as u64, Relaxed);
+    let live = LIVE.fetch_add(size as i64, Relaxed) + size as i64;
+    PEAK.fetch_max(live, Relaxed);
+}
+
+// what was allocated while measuring, by every thread
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Usage {
+    pub allocations: u64,
+    pub bytes: u64,
+    // the most bytes alive at once, on top of what was alive before
+    pub peak: u64,
+    // the bytes still alive at the end, like a parsed input
+    pub retained: i64,
+}
+
+impl Usage {
+    // the usage of running next right after self, next's peak comes on top of what self retained
+    pub fn then(self, next: Usage) -> Usage {
+        Usage {
+            allocations: self.allocations + next.allocations,
+            bytes: self.bytes + next.bytes,
+            peak: self.peak.max((self.retained + next.peak as i64).max(0) as u64),
+            retained: self.retained + next.retained,
+        }
+    }
+}
+
+// counts the allocations of f, all zero if Counting isn't the global allocator.
+// measuring from several threads at once mixes up their counts
+pub fn measure<T>(f: impl FnOnce() -> T) -> (T, Usage) {
+    ALLOCATIONS.store(0, Relaxed);
+    BYTES.store(0, Relaxed);
+    LIVE.store(0, Relaxed);
+    PEAK.store(0, Relaxed);
+    COUNTING.store(true, Relaxed);
+    let result = f();
+    COUNTING.store(false, Relaxed);
+
+    let usage = Usage {
+        allocations: ALLOCATIONS.load(Relaxed),
+        bytes: BYTES.load(Relaxed),
+        peak: PEAK.load(Relaxed).max(0) as u64,
+        retained: LIVE.load(Relaxed),
+    };
+    (result, usage)
+}
+
+// a number of bytes in the largest unit that keeps it above 1, e.g. 512B, 1.5KiB or 20.3MiB
+pub struct Bytes(pub u64);
+
+impl Display for Bytes {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let units = ["KiB", "MiB", "GiB", "TiB"];
+        let mut size = self.0 as f64;
+        if self.0 < 1024 {
+            return write!(f, "{}B", self.0);
+        }
+        for unit in units {
+            size /= 1024.;
+            if size < 1024. || unit == units[units.len() - 1] {
+                return write!(f, "{:.1}{}", size, unit);
+            }
+        }
+        unreachable!()
+    }
+}
+
+#[test]
+fn test_then() {
+    let parse = Usage { allocations: 2, bytes: 100, peak: 80, retained: 60 };
+    let solve = Usage { allocations: 3, bytes: 50, peak: 40, retained: -10 };
+    assert_eq!(parse.then(solve), Usage { allocations: 5, bytes: 150, peak: 100, retained: 50 });
+    assert_eq!(Usage { peak: 500, ..parse }.then(solve).peak, 500);
+}
+
+#[test]
+fn test_bytes() {
+    assert_eq!(Bytes(512).to_string(), "512B");
+    assert_eq!(Bytes(1536).to_string(), "1.5KiB");
+    assert_eq!(Bytes(20 * 1024 * 1024 + 300 * 1024).to_string(), "20.3MiB");
+    assert_eq!(Bytes(3 << 50).to_string(), "3072.0TiB");
+}
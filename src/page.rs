@@ -0,0 +1,205 @@
+// a puzzle description saved from the website, either the html as downloaded
+// or a plain text dump of it like the ones a text browser writes
+#[derive(Debug, PartialEq, Eq)]
+pub struct Page {
+    pub day: u8,
+    // the blocks the description introduces as an example, in the order they appear
+    pub examples: Vec<String>,
+    // "Your puzzle answer was ..." of the parts that were solved when the page was saved
+    pub answers: Vec<String>,
+}
+
+pub fn parse(content: &str) -> Result<Page, String> {
+    let html = ["<html", "<article", "<pre><code>"].iter().any(|tag| content.contains(tag));
+    let text = if html { unescape(&strip_tags(content)) } else { content.to_string() };
+
+    let day = text.split("--- Day ").nth(1)
+        .and_then(|title| title.split_once(':'))
+        .and_then(|(day, _)| day.trim().parse::<u8>().ok())
+        .ok_or("no title like '--- Day 24: Blizzard Basin ---' found")?;
+
+    let (examples, answers) = if html { parse_html(content) } else { parse_text(&text) };
+    Ok(Page { day, examples, answers })
+}
+
+// the name of the nth example of a day in the data directory, starting at 1
+pub fn example_file(day: u8, n: usize) -> String {
+    match n {
+        1 => format!("d{:02}_test.txt", day),
+        n => format!("d{:02}_test_{}.txt", day, n),
+    }
+}
+
+// the descriptions also draw intermediate states in code blocks, examples are the
+// ones after a sentence like "For example:" or "Here is a more complex example:"
+fn introduces_example(paragraph: &str) -> bool {
+    let sentence = paragraph.trim().rsplit(['.', '?', '!']).next().unwrap_or_default();
+    sentence.ends_with(':') && sentence.to_lowercase().contains("example")
+}
+
+fn parse_html(content: &str) -> (Vec<String>, Vec<String>) {
+    let mut examples = Vec::new();
+    let mut rest = content;
+
+    while let Some((before, block)) = rest.split_once("<pre><code>") {
+        let Some((code, after)) = block.split_once("</code></pre>") else { break };
+        let paragraph = before.rsplit_once("<p>").map_or(before, |(_, p)| p);
+        if introduces_example(&strip_tags(paragraph)) {
+            examples.push(unescape(&strip_tags(code)));
+        }
+        rest = after;
+    }
+
+    let answers = content.split("Your puzzle answer was <code>")
+        .skip(1)
+        .filter_map(|answer| answer.split_once("</code>").map(|(answer, _)| unescape(answer)))
+        .collect();
+
+    (examples, answers)
+}
+
+// paragraphs are separated by empty lines. an example runs from the paragraph after its introduction up
+// to the next paragraph of prose, so examples with empty lines like the calories of day 1 stay whole
+fn parse_text(text: &str) -> (Vec<String>, Vec<String>) {
+    let lines = text.lines().collect::<Vec<_>>();
+    let mut examples = Vec::new();
+    let mut i = 0;
+    while i < lines.len() {
+        if is_blank(lines[i]) {
+            i += 1;
+            continue;
+        }
+        let start = i;
+        while i < lines.len() && !is_blank(lines[i]) {
+            i += 1;
+        }
+        if introduces_example(&lines[start..i].join("\n")) {
+            let (example, end) = example_at(&lines, i);
+            examples.extend(example);
+            i = end;
+        }
+    }
+
+    let answers = text.lines()
+        .filter_map(|line| line.split_once("Your puzzle answer was "))
+        .map(|(_, answer)| answer.trim().trim_end_matches('.').to_string())
+        .collect();
+
+    (examples, answers)
+}
+
+// the example starting at or after the given line and the line after it. dumps that indent code blocks
+// also end an example at the first line indented less than its first paragraph
+fn example_at(lines: &[&str], from: usize) -> (Option<String>, usize) {
+    let start = (from..lines.len()).find(|&i| !is_blank(lines[i])).unwrap_or(lines.len());
+    let indent = lines[start..].iter().take_while(|line| !is_blank(line)).map(|line| indentation(line)).min().unwrap_or(0);
+    let mut end = start;
+    for i in start..lines.len() {
+        if is_blank(lines[i]) {
+            continue;
+        }
+        if indentation(lines[i]) < indent || (i > start && is_blank(lines[i - 1]) && is_prose(lines[i])) {
+            break;
+        }
+        end = i + 1;
+    }
+
+    let example = (end > start).then(|| lines[start..end].join("\n") + "\n");
+    (example, end)
+}
+
+// a sentence that starts a paragraph, unlike the lines of examples such as "Monkey 0:" or "move 1 from 2 to 1"
+fn is_prose(line: &str) -> bool {
+    indentation(line) == 0 && line.trim_end().ends_with(['.', ':', '?', '!']) && line.split_whitespace().count() >= 4
+}
+
+fn is_blank(line: &str) -> bool {
+    line.trim().is_empty()
+}
+
+fn indentation(line: &str) -> usize {
+    line.len() - line.trim_start().len()
+}
+
+fn strip_tags(html: &str) -> String {
+    let mut text = String::new();
+    let mut in_tag = false;
+    for c in html.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' if in_tag => in_tag = false,
+            c if !in_tag => text.push(c),
+            _ => (),
+        }
+    }
+    text
+}
+
+fn unescape(html: &str) -> String {
+    html.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&")
+}
+
+#[test]
+fn test_parse_html() {
+    let page = parse("<html><body><main><article class=\"day-desc\"><h2>--- Day 1: Calorie Counting ---</h2>
+<p>For example, suppose the Elves end up with the following list:</p>
+<pre><code>1000
+<em>2000</em>
+
+3000
+</code></pre>
+<p>This list represents the Calories &amp; more:</p>
+<pre><code>not an example
+</code></pre>
+<p>Here is a more complex example:</p>
+<pre><code>&lt;3
+</code></pre>
+</article>
+<p>Your puzzle answer was <code>67450</code>.</p><p>Your puzzle answer was <code>2=-&gt;0</code>.</p>
+</main></body></html>").unwrap();
+
+    assert_eq!(page, Page {
+        day: 1,
+        examples: vec!["1000\n2000\n\n3000\n".to_string(), "<3\n".to_string()],
+        answers: vec!["67450".to_string(), "2=->0".to_string()],
+    });
+}
+
+#[test]
+fn test_parse_text() {
+    let page = parse(&std::fs::read_to_string(concat!(env!("CARGO_MANIFEST_DIR"), "/instr")).unwrap()).unwrap();
+    assert_eq!(page.day, 24);
+    assert_eq!(page.examples.len(), 2);
+    assert_eq!(page.examples[1].trim_end(), include_str!("../data/d24_test.txt"));
+    assert!(page.answers.is_empty());
+
+    let page = parse("--- Day 7: No Space Left On Device ---\n\nFor example:\n\n$ cd /\n$ ls\n\nYour puzzle answer was 1297159.\n").unwrap();
+    assert_eq!(page.examples, vec!["$ cd /\n$ ls\n"]);
+    assert_eq!(page.answers, vec!["1297159"]);
+
+    // examples with empty lines, up to the next paragraph of prose or the end of an indented block
+    let page = parse("--- Day 1: Calorie Counting ---\n\nFor example:\n\n1000\n2000\n\n3000\n\nThis list represents the Calories of the food.\n\nAnother example:\n\n    1\n\n    2\nnot code\n").unwrap();
+    assert_eq!(page.examples, vec!["1000\n2000\n\n3000\n", "    1\n\n    2\n"]);
+    assert!(parse("no title").is_err());
+    assert_eq!(example_file(24, 1), "d24_test.txt");
+    assert_eq!(example_file(9, 2), "d09_test_2.txt");
+}
+
+#[test]
+fn test_parse_text_examples() {
+    // every example of the data directory comes out whole when a text dump shows it
+    let data = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("data");
+    for file in std::fs::read_dir(data).unwrap() {
+        let path = file.unwrap().path();
+        if !path.file_name().unwrap().to_str().unwrap().contains("_test") || path.extension().is_none_or(|e| e != "txt") {
+            continue;
+        }
+        let example = std::fs::read_to_string(&path).unwrap();
+        let page = parse(&format!("--- Day 1: Test ---\n\nFor example:\n\n{}\n\nIn this example, the answer is 42.\n", example.trim_end())).unwrap();
+        assert_eq!(page.examples, vec![example.trim_end().to_string() + "\n"], "{}", path.display());
+    }
+}
@@ -0,0 +1,139 @@
+use std::{collections::VecDeque, fmt::Display, sync::{mpsc, Arc, Mutex}, thread, time::{Duration, Instant}};
+
+use crate::{error::ParseError, solution::{Answer, Params, Runner}, trace};
+
+// a single puzzle part, parsed and solved on its own so it can run next to any other part
+pub struct Job {
+    pub day: u8,
+    pub part: u8,
+    pub runner: &'static dyn Runner,
+    pub input: Arc<str>,
+    pub params: Params,
+    // tells apart several inputs of the same day, handed back unchanged in Done
+    pub id: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Failure {
+    Timeout,
+    Panic,
+    Parse(ParseError),
+}
+
+impl Display for Failure {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Failure::Timeout => write!(f, "TIMEOUT"),
+            Failure::Panic => write!(f, "PANIC"),
+            Failure::Parse(_) => write!(f, "PARSE ERROR"),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct Done {
+    pub day: u8,
+    pub part: u8,
+    pub id: usize,
+    pub answer: Result<Answer, Failure>,
+    // parsing and solving, or how long was waited for a part that failed
+    pub runtime: Duration,
+}
+
+// runs the jobs on `workers` threads and returns them ordered by day, part and id.
+// threads can't be cancelled, so every job gets a thread of its own which its worker waits on.
+// after a timeout the worker moves on to the next job and the part keeps running in the background
+// until the process exits, so more than `workers` parts can be running at once.
+pub fn run(jobs: Vec<Job>, workers: usize, timeout: Option<Duration>) -> Vec<Done> {
+    let queue = Mutex::new(jobs.into_iter().collect::<VecDeque<_>>());
+    let done = Mutex::new(Vec::new());
+
+    thread::scope(|s| {
+        for _ in 0..workers.max(1) {
+            s.spawn(|| {
+                while let Some(job) = queue.lock().unwrap().pop_front() {
+                    let result = run_job(job, timeout);
+                    done.lock().unwrap().push(result);
+                }
+            });
+        }
+    });
+
+    let mut done = done.into_inner().unwrap();
+    done.sort_by_key(|d| (d.day, d.part, d.id));
+    done
+}
+
+fn run_job(job: Job, timeout: Option<Duration>) -> Done {
+    let (day, part, id) = (job.day, job.part, job.id);
+    let (tx, rx) = mpsc::channel();
+    let start = Instant::now();
+
+    thread::spawn(move || {
+        let start = Instant::now();
+        let _span = trace::span(format!("day {} part {}", job.day, job.part));
+        let parsed = {
+            let _span = trace::span("parse");
+            job.runner.parse_with(&job.input, &job.params)
+        };
+        let answer = parsed
+            .map(|parsed| parsed.solve(job.part))
+            .map_err(|e| Failure::Parse(e.in_day(job.day)));
+        // the receiver is gone if the part timed out
+        let _ = tx.send((answer, start.elapsed()));
+    });
+
+    let received = match timeout {
+        Some(timeout) => rx.recv_timeout(timeout).map_err(|e| match e {
+            mpsc::RecvTimeoutError::Timeout => Failure::Timeout,
+            mpsc::RecvTimeoutError::Disconnected => Failure::Panic,
+        }),
+        None => rx.recv().map_err(|_| Failure::Panic),
+    };
+
+    match received {
+        Ok((answer, runtime)) => Done { day, part, id, answer, runtime },
+        Err(failure) => Done { day, part, id, answer: Err(failure), runtime: start.elapsed() },
+    }
+}
+
+#[cfg(test)]
+struct Slow;
+
+#[cfg(test)]
+impl crate::solution::Solution for Slow {
+    type Parsed<'a> = u64;
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, ParseError> {
+        input.parse().map_err(|_| ParseError::new(1, 1, "a number").found(input))
+    }
+
+    fn part_1(millis: &u64) -> Answer {
+        thread::sleep(Duration::from_millis(*millis));
+        Answer::from(*millis)
+    }
+
+    fn part_2(_: &u64) -> Answer {
+        panic!("part 2 is broken")
+    }
+}
+
+#[test]
+fn test_run() {
+    let runner: &'static dyn Runner = &crate::solution::Registered::<Slow>(std::marker::PhantomData);
+    let job = |day, part, input: &str| Job { day, part, runner, input: Arc::from(input), params: Params::new(), id: 0 };
+    let jobs = vec![job(3, 1, "5000"), job(2, 1, "20"), job(1, 1, "10"), job(1, 2, "10"), job(4, 1, "soon")];
+
+    let start = Instant::now();
+    let done = run(jobs, 2, Some(Duration::from_millis(500)));
+    // the timed out part still sleeps, it must not hold up the others nor the end of the run. the limit
+    // is generous for slow machines but well below the 5s the part sleeps
+    assert!(start.elapsed() < Duration::from_secs(4), "the run took {:?}", start.elapsed());
+
+    assert_eq!(done.iter().map(|d| (d.day, d.part)).collect::<Vec<_>>(), vec![(1, 1), (1, 2), (2, 1), (3, 1), (4, 1)]);
+    assert_eq!(done[0].answer, Ok(Answer::U64(10)));
+    assert_eq!(done[1].answer, Err(Failure::Panic));
+    assert_eq!(done[2].answer, Ok(Answer::U64(20)));
+    assert_eq!(done[3].answer, Err(Failure::Timeout));
+    assert_eq!(done[4].answer, Err(Failure::Parse(ParseError::new(1, 1, "a number").found("soon").in_day(4))));
+}
@@ -0,0 +1,100 @@
+use std::marker::PhantomData;
+
+use crate::cache;
+use crate::solution::{Registered, Runner};
+use crate::{d01, d02, d03, d04, d05, d06, d07, d08, d09, d10, d11, d12, d13,
+            d14, d15, d16, d17, d18, d19, d20, d21, d22, d23, d24, d25};
+
+pub struct Entry {
+    pub day: u8,
+    // tells the implementations of a day apart, the first one is called "default"
+    pub name: &'static str,
+    // the hash of the day's module and the library code every day can use, cached answers of a day
+    // stay valid while none of them change
+    pub version: u64,
+    pub runner: &'static dyn Runner,
+}
+
+// the modules the days share, a change to any of them can change the answers of every day.
+// the other days and the runner's own modules don't
+const SHARED_VERSION: u64 = cache::hash(concat!(
+    env!("CARGO_PKG_VERSION"),
+    include_str!("helpers.rs"),
+    include_str!("error.rs"),
+    include_str!("solution.rs"),
+).as_bytes());
+
+macro_rules! register {
+    ($day:expr, $name:literal, $module:ident :: $solution:ident) => (
+        Entry {
+            day: $day,
+            name: $name,
+            version: cache::hash_from(SHARED_VERSION, include_str!(concat!(stringify!($module), ".rs")).as_bytes()),
+            runner: &Registered::<$module::$solution>(PhantomData),
+        }
+    );
+    ($day:expr, $module:ident :: $solution:ident) => (
+        register!($day, "default", $module::$solution)
+    );
+}
+
+// every known solution, a day can have several implementations, the first one is used by default
+pub static REGISTRY: &[Entry] = &[
+    register!(1, d01::Day01),
+    register!(2, d02::Day02),
+    register!(3, d03::Day03),
+    register!(4, d04::Day04),
+    register!(5, d05::Day05),
+    register!(6, d06::Day06),
+    register!(7, d07::Day07),
+    register!(7, "hash-map", d07::Day07HashMap),
+    register!(8, d08::Day08),
+    register!(8, "4-bit", d08::Day08Packed),
+    register!(9, d09::Day09),
+    register!(10, d10::Day10),
+    register!(11, d11::Day11),
+    register!(12, d12::Day12),
+    register!(13, d13::Day13),
+    register!(14, d14::Day14),
+    register!(15, d15::Day15),
+    register!(16, d16::Day16),
+    register!(17, d17::Day17),
+    register!(18, d18::Day18),
+    register!(19, d19::Day19),
+    register!(20, d20::Day20),
+    register!(21, d21::Day21),
+    register!(22, d22::Day22),
+    register!(23, d23::Day23),
+    register!(23, "bit-grid", d23::Day23BitGrid),
+    register!(24, d24::Day24),
+    register!(25, d25::Day25),
+];
+
+pub fn default_for(day: u8) -> Option<&'static Entry> {
+    REGISTRY.iter().find(|entry| entry.day == day)
+}
+
+pub fn implementations(day: u8) -> impl Iterator<Item = &'static Entry> {
+    REGISTRY.iter().filter(move |entry| entry.day == day)
+}
+
+pub fn find(day: u8, name: &str) -> Option<&'static Entry> {
+    implementations(day).find(|entry| entry.name == name)
+}
+
+#[test]
+fn test_registry() {
+    for day in 1..=25 {
+        let entry = default_for(day).unwrap();
+        assert_eq!(entry.day, day);
+        assert_eq!(entry.runner.parts(), if day == 25 { 1 } else { 2 });
+        assert_eq!(entry.name, "default");
+
+        let names = implementations(day).map(|entry| entry.name).collect::<Vec<_>>();
+        assert!(names.iter().enumerate().all(|(i, name)| !names[..i].contains(name)), "day {} has the name {:?} twice", day, names);
+    }
+    assert_eq!(find(7, "hash-map").map(|entry| entry.day), Some(7));
+    assert!(find(7, "4-bit").is_none());
+    assert_ne!(default_for(7).unwrap().version, default_for(8).unwrap().version);
+    assert_eq!(default_for(7).unwrap().version, find(7, "hash-map").unwrap().version);
+}
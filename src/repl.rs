@@ -0,0 +1,166 @@
+// an interactive shell on the state of a day: step its simulation, show it with the day's own
+// renderer and ask it questions, e.g. for the size of a directory on day 7
+use crate::error::ParseError;
+use crate::{d07, d09, d11, d14, d17, d23, d24};
+
+// a day's state while exploring it, loaded from the input
+pub trait Explore {
+    // advances the simulation by n ticks and tells where it is now, None if the day has no ticks
+    fn step(&mut self, _n: usize) -> Option<String> {
+        None
+    }
+
+    fn show(&self) -> String;
+
+    // answers a query of the day, the first word is the name of one of its queries
+    fn query(&mut self, query: &[&str]) -> Result<String, String>;
+}
+
+pub struct Explorer {
+    pub day: u8,
+    // what a single step is, None if there is nothing to step through
+    pub tick: Option<&'static str>,
+    // how to ask each query and what it answers
+    pub queries: &'static [(&'static str, &'static str)],
+    load: for<'a> fn(&'a str) -> Result<Box<dyn Explore + 'a>, ParseError>,
+}
+
+impl Explorer {
+    pub fn load<'a>(&self, input: &'a str) -> Result<Box<dyn Explore + 'a>, ParseError> {
+        (self.load)(input)
+    }
+}
+
+pub static EXPLORERS: &[Explorer] = &[
+    Explorer {
+        day: 7,
+        tick: None,
+        queries: &[("size <dir>", "the total size of the files in a directory, e.g. size /a/e"), ("ls <dir>", "the files and directories in a directory")],
+        load: d07::explore,
+    },
+    Explorer {
+        day: 9,
+        tick: Some("a step of the head"),
+        queries: &[("knots", "the position of every knot, head first"), ("visited", "the number of positions the tail visited")],
+        load: d09::explore,
+    },
+    Explorer {
+        day: 11,
+        tick: Some("a round"),
+        queries: &[("items [<monkey>]", "the worry levels of the items every monkey or a single one holds"), ("inspected", "how many items every monkey inspected")],
+        load: d11::explore,
+    },
+    Explorer {
+        day: 14,
+        tick: Some("a unit of sand coming to rest"),
+        queries: &[("sand", "the units of sand at rest")],
+        load: d14::explore,
+    },
+    Explorer {
+        day: 17,
+        tick: Some("a rock coming to rest"),
+        queries: &[("height", "the height of the tower"), ("rocks", "the rocks at rest")],
+        load: d17::explore,
+    },
+    Explorer {
+        day: 23,
+        tick: Some("a round"),
+        queries: &[("empty", "the empty ground tiles in the rectangle around the elves"), ("elves", "the number of elves")],
+        load: d23::explore,
+    },
+    Explorer {
+        day: 24,
+        tick: Some("a minute"),
+        queries: &[("fastest", "the fewest minutes from the entrance to the exit, starting now")],
+        load: d24::explore,
+    },
+];
+
+pub fn for_day(day: u8) -> Option<&'static Explorer> {
+    EXPLORERS.iter().find(|e| e.day == day)
+}
+
+// a day's input loaded into its explorer, commands are evaluated one line at a time
+pub struct Repl<'a> {
+    explorer: &'static Explorer,
+    input: &'a str,
+    state: Box<dyn Explore + 'a>,
+}
+
+impl<'a> Repl<'a> {
+    pub fn new(explorer: &'static Explorer, input: &'a str) -> Result<Self, ParseError> {
+        Ok(Self { explorer, input, state: explorer.load(input)? })
+    }
+
+    // the reply to a line, None once it asks to quit
+    pub fn eval(&mut self, line: &str) -> Option<String> {
+        let words = line.split_whitespace().collect::<Vec<_>>();
+        let reply = match words[..] {
+            [] => String::new(),
+            ["quit" | "exit" | "q"] => return None,
+            ["help" | "h" | "?"] => self.help(),
+            ["show" | "p"] => self.state.show(),
+            ["reset"] => match self.explorer.load(self.input) {
+                Ok(state) => {
+                    self.state = state;
+                    "back at the start".to_string()
+                },
+                Err(e) => format!("error: {}", e),
+            },
+            ["step" | "s", ref n @ ..] => match n {
+                [] => self.step(1),
+                [n] => match n.parse() {
+                    Ok(n) => self.step(n),
+                    Err(_) => format!("invalid number of ticks '{}'", n),
+                },
+                _ => "step takes a single number of ticks".to_string(),
+            },
+            [name, ..] if self.explorer.queries.iter().any(|(query, _)| query.split(' ').next() == Some(name)) => {
+                self.state.query(&words).unwrap_or_else(|e| format!("error: {}", e))
+            },
+            [name, ..] => format!("unknown command '{}', try help", name),
+        };
+        Some(reply)
+    }
+
+    fn step(&mut self, n: usize) -> String {
+        self.state.step(n).unwrap_or_else(|| format!("day {} has nothing to step through", self.explorer.day))
+    }
+
+    fn help(&self) -> String {
+        let mut commands = vec![
+            ("show", "print the current state"),
+            ("reset", "go back to the parsed input"),
+            ("quit", "leave"),
+        ];
+        if let Some(tick) = self.explorer.tick {
+            // a tick is what the day simulates one at a time
+            commands.insert(0, ("step [<n>]", tick));
+        }
+        commands.extend(self.explorer.queries.iter().copied());
+
+        let width = commands.iter().map(|(command, _)| command.len()).max().unwrap_or(0);
+        commands.iter().map(|(command, help)| format!("{:width$}  {}", command, help, width = width)).collect::<Vec<_>>().join("\n")
+    }
+}
+
+#[test]
+fn test_repl() {
+    let mut repl = Repl::new(for_day(11).unwrap(), include_str!("../data/d11_test.txt")).unwrap();
+    assert_eq!(repl.eval("items 0"), Some("Monkey 0: 79, 98".to_string()));
+    assert_eq!(repl.eval("step"), Some("after round 1".to_string()));
+    assert_eq!(repl.eval("items 0"), Some("Monkey 0: 20, 23, 27, 26".to_string()));
+    assert_eq!(repl.eval("s 19"), Some("after round 20".to_string()));
+    assert_eq!(repl.eval("inspected").unwrap().lines().next(), Some("Monkey 0: 101"));
+    assert_eq!(repl.eval("items 9"), Some("error: there is no monkey 9".to_string()));
+    assert_eq!(repl.eval("step x"), Some("invalid number of ticks 'x'".to_string()));
+    assert_eq!(repl.eval("fly"), Some("unknown command 'fly', try help".to_string()));
+    assert_eq!(repl.eval("reset"), Some("back at the start".to_string()));
+    assert_eq!(repl.eval("items 0"), Some("Monkey 0: 79, 98".to_string()));
+    assert_eq!(repl.eval("quit"), None);
+
+    let mut repl = Repl::new(for_day(7).unwrap(), include_str!("../data/d07_test.txt")).unwrap();
+    assert_eq!(repl.eval("size /a/e"), Some("584".to_string()));
+    assert_eq!(repl.eval("step"), Some("day 7 has nothing to step through".to_string()));
+    assert!(!repl.eval("help").unwrap().contains("step"));
+}
@@ -0,0 +1,315 @@
+use std::{str::FromStr, time::Duration};
+
+use crate::{answers::Verdict, memory::Usage, pool::Failure, solution::Answer};
+
+// machine readable output of the runner, written once all days are done
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Json,
+    Csv,
+    Markdown,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "json" => Ok(Format::Json),
+            "csv" => Ok(Format::Csv),
+            "markdown" | "md" => Ok(Format::Markdown),
+            _ => Err(format!("unknown format '{}', expected json, csv or markdown", s)),
+        }
+    }
+}
+
+// the result of a single puzzle part
+#[derive(Debug, Clone, PartialEq)]
+pub struct Record {
+    pub day: u8,
+    pub part: u8,
+    pub answer: Result<Answer, Failure>,
+    // parsing and solving, which is the median of both with --bench
+    pub runtime: Duration,
+    // only set when verifying
+    pub verdict: Option<Verdict>,
+    // only set with --memory, parsing and solving like the runtime
+    pub memory: Option<Usage>,
+    // the answer and runtime were taken from the cache of an earlier run
+    pub cached: bool,
+}
+
+impl Record {
+    // a part that was just run, its verdict is filled in once it's checked
+    pub fn new(day: u8, part: u8, answer: Result<Answer, Failure>, runtime: Duration, memory: Option<Usage>) -> Self {
+        Record { day, part, answer, runtime, verdict: None, memory, cached: false }
+    }
+
+    // a part that did not finish has no verdict, its failure is reported instead
+    fn status(&self) -> Option<String> {
+        match &self.answer {
+            Err(failure) => Some(failure.to_string()),
+            Ok(_) => self.verdict.as_ref().map(|v| v.name().to_string()),
+        }
+    }
+
+    fn kind(&self) -> &'static str {
+        self.answer.as_ref().map_or("", Answer::kind)
+    }
+
+    fn answer_string(&self) -> String {
+        self.answer.as_ref().map_or(String::new(), Answer::to_string)
+    }
+}
+
+pub fn render(format: Format, records: &[Record]) -> String {
+    match format {
+        Format::Json => render_json(records),
+        Format::Csv => render_csv(records),
+        Format::Markdown => render_markdown(records),
+    }
+}
+
+fn with_status(records: &[Record]) -> bool {
+    records.iter().any(|r| r.status().is_some())
+}
+
+fn with_memory(records: &[Record]) -> bool {
+    records.iter().any(|r| r.memory.is_some())
+}
+
+fn with_cached(records: &[Record]) -> bool {
+    records.iter().any(|r| r.cached)
+}
+
+fn render_json(records: &[Record]) -> String {
+    let objects = records.iter()
+        .map(|r| {
+            let mut fields = vec![
+                format!("\"day\": {}", r.day),
+                format!("\"part\": {}", r.part),
+                format!("\"answer\": {}", r.answer.as_ref().map_or("null".to_string(), json_answer)),
+                format!("\"type\": {}", r.answer.as_ref().map_or("null".to_string(), |a| format!("\"{}\"", a.kind()))),
+                format!("\"runtime_ns\": {}", r.runtime.as_nanos()),
+            ];
+            if let Some(memory) = r.memory {
+                fields.push(format!("\"allocations\": {}", memory.allocations));
+                fields.push(format!("\"allocated_bytes\": {}", memory.bytes));
+                fields.push(format!("\"peak_bytes\": {}", memory.peak));
+            }
+            if r.cached {
+                fields.push("\"cached\": true".to_string());
+            }
+            if let Some(status) = r.status() {
+                fields.push(format!("\"status\": \"{}\"", status));
+            }
+            if let (Ok(_), Some(Verdict::Fail(expected))) = (&r.answer, &r.verdict) {
+                fields.push(format!("\"expected\": {}", json_string(expected)));
+            }
+            format!("  {{{}}}", fields.join(", "))
+        })
+        .collect::<Vec<_>>();
+
+    if objects.is_empty() {
+        "[]\n".to_string()
+    } else {
+        format!("[\n{}\n]\n", objects.join(",\n"))
+    }
+}
+
+// numbers stay numbers, unless json has no way to write them. no answer is null, its type tells it apart
+// from a part that failed
+fn json_answer(answer: &Answer) -> String {
+    match answer {
+        Answer::Str(s) => json_string(s),
+        Answer::None => "null".to_string(),
+        Answer::F64(n) if !n.is_finite() => json_string(&n.to_string()),
+        n => n.to_string(),
+    }
+}
+
+pub(crate) fn json_string(s: &str) -> String {
+    let mut quoted = String::from('"');
+    for c in s.chars() {
+        match c {
+            '"' => quoted += "\\\"",
+            '\\' => quoted += "\\\\",
+            '\n' => quoted += "\\n",
+            c if c.is_control() => quoted += &format!("\\u{:04x}", c as u32),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+fn render_csv(records: &[Record]) -> String {
+    let status = with_status(records);
+    let memory = with_memory(records);
+    let cached = with_cached(records);
+    let mut csv = String::from("day,part,answer,type,runtime_ns");
+    if memory {
+        csv += ",allocations,allocated_bytes,peak_bytes";
+    }
+    if cached {
+        csv += ",cached";
+    }
+    if status {
+        csv += ",status";
+    }
+    csv.push('\n');
+
+    for r in records {
+        csv += &format!("{},{},{},{},{}", r.day, r.part, csv_field(&r.answer_string()), r.kind(), r.runtime.as_nanos());
+        if memory {
+            csv += &r.memory.map_or(",,,".to_string(), |m| format!(",{},{},{}", m.allocations, m.bytes, m.peak));
+        }
+        if cached {
+            csv += &format!(",{}", r.cached);
+        }
+        if status {
+            csv += &format!(",{}", r.status().unwrap_or_default());
+        }
+        csv.push('\n');
+    }
+
+    csv
+}
+
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+fn render_markdown(records: &[Record]) -> String {
+    let status = with_status(records);
+    let memory = with_memory(records);
+    let cached = with_cached(records);
+    let mut md = String::from("| Day | Part | Answer | Type | Runtime (ns) |");
+    if memory {
+        md += " Allocations | Allocated (bytes) | Peak (bytes) |";
+    }
+    if cached {
+        md += " Cached |";
+    }
+    md += if status { " Status |\n" } else { "\n" };
+    md += "|----:|-----:|--------|------|-------------:|";
+    if memory {
+        md += "------------:|------------------:|-------------:|";
+    }
+    if cached {
+        md += "--------|";
+    }
+    md += if status { "--------|\n" } else { "\n" };
+
+    for r in records {
+        let answer = match &r.answer {
+            Ok(answer) => format!("`{}`", answer.to_string().replace('|', "\\|").replace('\n', "<br>")),
+            Err(_) => String::new(),
+        };
+        md += &format!("| {} | {} | {} | {} | {} |", r.day, r.part, answer, r.kind(), r.runtime.as_nanos());
+        if memory {
+            md += &r.memory.map_or(" |  |  |".to_string(), |m| format!(" {} | {} | {} |", m.allocations, m.bytes, m.peak));
+        }
+        if cached {
+            md += if r.cached { " yes |" } else { "  |" };
+        }
+        if status {
+            md += &format!(" {} |", r.status().unwrap_or_default());
+        }
+        md.push('\n');
+    }
+
+    md
+}
+
+#[cfg(test)]
+fn test_records() -> Vec<Record> {
+    vec![
+        Record { day: 1, part: 1, answer: Ok(Answer::from(67450_usize)), runtime: Duration::from_nanos(1500), verdict: Some(Verdict::Pass), memory: None, cached: false },
+        Record { day: 5, part: 2, answer: Ok(Answer::from("A,\"B\"".to_string())), runtime: Duration::from_nanos(20), verdict: Some(Verdict::Fail("AB".to_string())), memory: None, cached: false },
+        Record { day: 21, part: 1, answer: Ok(Answer::from(3.5)), runtime: Duration::from_nanos(7), verdict: None, memory: None, cached: false },
+        Record { day: 19, part: 1, answer: Err(Failure::Timeout), runtime: Duration::from_secs(1), verdict: None, memory: None, cached: false },
+    ]
+}
+
+#[test]
+fn test_format() {
+    assert_eq!("json".parse(), Ok(Format::Json));
+    assert_eq!("md".parse(), Ok(Format::Markdown));
+    assert!("yaml".parse::<Format>().is_err());
+}
+
+#[test]
+fn test_render_json() {
+    assert_eq!(render(Format::Json, &[]), "[]\n");
+    assert_eq!(render(Format::Json, &test_records()), "[
+  {\"day\": 1, \"part\": 1, \"answer\": 67450, \"type\": \"usize\", \"runtime_ns\": 1500, \"status\": \"PASS\"},
+  {\"day\": 5, \"part\": 2, \"answer\": \"A,\\\"B\\\"\", \"type\": \"string\", \"runtime_ns\": 20, \"status\": \"FAIL\", \"expected\": \"AB\"},
+  {\"day\": 21, \"part\": 1, \"answer\": 3.5, \"type\": \"f64\", \"runtime_ns\": 7},
+  {\"day\": 19, \"part\": 1, \"answer\": null, \"type\": null, \"runtime_ns\": 1000000000, \"status\": \"TIMEOUT\"}
+]
+");
+
+    let unreachable = Record { day: 12, part: 1, answer: Ok(Answer::None), runtime: Duration::from_nanos(3), verdict: None, memory: None, cached: false };
+    assert_eq!(render(Format::Json, &[unreachable]), "[
+  {\"day\": 12, \"part\": 1, \"answer\": null, \"type\": \"none\", \"runtime_ns\": 3}
+]
+");
+}
+
+#[test]
+fn test_render_csv() {
+    assert_eq!(render(Format::Csv, &test_records()), "\
+day,part,answer,type,runtime_ns,status
+1,1,67450,usize,1500,PASS
+5,2,\"A,\"\"B\"\"\",string,20,FAIL
+21,1,3.5,f64,7,
+19,1,,,1000000000,TIMEOUT
+");
+    assert_eq!(render(Format::Csv, &test_records()[2..3]), "day,part,answer,type,runtime_ns\n21,1,3.5,f64,7\n");
+}
+
+#[test]
+fn test_render_markdown() {
+    assert_eq!(render(Format::Markdown, &test_records()[..1]), "\
+| Day | Part | Answer | Type | Runtime (ns) | Status |
+|----:|-----:|--------|------|-------------:|--------|
+| 1 | 1 | `67450` | usize | 1500 | PASS |
+");
+    assert!(render(Format::Markdown, &test_records()[3..]).ends_with("| 19 | 1 |  |  | 1000000000 | TIMEOUT |\n"));
+}
+
+#[test]
+fn test_render_memory() {
+    let memory = Some(Usage { allocations: 3, bytes: 4096, peak: 1024, retained: 0 });
+    let records = [Record { memory, ..test_records()[2].clone() }, test_records()[3].clone()];
+    assert!(render(Format::Json, &records).contains("\"runtime_ns\": 7, \"allocations\": 3, \"allocated_bytes\": 4096, \"peak_bytes\": 1024}"));
+    assert_eq!(render(Format::Csv, &records), "\
+day,part,answer,type,runtime_ns,allocations,allocated_bytes,peak_bytes,status
+21,1,3.5,f64,7,3,4096,1024,
+19,1,,,1000000000,,,,TIMEOUT
+");
+    assert!(render(Format::Markdown, &records[..1]).ends_with("| 21 | 1 | `3.5` | f64 | 7 | 3 | 4096 | 1024 |\n"));
+}
+
+#[test]
+fn test_render_cached() {
+    let records = [Record { cached: true, ..test_records()[0].clone() }, test_records()[2].clone()];
+    assert!(render(Format::Json, &records).contains("\"runtime_ns\": 1500, \"cached\": true, \"status\": \"PASS\"}"));
+    assert_eq!(render(Format::Csv, &records), "\
+day,part,answer,type,runtime_ns,cached,status
+1,1,67450,usize,1500,true,PASS
+21,1,3.5,f64,7,false,
+");
+    assert_eq!(render(Format::Markdown, &records), "\
+| Day | Part | Answer | Type | Runtime (ns) | Cached | Status |
+|----:|-----:|--------|------|-------------:|--------|--------|
+| 1 | 1 | `67450` | usize | 1500 | yes | PASS |
+| 21 | 1 | `3.5` | f64 | 7 |  |  |
+");
+    assert!(!render(Format::Csv, &test_records()).contains("cached"));
+}
@@ -0,0 +1,168 @@
+// generates the module of a new day and hooks it up, run from the root of the crate
+use std::{fs, path::Path};
+
+use aoc22::answers::Answers;
+
+// the placeholder expected for both parts of the example, which makes tests/examples.rs
+// fail until the real answers are filled in
+static TODO: &str = "TODO";
+
+static TEMPLATE: &str = "\
+use crate::error::{self, ParseError};
+use crate::solution::{Answer, Solution};
+
+static _TEST: &str = include_str!(\"../data/dNN_test.txt\");
+
+pub fn parse(input: &str) -> Result<Vec<&str>, ParseError> {
+    Ok(error::lines(input).map(|line| line.text).collect())
+}
+
+pub struct DayNN;
+
+impl Solution for DayNN {
+    type Parsed<'a> = Vec<&'a str>;
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, ParseError> {
+        parse(input)
+    }
+
+    fn part_1(lines: &Vec<&str>) -> Answer {
+        lines.len().into()
+    }
+
+    fn part_2(lines: &Vec<&str>) -> Answer {
+        lines.len().into()
+    }
+}
+";
+
+// returns what was done, one line per file
+pub(crate) fn new_day(day: u8) -> Result<Vec<String>, String> {
+    let name = format!("d{:02}", day);
+    let module = Path::new("src").join(format!("{}.rs", name));
+    if module.exists() {
+        return Err(format!("{} already exists", module.display()));
+    }
+
+    // everything is checked before the first file is written
+    let lib = edit(Path::new("src/lib.rs"), |lib| add_module(lib, day))?;
+    let registry = edit(Path::new("src/registry.rs"), |registry| add_to_registry(registry, day))?;
+    let examples_path = Path::new("data/answers_test.toml");
+    let mut examples = Answers::load(examples_path).map_err(|e| format!("could not load {}: {}", examples_path.display(), e))?;
+
+    let mut done = Vec::new();
+    write(&module, &TEMPLATE.replace("NN", &format!("{:02}", day)))?;
+    done.push(format!("created {}", module.display()));
+    for data in [format!("data/{}.txt", name), format!("data/{}_test.txt", name)] {
+        if !Path::new(&data).exists() {
+            write(Path::new(&data), "")?;
+            done.push(format!("created {}", data));
+        }
+    }
+    for (path, content) in [("src/lib.rs", lib), ("src/registry.rs", registry)] {
+        write(Path::new(path), &content)?;
+        done.push(format!("registered {} in {}", name, path));
+    }
+    for part in 1..=2 {
+        if examples.get(day, part).is_none() {
+            examples.insert(day, part, TODO.to_string());
+        }
+    }
+    examples.save(examples_path).map_err(|e| format!("could not write {}: {}", examples_path.display(), e))?;
+    done.push(format!("added the example answers to {}, replace the {} placeholders", examples_path.display(), TODO));
+
+    Ok(done)
+}
+
+fn edit(path: &Path, f: impl FnOnce(&str) -> Result<String, String>) -> Result<String, String> {
+    let content = fs::read_to_string(path).map_err(|e| format!("could not read {}: {}", path.display(), e))?;
+    f(&content).map_err(|e| format!("{}: {}", path.display(), e))
+}
+
+fn write(path: &Path, content: &str) -> Result<(), String> {
+    fs::write(path, content).map_err(|e| format!("could not write {}: {}", path.display(), e))
+}
+
+// the day of a line like `pub mod d07;` or `    register!(7, d07::Day07),`
+fn module_day(line: &str) -> Option<u8> {
+    let line = line.trim();
+    let name = line.strip_prefix("pub mod d").and_then(|l| l.strip_suffix(';'))
+        .or_else(|| line.strip_prefix("register!(").and_then(|l| l.split_once(',')).map(|(day, _)| day))?;
+    name.trim().parse().ok()
+}
+
+// inserts the line so that the days stay sorted, in front of the first later day or after the last one.
+// without any days yet it goes in front of the line `end`, or at the end of the file
+fn insert_sorted(content: &str, day: u8, new_line: &str, end: Option<&str>) -> Result<String, String> {
+    let lines = content.lines().collect::<Vec<_>>();
+    let days = lines.iter().enumerate().filter_map(|(i, line)| module_day(line).map(|d| (i, d))).collect::<Vec<_>>();
+    if days.iter().any(|(_, d)| *d == day) {
+        return Err(format!("day {} is already there", day));
+    }
+
+    let at = match days.iter().find(|(_, d)| *d > day) {
+        Some((i, _)) => *i,
+        None => match days.last() {
+            Some((i, _)) => i + 1,
+            None => end.and_then(|end| lines.iter().position(|line| line.trim() == end)).unwrap_or(lines.len()),
+        },
+    };
+
+    let mut result = lines[..at].to_vec();
+    result.push(new_line);
+    result.extend(&lines[at..]);
+    Ok(result.join("\n") + "\n")
+}
+
+fn add_module(lib: &str, day: u8) -> Result<String, String> {
+    insert_sorted(lib, day, &format!("pub mod d{:02};", day), None)
+}
+
+fn add_to_registry(registry: &str, day: u8) -> Result<String, String> {
+    let registry = insert_sorted(registry, day, &format!("    register!({}, d{:02}::Day{:02}),", day, day, day), Some("];"))?;
+
+    // the modules are imported in one `use crate::{d01, d02, ...};`
+    let start = registry.find("use crate::{d").ok_or("no `use crate::{d01, ...}` found")?;
+    let end = start + registry[start..].find("};").ok_or("unterminated `use crate::{...}`")? + 2;
+    let mut modules = registry[start + "use crate::{".len()..end - 2].split(',').map(str::trim).collect::<Vec<_>>();
+    let module = format!("d{:02}", day);
+    modules.push(&module);
+    modules.sort();
+
+    let rows = modules.chunks(13).map(|row| row.join(", ")).collect::<Vec<_>>();
+    let import = format!("use crate::{{{}}};", rows.join(",\n            "));
+    Ok(format!("{}{}{}", &registry[..start], import, &registry[end..]))
+}
+
+#[test]
+fn test_add_module() {
+    let lib = "pub mod answers;\nmod toml;\npub mod d01;\npub mod d03;\n";
+    assert_eq!(add_module(lib, 2).unwrap(), "pub mod answers;\nmod toml;\npub mod d01;\npub mod d02;\npub mod d03;\n");
+    assert_eq!(add_module(lib, 4).unwrap(), "pub mod answers;\nmod toml;\npub mod d01;\npub mod d03;\npub mod d04;\n");
+    assert!(add_module(lib, 3).is_err());
+}
+
+#[test]
+fn test_add_to_registry() {
+    let registry = "\
+use crate::{d01, d02, d03, d04, d05, d06, d07, d08, d09, d10, d11, d12, d13,
+            d14, d16};
+
+pub static REGISTRY: &[Entry] = &[
+    register!(1, d01::Day01),
+    register!(16, d16::Day16),
+];
+";
+    assert_eq!(add_to_registry(registry, 15).unwrap(), "\
+use crate::{d01, d02, d03, d04, d05, d06, d07, d08, d09, d10, d11, d12, d13,
+            d14, d15, d16};
+
+pub static REGISTRY: &[Entry] = &[
+    register!(1, d01::Day01),
+    register!(15, d15::Day15),
+    register!(16, d16::Day16),
+];
+");
+    assert!(add_to_registry(registry, 16).is_err());
+}
+
@@ -0,0 +1,171 @@
+use std::{collections::BTreeMap, fmt::Display, marker::PhantomData};
+
+use crate::error::ParseError;
+
+// the result of a single puzzle part, keeps the type the solver returned
+#[derive(Debug, Clone, PartialEq)]
+pub enum Answer {
+    Usize(usize),
+    Isize(isize),
+    U32(u32),
+    U64(u64),
+    I64(i64),
+    F64(f64),
+    Str(String),
+    // a part that has no answer on the input, e.g. a goal that can't be reached
+    None,
+}
+
+impl Answer {
+    // name of the type the solver returned
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Answer::Usize(_) => "usize",
+            Answer::Isize(_) => "isize",
+            Answer::U32(_) => "u32",
+            Answer::U64(_) => "u64",
+            Answer::I64(_) => "i64",
+            Answer::F64(_) => "f64",
+            Answer::Str(_) => "string",
+            Answer::None => "none",
+        }
+    }
+
+    // the answer of the type kind() names, written the way Display writes it
+    pub fn parse(kind: &str, answer: &str) -> Option<Answer> {
+        match kind {
+            "usize" => answer.parse().ok().map(Answer::Usize),
+            "isize" => answer.parse().ok().map(Answer::Isize),
+            "u32" => answer.parse().ok().map(Answer::U32),
+            "u64" => answer.parse().ok().map(Answer::U64),
+            "i64" => answer.parse().ok().map(Answer::I64),
+            "f64" => answer.parse().ok().map(Answer::F64),
+            "string" => Some(Answer::Str(answer.to_string())),
+            "none" if answer == "none" => Some(Answer::None),
+            _ => None,
+        }
+    }
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Answer::Usize(n) => write!(f, "{}", n),
+            Answer::Isize(n) => write!(f, "{}", n),
+            Answer::U32(n) => write!(f, "{}", n),
+            Answer::U64(n) => write!(f, "{}", n),
+            Answer::I64(n) => write!(f, "{}", n),
+            Answer::F64(n) => write!(f, "{}", n),
+            Answer::Str(s) => write!(f, "{}", s),
+            Answer::None => write!(f, "none"),
+        }
+    }
+}
+
+macro_rules! impl_answer_from {
+    ($tp:ty, $variant:ident) => (
+        impl From<$tp> for Answer {
+            fn from(answer: $tp) -> Self {
+                Answer::$variant(answer)
+            }
+        }
+    )
+}
+
+impl_answer_from!(usize, Usize);
+impl_answer_from!(isize, Isize);
+impl_answer_from!(u32, U32);
+impl_answer_from!(u64, U64);
+impl_answer_from!(i64, I64);
+impl_answer_from!(f64, F64);
+impl_answer_from!(String, Str);
+
+// for parts that have no answer on some inputs
+impl<T: Into<Answer>> From<Option<T>> for Answer {
+    fn from(answer: Option<T>) -> Self {
+        answer.map_or(Answer::None, Into::into)
+    }
+}
+
+// values a puzzle gives in its text rather than in the input, by name. they differ between the
+// example and the real inputs, like the row day 15 asks about
+pub type Params = BTreeMap<String, String>;
+
+// a day's puzzle: the input gets parsed once, and both parts are solved from the parsed input.
+// parsing fails on inputs that don't look like a puzzle input of the day, the parts expect
+// the parsed input to be a valid one
+pub trait Solution {
+    // number of parts, day 25 only has a single one
+    const PARTS: u8 = 2;
+
+    type Parsed<'a>;
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, ParseError>;
+
+    // days without parameters only need the input
+    fn parse_with<'a>(input: &'a str, _params: &Params) -> Result<Self::Parsed<'a>, ParseError> {
+        Self::parse(input)
+    }
+
+    fn part_1(parsed: &Self::Parsed<'_>) -> Answer;
+    fn part_2(parsed: &Self::Parsed<'_>) -> Answer;
+}
+
+// object safe counterpart of Solution, so different days can be stored side by side
+pub trait Runner: Sync {
+    fn parts(&self) -> u8;
+    fn parse_with<'a>(&self, input: &'a str, params: &Params) -> Result<Box<dyn Parsed + 'a>, ParseError>;
+
+    // with the parameters of the real inputs
+    fn parse<'a>(&self, input: &'a str) -> Result<Box<dyn Parsed + 'a>, ParseError> {
+        self.parse_with(input, &Params::new())
+    }
+}
+
+pub trait Parsed {
+    fn solve(&self, part: u8) -> Answer;
+}
+
+pub struct Registered<S>(pub PhantomData<S>);
+
+struct ParsedInput<'a, S: Solution>(S::Parsed<'a>);
+
+impl<S: Solution + Sync + 'static> Runner for Registered<S> {
+    fn parts(&self) -> u8 {
+        S::PARTS
+    }
+
+    fn parse_with<'a>(&self, input: &'a str, params: &Params) -> Result<Box<dyn Parsed + 'a>, ParseError> {
+        Ok(Box::new(ParsedInput::<S>(S::parse_with(input, params)?)))
+    }
+}
+
+impl<S: Solution> Parsed for ParsedInput<'_, S> {
+    fn solve(&self, part: u8) -> Answer {
+        match part {
+            1 => S::part_1(&self.0),
+            2 => S::part_2(&self.0),
+            _ => unreachable!(),
+        }
+    }
+}
+
+#[test]
+fn test_answer() {
+    assert_eq!(Answer::from(3_usize).to_string(), "3");
+    assert_eq!(Answer::from(3099532691300.0).to_string(), "3099532691300");
+    assert_eq!(Answer::from("CWMTGHBDW".to_string()).to_string(), "CWMTGHBDW");
+    assert_eq!(Answer::from(-5_i64), Answer::I64(-5));
+    assert_eq!(Answer::from(3_u32).kind(), "u32");
+    assert_eq!(Answer::from("PZULBAUA".to_string()).kind(), "string");
+
+    for answer in [Answer::from(3099532691300.0), Answer::from(-5_i64), Answer::from("A B".to_string()), Answer::from(None::<u32>)] {
+        assert_eq!(Answer::parse(answer.kind(), &answer.to_string()), Some(answer));
+    }
+    // no answer is its own kind, not a string that happens to read "none"
+    assert_eq!(Answer::from(None::<usize>).kind(), "none");
+    assert_eq!(Answer::parse("string", "none"), Some(Answer::Str("none".to_string())));
+    assert_eq!(Answer::parse("none", "0"), None);
+    assert_eq!(Answer::parse("u32", "-1"), None);
+    assert_eq!(Answer::parse("u128", "1"), None);
+}
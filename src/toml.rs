@@ -0,0 +1,107 @@
+// the tiny subset of toml used by the files the runner keeps next to the inputs:
+// one [dNN] table per day, holding `key = value` pairs
+//
+// [d01]
+// part_1 = "67450"
+
+pub(crate) struct Entry<'a> {
+    pub(crate) line: usize,
+    pub(crate) day: u8,
+    pub(crate) key: &'a str,
+    pub(crate) value: &'a str,
+}
+
+pub(crate) fn parse(content: &str) -> Result<Vec<Entry<'_>>, String> {
+    let mut entries = Vec::new();
+    let mut day = None;
+
+    for (line, text) in content.lines().enumerate().map(|(n, l)| (n + 1, l.trim())) {
+        if text.is_empty() || text.starts_with('#') {
+            continue;
+        }
+
+        if let Some(table) = text.strip_prefix('[').and_then(|t| t.strip_suffix(']')) {
+            day = Some(table.trim().strip_prefix('d')
+                .and_then(|d| d.parse::<u8>().ok())
+                .ok_or(format!("line {}: unknown table '{}', expected e.g. [d07]", line, table))?);
+            continue;
+        }
+
+        let day = day.ok_or(format!("line {}: value outside of a [dNN] table", line))?;
+        let (key, value) = text.split_once('=').ok_or(format!("line {}: expected key = value", line))?;
+        entries.push(Entry { line, day, key: key.trim(), value: value.trim() });
+    }
+
+    Ok(entries)
+}
+
+// expects the entries to be sorted by day
+pub(crate) fn write<K: std::fmt::Display, V: std::fmt::Display>(entries: impl Iterator<Item = (u8, K, V)>) -> String {
+    let mut toml = String::new();
+    let mut current_day = None;
+
+    for (day, key, value) in entries {
+        if current_day != Some(day) {
+            if current_day.is_some() {
+                toml.push('\n');
+            }
+            toml += &format!("[d{:02}]\n", day);
+            current_day = Some(day);
+        }
+        toml += &format!("{} = {}\n", key, value);
+    }
+
+    toml
+}
+
+pub(crate) fn quote(s: &str) -> String {
+    let mut quoted = String::from('"');
+    for c in s.chars() {
+        match c {
+            '"' => quoted += "\\\"",
+            '\\' => quoted += "\\\\",
+            '\n' => quoted += "\\n",
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+pub(crate) fn unquote(s: &str) -> Option<String> {
+    let inner = s.strip_prefix('"')?.strip_suffix('"')?;
+    let mut unquoted = String::new();
+    let mut chars = inner.chars();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => unquoted.push(match chars.next()? {
+                '"' => '"',
+                '\\' => '\\',
+                'n' => '\n',
+                _ => return None,
+            }),
+            '"' => return None,
+            c => unquoted.push(c),
+        }
+    }
+
+    Some(unquoted)
+}
+
+#[test]
+fn test_toml() {
+    let toml = write([(1, "a", 1), (1, "b", 2), (3, "a", 3)].into_iter());
+    assert_eq!(toml, "[d01]\na = 1\nb = 2\n\n[d03]\na = 3\n");
+
+    let entries = parse(&toml).unwrap();
+    assert_eq!(entries.iter().map(|e| (e.line, e.day, e.key, e.value)).collect::<Vec<_>>(),
+        vec![(2, 1, "a", "1"), (3, 1, "b", "2"), (6, 3, "a", "3")]);
+
+    assert!(parse("a = 1").is_err());
+    assert!(parse("[day1]\na = 1").is_err());
+    assert!(parse("[d01]\na").is_err());
+
+    assert_eq!(unquote(&quote("a \"quoted\"\nscreen\\")).as_deref(), Some("a \"quoted\"\nscreen\\"));
+    assert_eq!(unquote("1"), None);
+}
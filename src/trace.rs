@@ -0,0 +1,149 @@
+// spans around the phases of the solvers for --trace, written as folded stacks for flamegraph tools
+// or as a Chrome trace for chrome://tracing and Perfetto. spans cost a single check while tracing is off
+use std::{
+    borrow::Cow,
+    cell::RefCell,
+    collections::BTreeMap,
+    fmt::Write,
+    sync::{atomic::{AtomicBool, AtomicU64, Ordering::Relaxed}, Mutex, OnceLock},
+    time::{Duration, Instant},
+};
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+static START: OnceLock<Instant> = OnceLock::new();
+static EVENTS: Mutex<Vec<Event>> = Mutex::new(Vec::new());
+static NEXT_THREAD: AtomicU64 = AtomicU64::new(1);
+
+thread_local! {
+    // the names of the spans open on this thread, outermost first
+    static STACK: RefCell<Vec<Cow<'static, str>>> = const { RefCell::new(Vec::new()) };
+    static THREAD: u64 = NEXT_THREAD.fetch_add(1, Relaxed);
+}
+
+// a finished span
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Event {
+    // the names of the enclosing spans on the same thread and the span's own name last
+    pub stack: Vec<Cow<'static, str>>,
+    pub thread: u64,
+    // since tracing was enabled
+    pub start: Duration,
+    pub duration: Duration,
+}
+
+impl Event {
+    pub fn name(&self) -> &str {
+        self.stack.last().map_or("", |name| name)
+    }
+}
+
+pub fn enable() {
+    START.get_or_init(Instant::now);
+    ENABLED.store(true, Relaxed);
+}
+
+// the spans finished so far, tracing goes on
+pub fn take() -> Vec<Event> {
+    std::mem::take(&mut *EVENTS.lock().unwrap_or_else(|e| e.into_inner()))
+}
+
+// ends when dropped, bind it to a name like `_span` as `_` drops it right away
+#[must_use]
+pub struct Span(Option<Instant>);
+
+pub fn span(name: impl Into<Cow<'static, str>>) -> Span {
+    if !ENABLED.load(Relaxed) {
+        return Span(None);
+    }
+    STACK.with(|stack| stack.borrow_mut().push(name.into()));
+    Span(Some(Instant::now()))
+}
+
+impl Drop for Span {
+    fn drop(&mut self) {
+        let Some(start) = self.0 else { return };
+        let duration = start.elapsed();
+        let stack = STACK.with(|stack| {
+            let mut stack = stack.borrow_mut();
+            let event = stack.clone();
+            stack.pop();
+            event
+        });
+        let start = start.duration_since(*START.get_or_init(Instant::now));
+        let event = Event { stack, thread: THREAD.with(|thread| *thread), start, duration };
+        EVENTS.lock().unwrap_or_else(|e| e.into_inner()).push(event);
+    }
+}
+
+// one line per stack like "day 16;part 1;find_max 1234" with the microseconds spent in the span
+// itself and not in the spans within it, the format flamegraph.pl and inferno read
+pub fn folded(events: &[Event]) -> String {
+    let mut total = BTreeMap::<String, i128>::new();
+    for event in events {
+        *total.entry(event.stack.join(";")).or_default() += event.duration.as_micros() as i128;
+    }
+    let mut own = total.clone();
+    for event in events.iter().filter(|event| event.stack.len() > 1) {
+        let parent = event.stack[..event.stack.len() - 1].join(";");
+        *own.entry(parent).or_default() -= event.duration.as_micros() as i128;
+    }
+
+    own.iter()
+        .filter(|(_, micros)| **micros > 0)
+        .fold(String::new(), |mut folded, (stack, micros)| {
+            let _ = writeln!(folded, "{} {}", stack, micros);
+            folded
+        })
+}
+
+// the trace event format with a complete event for every span, times in microseconds
+pub fn chrome(events: &[Event]) -> String {
+    let events = events.iter()
+        .map(|event| format!(
+            "  {{\"name\": {}, \"ph\": \"X\", \"ts\": {:.3}, \"dur\": {:.3}, \"pid\": 1, \"tid\": {}}}",
+            crate::report::json_string(event.name()),
+            event.start.as_secs_f64() * 1e6,
+            event.duration.as_secs_f64() * 1e6,
+            event.thread,
+        ))
+        .collect::<Vec<_>>();
+    format!("{{\"traceEvents\": [\n{}\n]}}\n", events.join(",\n"))
+}
+
+#[cfg(test)]
+fn test_events() -> Vec<Event> {
+    let event = |stack: &[&'static str], start, duration| Event {
+        stack: stack.iter().map(|name| Cow::Borrowed(*name)).collect(),
+        thread: 1,
+        start: Duration::from_micros(start),
+        duration: Duration::from_micros(duration),
+    };
+    vec![event(&["day 24", "part 2", "a_star"], 10, 30), event(&["day 24", "part 2", "a_star"], 40, 50), event(&["day 24", "part 2"], 5, 100)]
+}
+
+#[test]
+fn test_folded() {
+    assert_eq!(folded(&test_events()), "day 24;part 2 20\nday 24;part 2;a_star 80\n");
+}
+
+#[test]
+fn test_chrome() {
+    assert_eq!(chrome(&test_events()[..1]), "{\"traceEvents\": [
+  {\"name\": \"a_star\", \"ph\": \"X\", \"ts\": 10.000, \"dur\": 30.000, \"pid\": 1, \"tid\": 1}
+]}
+");
+}
+
+#[test]
+fn test_span() {
+    enable();
+    {
+        let _outer = span("test_span outer");
+        let _inner = span(format!("test_span {}", "inner"));
+    }
+    let events = take().into_iter().filter(|event| event.stack[0] == "test_span outer").collect::<Vec<_>>();
+    assert_eq!(events.len(), 2);
+    assert_eq!(events[0].stack, vec!["test_span outer", "test_span inner"]);
+    assert_eq!(events[1].name(), "test_span outer");
+    assert!(events[1].duration >= events[0].duration);
+}
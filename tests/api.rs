@@ -0,0 +1,87 @@
+use aoc22::d06::Day06;
+use aoc22::d08::{self, Day08};
+use aoc22::d14::{self, Cave};
+use aoc22::helpers::BitGrid;
+use aoc22::registry;
+use aoc22::solution::{Answer, Solution};
+
+static D08_EXAMPLE: &str = "30373
+25512
+65332
+33549
+35390";
+
+#[test]
+fn test_solve_with_solution_trait() {
+    let grid = Day08::parse(D08_EXAMPLE).unwrap();
+    assert_eq!(grid.get(0, 0), Some(3));
+    assert_eq!(grid.get(4, 4), Some(0));
+    assert_eq!(Day08::part_1(&grid), Answer::Usize(21));
+    assert_eq!(Day08::part_2(&d08::parse(D08_EXAMPLE).unwrap()), Answer::Usize(8));
+}
+
+#[test]
+fn test_solve_with_registry() {
+    let entry = registry::default_for(7).unwrap();
+    let parsed = entry.runner.parse(include_str!("../data/d07_test.txt")).unwrap();
+    assert_eq!(parsed.solve(1).to_string(), "95437");
+    assert_eq!(parsed.solve(2).to_string(), "24933642");
+}
+
+#[test]
+fn test_grid_types() {
+    let cave = Cave::new(&d14::parse(include_str!("../data/d14_test.txt")).unwrap());
+    // 498,4 -> 498,6 -> 496,6 shifted by the cave's offset of 250 columns
+    assert!(cave[(248, 4)]);
+    assert!(!cave[(248, 3)]);
+
+    let mut grid = BitGrid::new(64, 64);
+    grid.set(3, 5);
+    assert!(grid.get(3, 5));
+    assert_eq!(grid.count(), 1);
+}
+
+#[test]
+fn test_parse_errors() {
+    // input meant for another day is rejected instead of panicking
+    for entry in registry::REGISTRY {
+        assert!(entry.runner.parse("not a puzzle input\n").is_err(), "day {} accepted garbage", entry.day);
+    }
+
+    // inputs that parse into nothing the parts can work with
+    for (day, input) in [(6, "x\n"), (6, "abcabcabcabcabcabc\n"), (15, ""), (18, "")] {
+        assert!(registry::default_for(day).unwrap().runner.parse(input).is_err(), "day {} accepted {:?}", day, input);
+    }
+    // a listing that stops early is still a file system, one that needs nothing deleted
+    for entry in registry::implementations(7) {
+        let parsed = entry.runner.parse("$ cd /\n$ ls\ndir a\n").unwrap();
+        assert_eq!(parsed.solve(2), Answer::Usize(0), "{}", entry.name);
+    }
+
+    let err = Day06::parse("abcabcabcabcabcabc\n").err().unwrap();
+    assert_eq!(err.to_string(), "line 1, column 19: expected a marker of 14 different letters, found end of line");
+
+    let err = Day08::parse("303\n2x5\n653\n").err().unwrap();
+    assert_eq!(err.to_string(), "line 2, column 2: expected a tree height from 0 to 9, found 'x'");
+
+    // a monkey testing divisibility by 0 would divide by zero on its first item
+    let input = include_str!("../data/d11_test.txt").replacen("divisible by 23", "divisible by 0", 1);
+    let err = registry::default_for(11).unwrap().runner.parse(&input).err().unwrap();
+    assert_eq!(err.to_string(), "line 4, column 22: expected a divisor other than 0, found '0'");
+
+    // a program running past the 240 cycles of the screen draws nothing more
+    let input = "noop\n".repeat(300);
+    let parsed = registry::default_for(10).unwrap().runner.parse(&input).unwrap();
+    assert!(parsed.solve(2).to_string().starts_with("###."));
+}
+
+#[test]
+fn test_unreachable() {
+    // a goal out of reach is no answer rather than a made up number or a panic
+    for (day, input) in [(12, "SbE\n"), (24, "#.#\n#v#\n#.#\n")] {
+        let parsed = registry::default_for(day).unwrap().runner.parse(input).unwrap();
+        for part in 1..=2 {
+            assert_eq!(parsed.solve(part), Answer::None, "day {} part {}", day, part);
+        }
+    }
+}
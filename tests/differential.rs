@@ -0,0 +1,38 @@
+// every implementation of a day has to give the same answers as the others, on the inputs in the
+// data directory that compare uses as well and on generated inputs
+use std::path::Path;
+
+use aoc22::compare::{self, compare};
+use aoc22::{generate, registry};
+
+#[test]
+fn test_implementations_agree() {
+    let data_dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("data");
+    let mut disagreements = Vec::new();
+    let mut compared = 0;
+
+    for day in 1..=25 {
+        if registry::implementations(day).count() < 2 {
+            continue;
+        }
+        let mut inputs = Vec::new();
+        for path in compare::inputs(&data_dir, day).unwrap() {
+            inputs.push((path.display().to_string(), std::fs::read_to_string(&path).unwrap()));
+        }
+        if let Some(generator) = generate::for_day(day) {
+            // the default implementation of day 23 takes minutes on grids larger than a real input in a debug build
+            let max_size = if day == 23 { generator.default_size } else { 300 };
+            for (seed, size) in (0..10).zip([1, 2, 3, 5, 10, 20, 50, 100, 200, 300]).filter(|(_, size)| *size <= max_size) {
+                inputs.push((format!("seed {} size {}", seed, size), generator.generate(seed, size)));
+            }
+        }
+
+        for (source, input) in &inputs {
+            disagreements.extend(compare(day, input).into_iter().map(|disagreement| format!("{}: {}", source, disagreement)));
+            compared += 1;
+        }
+    }
+
+    assert!(disagreements.is_empty(), "{} disagreements:\n{}", disagreements.len(), disagreements.join("\n"));
+    assert!(compared > 0);
+}
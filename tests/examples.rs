@@ -0,0 +1,63 @@
+// runs every solution against the examples from the puzzle descriptions.
+// data/dNN_test.txt is checked against data/answers_test.toml, further examples like data/dNN_test_2.txt
+// against data/answers_test_2.toml, the same way data/dNN.txt belongs to data/answers.toml.
+// a new case only needs its input file and the expected answers, plus the parameters the puzzle gives
+// outside of the input, like param.row of day 15.
+use std::path::{Path, PathBuf};
+
+use aoc22::answers::Answers;
+use aoc22::registry;
+
+fn data_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("data")
+}
+
+// the day and example name of a file like d09_test_2.txt
+fn example(file_name: &str) -> Option<(u8, &str)> {
+    let (day, name) = file_name.strip_prefix('d')?.strip_suffix(".txt")?.split_once('_')?;
+    Some((day.parse().ok()?, name))
+}
+
+#[test]
+fn test_examples() {
+    let mut checked = 0;
+    let mut failures = Vec::new();
+
+    for file in std::fs::read_dir(data_dir()).unwrap() {
+        let path = file.unwrap().path();
+        let file_name = path.file_name().unwrap().to_str().unwrap();
+        let Some((day, name)) = example(file_name) else { continue };
+        let answers = Answers::load(&data_dir().join(format!("answers_{}.toml", name))).unwrap();
+        let input = std::fs::read_to_string(&path).unwrap();
+
+        // every implementation of the day has to agree with the examples
+        for entry in registry::REGISTRY.iter().filter(|e| e.day == day) {
+            let parsed = entry.runner.parse_with(&input, &answers.params(day)).unwrap_or_else(|e| panic!("{}: {}", file_name, e.in_day(day)));
+            for part in 1..=entry.runner.parts() {
+                // some examples only illustrate the puzzle and have no answer
+                let Some(expected) = answers.get(day, part) else { continue };
+                let answer = parsed.solve(part).to_string();
+                if answer != expected {
+                    failures.push(format!("{} part {}: expected {:?}, got {:?}", file_name, part, expected, answer));
+                }
+                checked += 1;
+            }
+        }
+    }
+
+    assert!(failures.is_empty(), "{} of {} example answers are wrong:\n{}", failures.len(), checked, failures.join("\n"));
+    assert!(checked > 0);
+}
+
+#[test]
+fn test_every_day_has_an_example() {
+    let answers = Answers::load(&data_dir().join("answers_test.toml")).unwrap();
+
+    for entry in registry::REGISTRY {
+        assert!(data_dir().join(format!("d{:02}_test.txt", entry.day)).exists(), "day {} has no example input", entry.day);
+        for part in 1..=entry.runner.parts() {
+            assert!(answers.get(entry.day, part).is_some(), "day {} part {} has no example answer", entry.day, part);
+        }
+    }
+}
+
@@ -0,0 +1,37 @@
+// every film has to record its example, with frames of a single size that its palette colors
+use aoc22::frames::{self, FILMS};
+
+#[test]
+fn test_films() {
+    for film in FILMS {
+        let input = std::fs::read_to_string(format!("data/d{:02}_test.txt", film.day)).unwrap();
+        let recorded = film.record(&input, 1, 50).unwrap_or_else(|e| panic!("day {}: {}", film.day, e));
+        assert!(recorded.len() > 1 && recorded.len() <= 50, "day {}: {} frames", film.day, recorded.len());
+        let (width, height) = (recorded[0].width(), recorded[0].height());
+        assert!(width > 0 && height > 0, "day {}", film.day);
+        assert!(recorded.iter().all(|f| (f.width(), f.height()) == (width, height)), "day {}", film.day);
+        assert!(recorded.windows(2).any(|w| w[0] != w[1]), "day {}: nothing moves", film.day);
+
+        let gif = frames::gif(&recorded, film.palette, 2, std::time::Duration::from_millis(100)).unwrap();
+        assert!(gif.starts_with(b"GIF89a") && gif.ends_with(b";"), "day {}", film.day);
+    }
+}
+
+#[test]
+fn test_last_frame() {
+    // the way to the goal of day 12 is 31 steps long, with 30 squares between the start and the goal
+    let input = std::fs::read_to_string("data/d12_test.txt").unwrap();
+    let recorded = frames::for_day(12).unwrap().record(&input, 1, 100).unwrap();
+    let last = recorded.last().unwrap();
+    let path = last.rows().flatten().filter(|c| **c == '*').count();
+    assert_eq!(path, 30);
+
+    // the sand of day 14 comes to rest 24 times, all of it in the last frame
+    let input = std::fs::read_to_string("data/d14_test.txt").unwrap();
+    let recorded = frames::for_day(14).unwrap().record(&input, 5, 100).unwrap();
+    assert_eq!(recorded.last().unwrap().rows().flatten().filter(|c| **c == 'o').count(), 24);
+
+    // the wind in the only tile of this valley never lets the expedition in
+    let recorded = frames::for_day(24).unwrap().record("#.#\n#v#\n#.#\n", 1, 100).unwrap();
+    assert_eq!(recorded.len(), 1);
+}
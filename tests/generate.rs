@@ -0,0 +1,29 @@
+// every generated input has to parse and solve, at several seeds and sizes
+use aoc22::generate::{self, GENERATORS};
+use aoc22::registry;
+
+#[test]
+fn test_generated_inputs_solve() {
+    for generator in GENERATORS {
+        for (seed, size) in [(0, 1), (1, 3), (2, 10), (3, 50)] {
+            let input = generator.generate(seed, size);
+            for entry in registry::REGISTRY.iter().filter(|e| e.day == generator.day) {
+                let parsed = entry.runner.parse(&input)
+                    .unwrap_or_else(|e| panic!("seed {} size {}: {}\n{}", seed, size, e.in_day(generator.day), input));
+                for part in 1..=entry.runner.parts() {
+                    parsed.solve(part);
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn test_generators_are_seeded() {
+    for generator in GENERATORS {
+        assert_eq!(generator.generate(5, 20), generator.generate(5, 20), "day {}", generator.day);
+        assert_ne!(generator.generate(5, 20), generator.generate(6, 20), "day {}", generator.day);
+    }
+    assert!(generate::for_day(21).is_some());
+    assert!(generate::for_day(1).is_none());
+}
@@ -0,0 +1,24 @@
+// the counting allocator only counts inside memory::measure
+use aoc22::memory::{self, Counting};
+
+#[global_allocator]
+static ALLOCATOR: Counting = Counting;
+
+#[test]
+fn test_measure() {
+    let (v, usage) = memory::measure(|| {
+        let v = Vec::<u8>::with_capacity(1000);
+        let scratch = vec![0_u8; 4000];
+        drop(scratch);
+        v
+    });
+    assert_eq!(v, Vec::<u8>::new());
+    assert!(usage.allocations >= 2);
+    assert!(usage.bytes >= 5000);
+    assert!(usage.peak >= 5000);
+    assert!(usage.retained >= 1000);
+
+    let (_, usage) = memory::measure(|| drop(v));
+    assert_eq!((usage.allocations, usage.bytes, usage.peak), (0, 0, 0));
+    assert!(usage.retained <= -1000);
+}
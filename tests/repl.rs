@@ -0,0 +1,46 @@
+// every explorer has to load its example and answer every command on it, before and after stepping
+use aoc22::repl::{Repl, EXPLORERS};
+
+#[test]
+fn test_explorers() {
+    for explorer in EXPLORERS {
+        let input = std::fs::read_to_string(format!("data/d{:02}_test.txt", explorer.day)).unwrap();
+        let mut repl = Repl::new(explorer, &input).unwrap_or_else(|e| panic!("day {}: {}", explorer.day, e));
+        let queries = explorer.queries.iter().map(|(query, _)| query.split(' ').next().unwrap()).collect::<Vec<_>>();
+
+        for command in ["help", "show", "step 5", "show"].into_iter().chain(queries.iter().copied()) {
+            let reply = repl.eval(command).unwrap();
+            assert!(!reply.starts_with("unknown command"), "day {}: {}: {}", explorer.day, command, reply);
+        }
+        assert!(!repl.eval("show").unwrap().is_empty(), "day {}", explorer.day);
+    }
+}
+
+#[test]
+fn test_step() {
+    let input = std::fs::read_to_string("data/d17_test.txt").unwrap();
+    let mut repl = Repl::new(aoc22::repl::for_day(17).unwrap(), &input).unwrap();
+    repl.eval("step 2022");
+    assert_eq!(repl.eval("height"), Some("3068".to_string()));
+
+    let input = std::fs::read_to_string("data/d14_test.txt").unwrap();
+    let mut repl = Repl::new(aoc22::repl::for_day(14).unwrap(), &input).unwrap();
+    assert_eq!(repl.eval("step 100"), Some("24 units of sand at rest, the next one falls into the abyss".to_string()));
+
+    let input = std::fs::read_to_string("data/d23_test.txt").unwrap();
+    let mut repl = Repl::new(aoc22::repl::for_day(23).unwrap(), &input).unwrap();
+    repl.eval("step 10");
+    assert_eq!(repl.eval("empty"), Some("110".to_string()));
+    assert_eq!(repl.eval("step 100"), Some("after round 20, in which no elf moved".to_string()));
+
+    let input = std::fs::read_to_string("data/d24_test.txt").unwrap();
+    let mut repl = Repl::new(aoc22::repl::for_day(24).unwrap(), &input).unwrap();
+    assert_eq!(repl.eval("fastest"), Some("18 minutes, arriving in minute 18".to_string()));
+    let mut repl = Repl::new(aoc22::repl::for_day(24).unwrap(), "#.#\n#v#\n#.#\n").unwrap();
+    assert_eq!(repl.eval("fastest"), Some("error: the winds block every way to the exit".to_string()));
+
+    let input = std::fs::read_to_string("data/d09_test_2.txt").unwrap();
+    let mut repl = Repl::new(aoc22::repl::for_day(9).unwrap(), &input).unwrap();
+    assert_eq!(repl.eval("step 1000"), Some("all moves done".to_string()));
+    assert_eq!(repl.eval("visited"), Some("36".to_string()));
+}